@@ -0,0 +1,46 @@
+//! Building a 1k edge graph the way `graph!` and `static_graph!` expand.
+//!
+//! `graph!` adds the nodes and connects them one by one, which checks the policy for every edge. `static_graph!` is a single `from_raw_parts` call.
+//! The edges are generated here because a 1k edge literal would not fit in this file.
+//!
+//! Run with `cargo bench --bench construction`
+use criterion::{criterion_group, criterion_main, Criterion};
+use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+
+const NODES: usize = 200;
+const EDGES: usize = 1_000;
+
+/// Every node connected to the next five, wrapping around.
+fn edges() -> Vec<(NodeID, NodeID, u32)> {
+    (0..EDGES)
+        .map(|index| {
+            let a = index / 5;
+            let b = (a + index % 5 + 1) % NODES;
+            (NodeID(a), NodeID(b), index as u32)
+        })
+        .collect()
+}
+
+fn construction(c: &mut Criterion) {
+    let edges = edges();
+    let mut group = c.benchmark_group("build_1k_edges");
+    group.bench_function("connect_nodes", |b| {
+        b.iter(|| {
+            let mut graph = AdjListGraph::default();
+            let ids = graph.add_nodes_from_iterator(0..NODES);
+            for (a, b, weight) in &edges {
+                graph
+                    .connect_nodes_with_weight(ids[a.0], ids[b.0], *weight)
+                    .unwrap();
+            }
+            graph
+        })
+    });
+    group.bench_function("from_raw_parts", |b| {
+        b.iter(|| AdjListGraph::from_raw_parts((0..NODES).collect(), edges.clone()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, construction);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+//! Compares the sequential and parallel algorithms with 1, 2, 4 and 8 threads.
+//!
+//! Run with `cargo bench --features rayon`
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+
+const NODES: usize = 200_000;
+const EDGES: usize = 500_000;
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+
+fn random_graph() -> AdjListGraph<usize> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut graph = AdjListGraph::default();
+    let ids = graph.add_nodes_from_iterator(0..NODES);
+    for _ in 0..EDGES {
+        let a = ids[rng.gen_range(0..NODES)];
+        let b = ids[rng.gen_range(0..NODES)];
+        let _ = graph.connect_nodes_with_weight(a, b, rng.gen_range(0..100));
+    }
+    graph
+}
+
+fn connected_components(c: &mut Criterion) {
+    let graph = random_graph();
+    let mut group = c.benchmark_group("connected_components");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| b.iter(|| graph.connected_components()));
+    for threads in THREADS {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("parallel", threads), &threads, |b, _| {
+            b.iter(|| pool.install(|| graph.connected_components_parallel()))
+        });
+    }
+    group.finish();
+}
+
+fn bfs_distances(c: &mut Criterion) {
+    let graph = random_graph();
+    let mut group = c.benchmark_group("bfs_distances");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter(|| graph.bfs_distances(NodeID(0)).unwrap())
+    });
+    for threads in THREADS {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("parallel", threads), &threads, |b, _| {
+            b.iter(|| pool.install(|| graph.bfs_distances_parallel(NodeID(0)).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, connected_components, bfs_distances);
+criterion_main!(benches);
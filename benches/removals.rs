@@ -0,0 +1,98 @@
+//! Operations on a graph after 100k node removals.
+//!
+//! Every dead slot check used to scan the whole empty slot queue, so these were O(live · dead).
+//!
+//! Run with `cargo bench --bench removals`
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+
+const NODES: usize = 200_000;
+const REMOVED: usize = 100_000;
+
+/// A path of `NODES` nodes with every other node removed.
+fn churned_graph() -> AdjListGraph<usize> {
+    let mut graph = AdjListGraph::default();
+    let ids = graph.add_nodes_from_iterator(0..NODES);
+    for pair in ids.windows(2) {
+        graph.connect_nodes(pair[0], pair[1]).unwrap();
+    }
+    for id in ids.iter().step_by(NODES / REMOVED) {
+        graph.remove_node(*id);
+    }
+    graph
+}
+
+fn after_removals(c: &mut Criterion) {
+    let mut group = c.benchmark_group("after_100k_removals");
+    group.sample_size(10);
+    group.bench_function("remove", |b| b.iter(churned_graph));
+    let graph = churned_graph();
+    group.bench_function("node_ids", |b| b.iter(|| graph.node_ids().count()));
+    group.bench_function("validate", |b| b.iter(|| graph.validate()));
+    group.bench_function("connected_components", |b| {
+        b.iter(|| graph.connected_components())
+    });
+    group.bench_function("reuse_slots", |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |mut graph| {
+                for value in 0..REMOVED {
+                    graph.add_node(value);
+                }
+                graph
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+/// Removing 10% of a 100k node graph one by one and as a batch.
+fn batch_removal(c: &mut Criterion) {
+    let mut graph = AdjListGraph::default();
+    let ids = graph.add_nodes_from_iterator(0..100_000usize);
+    for pair in ids.windows(2) {
+        graph.connect_nodes(pair[0], pair[1]).unwrap();
+    }
+    let removed: Vec<NodeID> = ids.into_iter().step_by(10).collect();
+
+    let mut group = c.benchmark_group("remove_10_percent");
+    group.sample_size(10);
+    group.bench_function("remove_node_and_compact", |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |mut graph| {
+                for id in &removed {
+                    graph.remove_node(*id);
+                }
+                graph.remove_dead_values();
+                graph
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("remove_nodes", |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |mut graph| {
+                graph.remove_nodes(&removed);
+                graph
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("remove_nodes_and_compact", |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |mut graph| {
+                graph.remove_nodes_and_compact(&removed);
+                graph
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, after_removals, batch_removal);
+criterion_main!(benches);
@@ -32,6 +32,16 @@ impl Edge {
     pub fn nodes(&self) -> (NodeID, NodeID) {
         (self.node_a, self.node_b)
     }
+    /// Returns the node on the opposite side of the edge.
+    ///
+    /// For a self-loop this is the same node.
+    pub fn other_node(&self, node: NodeID) -> NodeID {
+        if self.node_a == node {
+            self.node_b
+        } else {
+            self.node_a
+        }
+    }
     pub fn node_values<'graph, T>(
         &self,
         graph: &'graph AdjListGraph<T>,
@@ -39,6 +49,43 @@ impl Edge {
         (&graph[self.node_a], &graph[self.node_b])
     }
 }
+/// A live edge and its ID. Returned by [AdjListGraph::edges] and [AdjListGraph::neighbor_edges].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeRef<'g> {
+    id: EdgeID,
+    edge: &'g Edge,
+}
+impl<'g> EdgeRef<'g> {
+    pub(crate) fn new(id: EdgeID, edge: &'g Edge) -> Self {
+        Self { id, edge }
+    }
+    pub fn id(&self) -> EdgeID {
+        self.id
+    }
+    pub fn weight(&self) -> u32 {
+        self.edge.weight
+    }
+    /// The two nodes in the order they were connected.
+    pub fn endpoints(&self) -> (NodeID, NodeID) {
+        self.edge.nodes()
+    }
+    /// The node on the opposite side of the edge. For a self loop this is the same node.
+    pub fn other(&self, node: NodeID) -> NodeID {
+        self.edge.other_node(node)
+    }
+    pub fn is_self_loop(&self) -> bool {
+        self.edge.node_a == self.edge.node_b
+    }
+    /// The values of the two nodes in the order of [EdgeRef::endpoints].
+    ///
+    /// `graph` must be the graph the edge came from.
+    pub fn values<T>(&self, graph: &'g AdjListGraph<T>) -> (&'g T, &'g T) {
+        (
+            graph[self.edge.node_a].value(),
+            graph[self.edge.node_b].value(),
+        )
+    }
+}
 #[derive(Debug, Clone, Copy)]
 pub struct EdgeID(pub(crate) usize);
 
@@ -1,12 +1,41 @@
-use crate::adjacency_list::AdjListGraph;
+use crate::adjacency_list::{AdjListGraph, EdgeID, NodeID};
+use crate::collections::HashSet;
+use crate::prelude::*;
+use crate::GraphError;
 
-use super::FormattedStringBuilder;
+use super::{FormattedStringBuilder, FormattedWriter, WeightFormatter};
+/// What the label of a node shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelStyle {
+    /// The value of the node.
+    #[default]
+    ValueOnly,
+    /// The ID of the node. `3`
+    IdOnly,
+    /// The ID then the value. `3: C`
+    IdAndValue,
+}
 #[derive(Debug, Clone)]
 pub struct GraphizSettings {
     pub layout: String,
     pub overlap: bool,
     pub node_layout: String,
     pub graph_name: String,
+    /// Adds the weight as the label of every edge. Off by default.
+    pub show_weights: bool,
+    pub weight_formatter: WeightFormatter,
+    /// Adds the node IDs to the node labels as `(n3)` and the edge IDs to the edge labels as `e2`.
+    ///
+    /// Useful for matching a diagram with logs that reference IDs.
+    /// The node IDs are only added if [GraphizSettings::label_style] does not already show them.
+    pub show_ids: bool,
+    pub label_style: LabelStyle,
+    /// Adds the edge IDs as the tooltip of every edge. Unlike [GraphizSettings::show_ids] this keeps the labels as they are.
+    pub edge_id_tooltip: bool,
+    /// The attributes added to the edges passed to [export_graphiz_highlight].
+    pub highlight_style: String,
+    /// Scales the nodes between `(min, max)` inches by their degree. The nodes with the most edges get `max`.
+    pub node_size_by_degree: Option<(f64, f64)>,
 }
 impl Default for GraphizSettings {
     fn default() -> Self {
@@ -15,32 +44,524 @@ impl Default for GraphizSettings {
             overlap: false,
             node_layout: "circle".to_string(),
             graph_name: "G".to_string(),
+            show_weights: false,
+            weight_formatter: WeightFormatter::default(),
+            show_ids: false,
+            label_style: LabelStyle::default(),
+            edge_id_tooltip: false,
+            highlight_style: "style=bold, color=red".to_string(),
+            node_size_by_degree: None,
         }
     }
 }
 
 pub fn export_graphiz<T>(graph: &AdjListGraph<T>, settings: &GraphizSettings) -> String
 where
-    T: std::fmt::Display,
+    T: core::fmt::Display,
+{
+    export(graph, settings, None, None)
+}
+/// [export_graphiz] straight into a writer, without building the whole output as a [String] first.
+#[cfg(feature = "std")]
+pub fn write_graphiz<T>(
+    graph: &AdjListGraph<T>,
+    settings: &GraphizSettings,
+    writer: impl std::io::Write,
+) -> std::io::Result<()>
+where
+    T: core::fmt::Display,
+{
+    let mut output = FormattedWriter::io(writer, 4);
+    write(&mut output, graph, settings, None, None);
+    output.finish_io().map(|_| ())
+}
+/// Exports every edge and draws the edges in `highlight_edges` with [GraphizSettings::highlight_style].
+///
+/// Useful for showing a result, like a minimum spanning tree, on top of the original graph.
+pub fn export_graphiz_highlight<T>(
+    graph: &AdjListGraph<T>,
+    settings: &GraphizSettings,
+    highlight_edges: &HashSet<EdgeID>,
+) -> String
+where
+    T: core::fmt::Display,
+{
+    export(graph, settings, Some(highlight_edges), None)
+}
+/// Exports the graph top down. Uses the `dot` layout and puts every BFS layer from `root` in its own rank.
+///
+/// Nodes that can not be reached from `root` are grouped at the end without a rank.
+///
+/// Returns an error if `root` does not exist.
+pub fn export_graphiz_layered<T>(
+    graph: &AdjListGraph<T>,
+    settings: &GraphizSettings,
+    root: NodeID,
+) -> Result<String, GraphError>
+where
+    T: core::fmt::Display,
+{
+    let layers = graph.bfs_layers(root)?;
+    Ok(export(graph, settings, None, Some(&layers)))
+}
+fn export<T>(
+    graph: &AdjListGraph<T>,
+    settings: &GraphizSettings,
+    highlight_edges: Option<&HashSet<EdgeID>>,
+    layers: Option<&[Vec<NodeID>]>,
+) -> String
+where
+    T: core::fmt::Display,
+{
+    let mut output = FormattedStringBuilder::string(4);
+    write(&mut output, graph, settings, highlight_edges, layers);
+    output.into_string()
+}
+fn write<T, W>(
+    output: &mut FormattedWriter<W>,
+    graph: &AdjListGraph<T>,
+    settings: &GraphizSettings,
+    highlight_edges: Option<&HashSet<EdgeID>>,
+    layers: Option<&[Vec<NodeID>]>,
+) where
+    T: core::fmt::Display,
+    W: core::fmt::Write,
+{
+    output.push_fmt(format_args!("graph {} {{", settings.graph_name));
+    output.block(|graphiz| {
+        let layout = if layers.is_some() {
+            "dot"
+        } else {
+            &settings.layout
+        };
+        graphiz.push_fmt(format_args!("layout={layout}"));
+        graphiz.push_fmt(format_args!("overlap={}", settings.overlap));
+        graphiz.push_fmt(format_args!("node [shape={}]", settings.node_layout));
+        write_nodes(graphiz, graph, settings);
+        if let Some(layers) = layers {
+            write_layers(graphiz, graph, layers);
+        }
+        graphiz.push("//  Edges");
+        for (index, edge) in graph.edges.iter().enumerate() {
+            if graph.is_edge_empty(index) {
+                continue;
+            }
+            let weight = settings
+                .show_weights
+                .then(|| settings.weight_formatter.format(edge.weight()));
+            let label = match (settings.show_ids, weight) {
+                (true, Some(weight)) => Some(format!("e{index}: {weight}")),
+                (true, None) => Some(format!("e{index}")),
+                (false, weight) => weight,
+            };
+            let mut attributes: Vec<String> = label
+                .map(|label| format!("label=\"{}\"", escape_dot_label(&label)))
+                .into_iter()
+                .collect();
+            if settings.edge_id_tooltip {
+                attributes.push(format!("tooltip=\"e{index}\""));
+            }
+            if highlight_edges.is_some_and(|highlight| highlight.contains(&EdgeID(index))) {
+                attributes.push(settings.highlight_style.clone());
+            }
+            let label = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            graphiz.push_fmt(format_args!(
+                "{node_a} -- {node_b}{label};",
+                node_a = edge.node_a.0,
+                node_b = edge.node_b.0
+            ));
+        }
+    });
+    output.push("}");
+}
+fn write_nodes<T, W>(
+    graphiz: &mut FormattedWriter<W>,
+    graph: &AdjListGraph<T>,
+    settings: &GraphizSettings,
+) where
+    T: core::fmt::Display,
+    W: core::fmt::Write,
 {
-    let mut graphiz = FormattedStringBuilder::new(format!("graph {} {{\n", settings.graph_name), 4);
-    graphiz.push(format!("layout={}", settings.layout));
-    graphiz.push(format!("overlap={}", settings.overlap));
-    graphiz.push(format!("node [shape={}]", settings.node_layout));
     graphiz.push("//  Nodes");
+    let degrees = settings.node_size_by_degree.map(|sizes| {
+        let histogram = graph.degree_histogram();
+        let min_degree = histogram.iter().position(|count| *count != 0).unwrap_or(0);
+        (sizes, min_degree, histogram.len().saturating_sub(1))
+    });
     for (index, node) in graph.nodes.iter().enumerate() {
         if let Some(value) = node.optional_value() {
-            graphiz.push(format!("{{node [label=\"{value}\"] {index}}};"))
+            let label = match settings.label_style {
+                LabelStyle::ValueOnly if settings.show_ids => format!("{value} (n{index})"),
+                LabelStyle::ValueOnly => value.to_string(),
+                LabelStyle::IdOnly => index.to_string(),
+                LabelStyle::IdAndValue => format!("{index}: {value}"),
+            };
+            let mut attributes = vec![format!("label=\"{}\"", escape_dot_label(&label))];
+            if let Some(((min_size, max_size), min_degree, max_degree)) = degrees {
+                let size = if max_degree == min_degree {
+                    min_size
+                } else {
+                    let scale =
+                        (node.edges.len() - min_degree) as f64 / (max_degree - min_degree) as f64;
+                    min_size + (max_size - min_size) * scale
+                };
+                attributes.push(format!("width={size:.2}, height={size:.2}, fixedsize=true"));
+            }
+            graphiz.push_fmt(format_args!(
+                "{{node [{attributes}] {index}}};",
+                attributes = attributes.join(", ")
+            ))
+        }
+    }
+}
+/// Escapes a label for a quoted DOT string. Quotes and backslashes are escaped and line breaks become `\n`.
+fn escape_dot_label(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for char in label.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            char => escaped.push(char),
         }
     }
-    graphiz.push("//  Edges");
-    for edge in &graph.edges {
-        graphiz.push(format!(
-            "{node_a} -- {node_b};",
-            node_a = edge.node_a.0,
-            node_b = edge.node_b.0
-        ));
+    escaped
+}
+fn write_layers<T, W>(
+    graphiz: &mut FormattedWriter<W>,
+    graph: &AdjListGraph<T>,
+    layers: &[Vec<NodeID>],
+) where
+    W: core::fmt::Write,
+{
+    graphiz.push("//  Layers");
+    let mut reached = vec![false; graph.nodes.len()];
+    for layer in layers {
+        let mut group = String::from("{rank=same;");
+        for node in layer {
+            reached[node.0] = true;
+            group.push_str(&format!(" {};", node.0));
+        }
+        group.push('}');
+        graphiz.push(group);
+    }
+    let unreachable: Vec<NodeID> = graph.node_ids().filter(|node| !reached[node.0]).collect();
+    if !unreachable.is_empty() {
+        graphiz.push("//  Unreachable");
+        let nodes: Vec<String> = unreachable
+            .iter()
+            .map(|node| format!("{};", node.0))
+            .collect();
+        graphiz.push_fmt(format_args!("{{{}}}", nodes.join(" ")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tux_graph_macros::graph_no_import;
+
+    use super::*;
+    use crate::testing::assert_round_trips_dot;
+    use crate::testing::golden::{assert_matches_golden, golden_path, unified_diff};
+
+    #[test]
+    pub fn weights_with_units() {
+        let graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+
+            a -- b [weight = 12];
+        };
+        let settings = GraphizSettings {
+            show_weights: true,
+            weight_formatter: WeightFormatter::new(|weight| format!("{weight}ms")),
+            ..Default::default()
+        };
+        assert_matches_golden(
+            "export/graphiz/weights_with_units.dot",
+            &export_graphiz(&graph, &settings),
+        );
+
+        let settings = GraphizSettings {
+            show_weights: false,
+            ..settings
+        };
+        assert!(export_graphiz(&graph, &settings).contains("    0 -- 1;\n"));
+        // Weights are opt in, so the default output has no edge labels.
+        assert!(export_graphiz(&graph, &GraphizSettings::default()).contains("    0 -- 1;\n"));
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn write_matches_export() {
+        let graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+
+            a -- b [weight = 12];
+        };
+        let settings = GraphizSettings {
+            show_weights: true,
+            ..Default::default()
+        };
+        let mut written = Vec::new();
+        write_graphiz(&graph, &settings, &mut written).unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            export_graphiz(&graph, &settings)
+        );
+    }
+    #[test]
+    pub fn highlight_mst() {
+        let graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+            a -- c [weight = 3];
+        };
+        let mst = graph.kruskal_find_mst().unwrap();
+        // The MST is a new graph. So its edges are matched to the original ones by their nodes.
+        let highlight: HashSet<EdgeID> = mst
+            .unique_edges()
+            .map(|(a, b, _)| {
+                let a = graph.find_node_with_that_equals(mst[a].value()).unwrap();
+                let b = graph.find_node_with_that_equals(mst[b].value()).unwrap();
+                graph.connection(a, b).unwrap().0
+            })
+            .collect();
+        let settings = GraphizSettings {
+            show_weights: true,
+            ..Default::default()
+        };
+        let output = export_graphiz_highlight(&graph, &settings, &highlight);
+        assert!(
+            output.contains("0 -- 1 [label=\"1\", style=bold, color=red];"),
+            "{output}"
+        );
+        assert!(
+            output.contains("1 -- 2 [label=\"2\", style=bold, color=red];"),
+            "{output}"
+        );
+        assert!(output.contains("0 -- 2 [label=\"3\"];"), "{output}");
+        assert_round_trips_dot(&graph);
+    }
+    #[test]
+    pub fn node_size_by_degree() {
+        let graph = graph_no_import! {
+            hub [value = "Hub"];
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            hub -- a;
+            hub -- b;
+            hub -- c;
+        };
+        let settings = GraphizSettings {
+            node_size_by_degree: Some((0.5, 2.0)),
+            show_weights: false,
+            ..Default::default()
+        };
+        assert_matches_golden(
+            "export/graphiz/node_size_by_degree.dot",
+            &export_graphiz(&graph, &settings),
+        );
+    }
+    #[test]
+    pub fn layered() {
+        let mut graph = graph_no_import! {
+            root [value = "Root"];
+            left [value = "Left"];
+            right [value = "Right"];
+            leaf [value = "Leaf"];
+
+            root -- left [weight = 1];
+            root -- right [weight = 2];
+            left -- leaf [weight = 3];
+        };
+        let lonely = graph.add_node("Lonely");
+        let settings = GraphizSettings {
+            show_weights: true,
+            ..Default::default()
+        };
+        assert_matches_golden(
+            "export/graphiz/layered.dot",
+            &export_graphiz_layered(&graph, &settings, NodeID(0)).unwrap(),
+        );
+        graph.remove_node(lonely);
+        let output = export_graphiz_layered(&graph, &settings, NodeID(0)).unwrap();
+        assert!(!output.contains("Unreachable"));
+        assert!(export_graphiz_layered(&graph, &settings, lonely).is_err());
+    }
+    #[test]
+    pub fn label_styles() {
+        let graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+
+            a -- b [weight = 3];
+        };
+        let nodes = |settings: &GraphizSettings| {
+            export_graphiz(&graph, settings)
+                .lines()
+                .filter(|line| line.trim_start().starts_with("{node"))
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let mut settings = GraphizSettings::default();
+        assert_eq!(
+            nodes(&settings),
+            "{node [label=\"A\"] 0};\n{node [label=\"B\"] 1};"
+        );
+        settings.label_style = LabelStyle::IdOnly;
+        assert_eq!(
+            nodes(&settings),
+            "{node [label=\"0\"] 0};\n{node [label=\"1\"] 1};"
+        );
+        settings.label_style = LabelStyle::IdAndValue;
+        assert_eq!(
+            nodes(&settings),
+            "{node [label=\"0: A\"] 0};\n{node [label=\"1: B\"] 1};"
+        );
+        // The ID is already in the label.
+        settings.show_ids = true;
+        assert_eq!(
+            nodes(&settings),
+            "{node [label=\"0: A\"] 0};\n{node [label=\"1: B\"] 1};"
+        );
+
+        let settings = GraphizSettings {
+            show_weights: true,
+            edge_id_tooltip: true,
+            ..Default::default()
+        };
+        assert!(export_graphiz(&graph, &settings)
+            .contains("    0 -- 1 [label=\"3\", tooltip=\"e0\"];\n"));
+    }
+    #[test]
+    pub fn show_ids() {
+        let graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            a -- b [weight = 5];
+            b -- c [weight = 5];
+        };
+        let settings = GraphizSettings {
+            show_ids: true,
+            show_weights: true,
+            ..Default::default()
+        };
+        let output = export_graphiz(&graph, &settings);
+        assert!(output.contains("{node [label=\"C (n2)\"] 2};"), "{output}");
+        assert!(output.contains("1 -- 2 [label=\"e1: 5\"];"), "{output}");
+
+        let settings = GraphizSettings {
+            show_weights: false,
+            ..settings
+        };
+        let output = export_graphiz(&graph, &settings);
+        assert!(output.contains("0 -- 1 [label=\"e0\"];"), "{output}");
+    }
+    #[test]
+    pub fn removed_slots() {
+        let mut graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+            d [value = "D"];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+            c -- d [weight = 3];
+            a -- d [weight = 4];
+        };
+        graph.remove_node(NodeID(3));
+        let (edge, _) = graph.connection(NodeID(0), NodeID(1)).unwrap();
+        graph.remove_edge(edge);
+        let settings = GraphizSettings {
+            show_weights: true,
+            ..Default::default()
+        };
+        assert_matches_golden(
+            "export/graphiz/removed_slots.dot",
+            &export_graphiz(&graph, &settings),
+        );
+        assert_round_trips_dot(&graph);
+    }
+    #[test]
+    pub fn escaped_labels() {
+        let graph = graph_no_import! {
+            a [value = "Say \"hi\""];
+            b [value = "C:\\temp\nfiles"];
+
+            a -- b [weight = 1];
+        };
+        let settings = GraphizSettings {
+            show_weights: true,
+            weight_formatter: WeightFormatter::new(|weight| format!("{weight}\"")),
+            ..Default::default()
+        };
+        let output = export_graphiz(&graph, &settings);
+        assert!(
+            output.contains(r#"{node [label="Say \"hi\""] 0};"#),
+            "{output}"
+        );
+        assert!(
+            output.contains(r#"{node [label="C:\\temp\nfiles"] 1};"#),
+            "{output}"
+        );
+        assert!(output.contains(r#"0 -- 1 [label="1\""];"#), "{output}");
+
+        let graph = graph_no_import! {
+            a [value = "Say \"hi\""];
+            b [value = "B"];
+
+            a -- b [weight = 1];
+        };
+        assert_round_trips_dot(&graph);
+    }
+    #[test]
+    pub fn format_change_diff() {
+        let graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+
+            a -- b [weight = 12];
+        };
+        let settings = GraphizSettings {
+            show_weights: true,
+            label_style: LabelStyle::IdAndValue,
+            ..Default::default()
+        };
+        // The output of `weights_with_units`. Never updated, so the format change stays visible.
+        let golden =
+            std::fs::read_to_string(golden_path("export/graphiz/format_change.dot")).unwrap();
+        assert_eq!(
+            unified_diff(&golden, &export_graphiz(&graph, &settings)),
+            r#"@@ -3,8 +3,8 @@
+     overlap=false
+     node [shape=circle]
+     //  Nodes
+-    {node [label="A"] 0};
+-    {node [label="B"] 1};
++    {node [label="0: A"] 0};
++    {node [label="1: B"] 1};
+     //  Edges
+-    0 -- 1 [label="12ms"];
++    0 -- 1 [label="12"];
+ }
+"#
+        );
     }
-    graphiz.push_no_indent("}");
-    graphiz.finish()
 }
@@ -0,0 +1,115 @@
+use crate::adjacency_list::{AdjListGraph, NodeID};
+use crate::collections::HashMap;
+use crate::prelude::*;
+
+/// The value used for a cell of the adjacency matrix when there is no edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AbsentEncoding {
+    /// Missing edges are `0`. A zero weight edge can not be told apart from a missing edge.
+    #[default]
+    Zero,
+    /// Missing edges are the given value.
+    Custom(u32),
+    /// Missing edges are [u32::MAX]. Use this when the matrix is treated as distances.
+    Max,
+}
+impl AbsentEncoding {
+    /// The value of a cell without an edge.
+    pub fn absent_value(&self) -> u32 {
+        match self {
+            AbsentEncoding::Zero => 0,
+            AbsentEncoding::Custom(value) => *value,
+            AbsentEncoding::Max => u32::MAX,
+        }
+    }
+    /// The value of a diagonal cell without a self-loop.
+    ///
+    /// [AbsentEncoding::Max] uses `0` as the distance from a node to itself. The other encodings use the absent value.
+    pub fn diagonal_value(&self) -> u32 {
+        match self {
+            AbsentEncoding::Max => 0,
+            other => other.absent_value(),
+        }
+    }
+}
+/// Exports the graph as a symmetric adjacency matrix of edge weights.
+///
+/// Rows and columns follow the live nodes in ascending ID order, so dead node slots are skipped.
+///
+/// - A self-loop is stored on the diagonal with its weight, for every encoding.
+/// - A diagonal cell without a self-loop is [AbsentEncoding::diagonal_value].
+/// - Any other cell without an edge is [AbsentEncoding::absent_value].
+/// - If the graph has parallel edges the lowest weight is used.
+pub fn to_adjacency_matrix<T>(graph: &AdjListGraph<T>, absent: AbsentEncoding) -> Vec<Vec<u32>> {
+    let rows: HashMap<NodeID, usize> = graph
+        .node_ids()
+        .enumerate()
+        .map(|(row, id)| (id, row))
+        .collect();
+    let size = rows.len();
+    let mut matrix = vec![vec![absent.absent_value(); size]; size];
+    let mut filled = vec![vec![false; size]; size];
+    for (row, cells) in matrix.iter_mut().enumerate() {
+        cells[row] = absent.diagonal_value();
+    }
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if graph.is_edge_empty(index) {
+            continue;
+        }
+        let (a, b) = (rows[&edge.node_a], rows[&edge.node_b]);
+        let weight = if filled[a][b] {
+            matrix[a][b].min(edge.weight())
+        } else {
+            edge.weight()
+        };
+        matrix[a][b] = weight;
+        matrix[b][a] = weight;
+        filled[a][b] = true;
+        filled[b][a] = true;
+    }
+    matrix
+}
+#[cfg(test)]
+mod tests {
+    use super::{to_adjacency_matrix, AbsentEncoding};
+    use crate::adjacency_list::AdjListGraph;
+
+    #[test]
+    pub fn zero_and_max_encodings() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node('A');
+        let b = graph.add_node('B');
+        let c = graph.add_node('C');
+        graph.connect_nodes_with_weight(a, b, 4).unwrap();
+        graph.connect_nodes_with_weight(c, c, 2).unwrap();
+
+        assert_eq!(
+            to_adjacency_matrix(&graph, AbsentEncoding::Zero),
+            vec![vec![0, 4, 0], vec![4, 0, 0], vec![0, 0, 2]]
+        );
+        let max = u32::MAX;
+        assert_eq!(
+            to_adjacency_matrix(&graph, AbsentEncoding::Max),
+            vec![vec![0, 4, max], vec![4, 0, max], vec![max, max, 2]]
+        );
+        assert_eq!(
+            to_adjacency_matrix(&graph, AbsentEncoding::Custom(7)),
+            vec![vec![7, 4, 7], vec![4, 7, 7], vec![7, 7, 2]]
+        );
+    }
+    #[test]
+    pub fn skips_dead_nodes() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node('A');
+        let b = graph.add_node('B');
+        let c = graph.add_node('C');
+        graph.connect_nodes_with_weight(a, c, 3).unwrap();
+        graph.connect_nodes_with_weight(a, b, 1).unwrap();
+        graph.remove_node(b);
+
+        assert_eq!(
+            to_adjacency_matrix(&graph, AbsentEncoding::Zero),
+            vec![vec![0, 3], vec![3, 0]]
+        );
+    }
+}
@@ -1,28 +1,38 @@
+use crate::prelude::*;
+use alloc::sync::Arc;
+use core::fmt;
 pub mod graphiz;
-#[derive(Debug, Clone)]
-pub(crate) struct FormattedStringBuilder {
-    target: String,
-    indent: usize,
-}
-impl FormattedStringBuilder {
-    pub fn new(start: impl Into<String>, indent: usize) -> Self {
-        Self {
-            target: start.into(),
-            indent,
-        }
+pub mod matrix;
+#[cfg(feature = "std")]
+pub mod tikz;
+mod writer;
+
+pub(crate) use writer::{FormattedStringBuilder, FormattedWriter};
+/// Turns an edge weight into the text used by an exporter.
+///
+/// The default renders the plain number.
+/// ```
+/// use tux_graph::adjacency_list::export::WeightFormatter;
+/// let formatter = WeightFormatter::new(|weight| format!("{weight}ms"));
+/// assert_eq!(formatter.format(12), "12ms");
+/// ```
+#[derive(Clone)]
+pub struct WeightFormatter(Arc<dyn Fn(u32) -> String + Send + Sync>);
+impl WeightFormatter {
+    pub fn new(formatter: impl Fn(u32) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(formatter))
     }
-    pub fn push(&mut self, line: impl AsRef<str>) {
-        for _ in 0..self.indent {
-            self.target.push(' ');
-        }
-        self.target.push_str(line.as_ref());
-        self.target.push('\n');
+    pub fn format(&self, weight: u32) -> String {
+        (self.0)(weight)
     }
-    pub fn push_no_indent(&mut self, line: impl AsRef<str>) {
-        self.target.push_str(line.as_ref());
-        self.target.push('\n');
+}
+impl Default for WeightFormatter {
+    fn default() -> Self {
+        Self::new(|weight| weight.to_string())
     }
-    pub fn finish(self) -> String {
-        self.target
+}
+impl fmt::Debug for WeightFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WeightFormatter").finish_non_exhaustive()
     }
 }
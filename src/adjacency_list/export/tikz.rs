@@ -0,0 +1,158 @@
+use crate::adjacency_list::AdjListGraph;
+use crate::prelude::*;
+use crate::GraphError;
+
+use super::{FormattedStringBuilder, WeightFormatter};
+#[derive(Debug, Clone)]
+pub struct TikzSettings {
+    pub scale: f64,
+    pub show_weights: bool,
+    /// The style applied to every `\node`
+    pub node_style: String,
+    pub weight_formatter: WeightFormatter,
+}
+impl Default for TikzSettings {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            show_weights: true,
+            node_style: "draw, circle".to_string(),
+            weight_formatter: WeightFormatter::default(),
+        }
+    }
+}
+/// Exports the graph as a `tikzpicture`.
+///
+/// `positions` is indexed by the node ID. If it is not provided, the nodes are placed on a circle.
+///
+/// Returns [GraphError::MissingPosition] for the first live node that `positions` is too short to place.
+pub fn export_tikz<T>(
+    graph: &AdjListGraph<T>,
+    settings: &TikzSettings,
+    positions: Option<&[(f64, f64)]>,
+) -> Result<String, GraphError>
+where
+    T: core::fmt::Display,
+{
+    let circular;
+    let positions = match positions {
+        Some(positions) => positions,
+        None => {
+            circular = graph
+                .layout_circular()
+                .into_iter()
+                .map(|position| position.unwrap_or_default())
+                .collect::<Vec<_>>();
+            &circular
+        }
+    };
+    let mut tikz = FormattedStringBuilder::string(4);
+    tikz.push_fmt(format_args!(
+        "\\begin{{tikzpicture}}[scale={}]",
+        settings.scale
+    ));
+    tikz.indent();
+    for (id, value) in graph {
+        let (x, y) = *positions
+            .get(id.0)
+            .ok_or(GraphError::MissingPosition { node: id })?;
+        tikz.push_fmt(format_args!(
+            "\\node[{style}] (n{index}) at ({x:.3}, {y:.3}) {{{value}}};",
+            style = settings.node_style,
+            index = id.0,
+            x = x + 0.0,
+            y = y + 0.0,
+            value = escape_latex(&value.to_string())
+        ));
+    }
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if graph.is_edge_empty(index) {
+            continue;
+        }
+        let label = if settings.show_weights {
+            format!(
+                " node[midway, auto] {{{}}}",
+                escape_latex(&settings.weight_formatter.format(edge.weight()))
+            )
+        } else {
+            String::new()
+        };
+        tikz.push_fmt(format_args!(
+            "\\draw (n{node_a}) --{label} (n{node_b});",
+            node_a = edge.node_a.0,
+            node_b = edge.node_b.0
+        ));
+    }
+    tikz.dedent();
+    tikz.push("\\end{tikzpicture}");
+    Ok(tikz.into_string())
+}
+/// Escapes the characters that have a special meaning in LaTeX.
+fn escape_latex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '_' | '&' | '%' | '$' | '#' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use super::*;
+    use crate::testing::golden::assert_matches_golden;
+
+    #[test]
+    pub fn triangle_with_weights() {
+        let graph = graph_no_import! {
+            a [value = "A_1"];
+            b [value = "B&C"];
+            c [value = "100%"];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+            c -- a [weight = 3];
+        };
+        let tikz = export_tikz(&graph, &TikzSettings::default(), None).unwrap();
+        assert_matches_golden("export/tikz/triangle_with_weights.tex", &tikz);
+    }
+    #[test]
+    pub fn custom_positions_without_weights() {
+        let graph = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+
+            a -- b [weight = 1];
+        };
+        let settings = TikzSettings {
+            scale: 2.0,
+            show_weights: false,
+            node_style: "fill=gray".to_string(),
+            ..Default::default()
+        };
+        let tikz = export_tikz(&graph, &settings, Some(&[(0.0, 0.0), (1.5, 2.0)])).unwrap();
+        assert_matches_golden("export/tikz/custom_positions_without_weights.tex", &tikz);
+
+        let error = export_tikz(&graph, &settings, Some(&[(0.0, 0.0)])).unwrap_err();
+        assert_eq!(
+            error,
+            GraphError::MissingPosition {
+                node: crate::adjacency_list::NodeID(1)
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Node NodeID(1) has no position. The positions are too short"
+        );
+    }
+}
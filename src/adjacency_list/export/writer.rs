@@ -0,0 +1,183 @@
+//! Indented line writing shared by the exporters.
+use crate::prelude::*;
+use core::fmt::{self, Write};
+
+/// Writes lines indented by the current depth. Empty lines are written without indentation, so no line ends in spaces.
+///
+/// Only the start of a line is indented. Line breaks inside of a value, such as in a label, are written as they are.
+///
+/// Writes to anything implementing [fmt::Write]. Use [FormattedStringBuilder] to build a [String]
+/// and [FormattedWriter::io] to write to an [std::io::Write].
+#[derive(Debug, Clone)]
+pub(crate) struct FormattedWriter<W> {
+    target: W,
+    indent_width: usize,
+    depth: usize,
+    failed: bool,
+}
+/// A [FormattedWriter] building a [String].
+pub(crate) type FormattedStringBuilder = FormattedWriter<String>;
+impl<W: Write> FormattedWriter<W> {
+    /// Every level of indentation is `indent_width` spaces.
+    pub fn new(target: W, indent_width: usize) -> Self {
+        Self {
+            target,
+            indent_width,
+            depth: 0,
+            failed: false,
+        }
+    }
+    pub fn indent(&mut self) {
+        self.depth += 1;
+    }
+    /// Does nothing at the top level.
+    pub fn dedent(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+    /// Runs `f` one level deeper.
+    pub fn block(&mut self, f: impl FnOnce(&mut Self)) {
+        self.indent();
+        f(self);
+        self.dedent();
+    }
+    pub fn push(&mut self, line: impl AsRef<str>) {
+        self.push_fmt(format_args!("{}", line.as_ref()));
+    }
+    /// Writes a line without formatting it into a [String] first.
+    pub fn push_fmt(&mut self, args: fmt::Arguments<'_>) {
+        let mut line = Line {
+            writer: self,
+            indented: false,
+        };
+        let result = line
+            .write_fmt(args)
+            .and_then(|_| self.target.write_char('\n'));
+        if result.is_err() {
+            self.failed = true;
+        }
+    }
+    /// Returns the target. Fails if any write to it failed.
+    pub fn finish(self) -> Result<W, fmt::Error> {
+        if self.failed {
+            Err(fmt::Error)
+        } else {
+            Ok(self.target)
+        }
+    }
+}
+impl FormattedStringBuilder {
+    pub fn string(indent_width: usize) -> Self {
+        Self::new(String::new(), indent_width)
+    }
+    pub fn into_string(self) -> String {
+        self.finish().expect("Writing to a String never fails")
+    }
+}
+/// Indents the line once something is written to it.
+struct Line<'a, W> {
+    writer: &'a mut FormattedWriter<W>,
+    indented: bool,
+}
+impl<W: Write> Write for Line<'_, W> {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        if !self.indented && !value.is_empty() {
+            let width = self.writer.depth * self.writer.indent_width;
+            write!(self.writer.target, "{:width$}", "")?;
+            self.indented = true;
+        }
+        self.writer.target.write_str(value)
+    }
+}
+#[cfg(feature = "std")]
+mod io {
+    use core::fmt;
+    use std::io;
+
+    use super::FormattedWriter;
+
+    /// Passes the text on to an [io::Write] and keeps the first error, which [fmt::Write] can not return.
+    #[derive(Debug)]
+    pub(crate) struct IoTarget<W> {
+        inner: W,
+        error: Option<io::Error>,
+    }
+    impl<W: io::Write> fmt::Write for IoTarget<W> {
+        fn write_str(&mut self, value: &str) -> fmt::Result {
+            self.inner.write_all(value.as_bytes()).map_err(|error| {
+                self.error.get_or_insert(error);
+                fmt::Error
+            })
+        }
+    }
+    impl<W: io::Write> FormattedWriter<IoTarget<W>> {
+        pub fn io(writer: W, indent_width: usize) -> Self {
+            Self::new(
+                IoTarget {
+                    inner: writer,
+                    error: None,
+                },
+                indent_width,
+            )
+        }
+        /// Returns the writer, or the first error it returned.
+        pub fn finish_io(self) -> io::Result<W> {
+            let failed = self.failed;
+            let IoTarget { inner, error } = self.target;
+            match error {
+                Some(error) => Err(error),
+                None if failed => Err(io::Error::other("Failed to format the output")),
+                None => Ok(inner),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    pub fn nesting() {
+        let mut builder = FormattedStringBuilder::string(2);
+        builder.push("graph {");
+        builder.block(|builder| {
+            builder.push("a;");
+            builder.block(|builder| builder.push_fmt(format_args!("{} -- {};", 1, 2)));
+            builder.push("label=\"b\nc\"");
+        });
+        builder.push("}");
+        assert_eq!(
+            builder.into_string(),
+            "graph {\n  a;\n    1 -- 2;\n  label=\"b\nc\"\n}\n"
+        );
+    }
+    #[test]
+    pub fn empty_lines_have_no_spaces() {
+        let mut builder = FormattedStringBuilder::string(4);
+        builder.indent();
+        builder.push("");
+        builder.push_fmt(format_args!("{}", ""));
+        builder.push("a");
+        builder.dedent();
+        builder.dedent();
+        builder.push("end");
+        let output = builder.into_string();
+        assert_eq!(output, "\n\n    a\nend\n");
+        assert!(output.lines().all(|line| !line.ends_with(' ')));
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn io_errors() {
+        let mut writer = FormattedWriter::io(Vec::new(), 1);
+        writer.block(|writer| writer.push("a"));
+        assert_eq!(writer.finish_io().unwrap(), b" a\n");
+
+        let mut full = [0u8; 2];
+        let mut writer = FormattedWriter::io(&mut full[..], 1);
+        writer.push("abc");
+        let error = writer.finish_io().unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    }
+}
@@ -1,13 +1,71 @@
-use std::{collections::VecDeque, mem};
+use crate::collections::HashMap;
+use crate::prelude::*;
+use core::{hash::Hash, mem};
 
+#[cfg(feature = "std")]
+mod analytics;
 mod check;
+mod coloring;
+mod components;
+mod connectivity;
+mod conversions;
+mod debug;
+#[cfg(feature = "std")]
+mod edge_list;
 mod equality;
+mod euler;
+mod filtered;
+mod flow;
+mod graph_read;
+mod insertion_order;
+mod iter;
+#[cfg(feature = "std")]
+mod layout;
+mod merge;
 mod mst;
+mod name_map;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod partition;
+mod path;
+mod policy;
+mod rewrite;
+mod sample;
 mod search;
+mod set_ops;
+mod shortest_path;
+mod slots;
+mod snapshot;
+mod stats;
+mod subgraph;
+#[cfg(test)]
+pub(crate) mod test_graphs;
 mod utils;
+#[cfg(feature = "std")]
+pub use analytics::CachedAnalytics;
+pub use check::RepairReport;
+pub use components::{ComponentView, Components};
+pub use debug::RawDebug;
+#[cfg(feature = "std")]
+pub use edge_list::{EdgeListError, EdgeListOptions};
+pub use filtered::FilteredGraph;
+pub use iter::*;
+pub use merge::{MergeConflict, MergeSide};
+pub use mst::MstSensitivity;
+pub use name_map::NameMap;
+pub use path::Path;
+pub use policy::{GraphPolicy, PolicyAction};
+pub use rewrite::{halve_weight, RewireTo};
+use slots::EmptySlots;
+pub use snapshot::GraphSnapshot;
+pub use stats::GraphStats;
 pub(crate) use utils::*;
+pub use utils::{BatchResult, EdgeCopyResult, NeighborOrder, NeighborSelection};
 
-use crate::utils::ExtendedVec;
+use crate::utils::{
+    logging::{debug, debug_span, trace},
+    remove_dead_slots, ExtendedVec, IdType,
+};
 use crate::{adjacency_list::*, GraphError};
 
 /// A graph is a collection of nodes and edges.
@@ -23,22 +81,63 @@ use crate::{adjacency_list::*, GraphError};
 /// ## Serde Note
 ///
 /// Serialize is manually implemented to prevent serializing the empty slots.
-#[derive(Debug, Clone)]
+///
+/// ## Debug Note
+///
+/// Debug only prints the live nodes and edges. Use [AdjListGraph::raw_debug] to see the slots as they are stored.
+#[derive(Clone)]
 pub struct AdjListGraph<T> {
     pub(crate) nodes: Vec<Node<T>>,
     pub(crate) edges: Vec<Edge>,
 
     // Stores a Queue of empty slots in the edges and nodes arrays.
     // This will prevent having to update each node and edge index when removing a node or edge.
-    empty_edge_slots: VecDeque<EdgeID>,
-    empty_node_slots: VecDeque<NodeID>,
+    empty_edge_slots: EmptySlots<EdgeID>,
+    empty_node_slots: EmptySlots<NodeID>,
+    policy: GraphPolicy,
+    /// Indexed by node slot. Slots past the end have a weight of 1.
+    node_weights: Vec<u32>,
+    /// `None` unless [AdjListGraph::track_insertion_order] was called.
+    insertion_order: Option<insertion_order::InsertionOrder>,
+    /// Bumped by every mutation. See [AdjListGraph::content_version].
+    content_version: u64,
+    #[cfg(feature = "std")]
+    analytics: analytics::AnalyticsCache,
 }
 mod _serde {
     use super::*;
     use serde::Deserialize;
     use serde::{de::Visitor, ser::SerializeStruct, Serialize};
+    const VERSION: &str = "version";
+    const POLICY: &str = "policy";
     const NODES: &str = "nodes";
     const EDGES: &str = "edges";
+    const NODE_WEIGHTS: &str = "node_weights";
+    const FIELDS: &[&str] = &[VERSION, POLICY, NODES, EDGES, NODE_WEIGHTS];
+    /// Keys are parsed as an enum so both borrowed and owned strings work.
+    #[derive(Deserialize)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Field {
+        Version,
+        Policy,
+        Nodes,
+        Edges,
+        #[serde(rename = "node_weights")]
+        NodeWeights,
+    }
+    /// The version of the serialized format. Bump this when the format changes.
+    ///
+    /// Payloads without a version were written before the field existed and are read as version 1.
+    ///
+    /// Version 2 added the policy. Version 1 payloads use the default policy.
+    ///
+    /// Version 3 added the node weights. Graphs without node weights are still written as version 2, so older readers can load them.
+    ///
+    /// Graphs are only written without dead slots. A slot that looks cleared, a node without a value and edges or an edge whose nodes are both `usize::MAX`,
+    /// is read as a dead slot, so the IDs after it do not change. A partly cleared slot is an error, as is a node or edge that references a slot that does not reference it back.
+    const FORMAT_VERSION: u32 = 3;
+    /// The version written for graphs without node weights.
+    const FORMAT_VERSION_WITHOUT_NODE_WEIGHTS: u32 = 2;
     impl<T> Serialize for AdjListGraph<T>
     where
         T: Serialize,
@@ -48,62 +147,190 @@ mod _serde {
             S: serde::Serializer,
         {
             if self.has_dead_edges() || self.has_dead_nodes() {
+                debug!(
+                    dead_nodes = self.empty_node_slots.len(),
+                    dead_edges = self.empty_edge_slots.len(),
+                    "Refusing to serialize a graph with dead values"
+                );
                 return Err(serde::ser::Error::custom("Graph has dead nodes or edges. Please call remove_dead_values before serializing."));
             }
-            let mut state = serializer.serialize_struct("AdjListGraph", 2)?;
+            let has_node_weights = !self.node_weights.is_empty();
+            let mut state =
+                serializer.serialize_struct("AdjListGraph", 4 + has_node_weights as usize)?;
+            let version = if has_node_weights {
+                FORMAT_VERSION
+            } else {
+                FORMAT_VERSION_WITHOUT_NODE_WEIGHTS
+            };
+            state.serialize_field(VERSION, &version)?;
+            state.serialize_field(POLICY, &self.policy)?;
             state.serialize_field(NODES, &self.nodes)?;
             state.serialize_field(EDGES, &self.edges)?;
+            if has_node_weights {
+                let weights: Vec<u32> = (0..self.nodes.len())
+                    .map(|index| self.node_weight_inner(index))
+                    .collect();
+                state.serialize_field(NODE_WEIGHTS, &weights)?;
+            } else {
+                state.skip_field(NODE_WEIGHTS)?;
+            }
             state.end()
         }
     }
     #[derive(Default)]
-    struct AdjGraphVisitor<T>(std::marker::PhantomData<T>);
+    struct AdjGraphVisitor<T>(core::marker::PhantomData<T>);
     impl<'de, T> Visitor<'de> for AdjGraphVisitor<T>
     where
         T: Deserialize<'de>,
     {
         type Value = AdjListGraph<T>;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("Expecting a struct with nodes and edges fields.")
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("Expecting a struct with version, policy, nodes and edges fields.")
         }
 
         fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
         where
             A: serde::de::MapAccess<'de>,
         {
+            let mut version = None;
+            let mut policy = None;
             let mut nodes = None;
             let mut edges = None;
-            while let Some(key) = map.next_key::<&str>()? {
+            let mut node_weights = None;
+            while let Some(key) = map.next_key::<Field>()? {
                 match key {
-                    NODES => {
+                    Field::Version => {
+                        if version.is_some() {
+                            return Err(serde::de::Error::duplicate_field(VERSION));
+                        }
+                        let value: u32 = map.next_value()?;
+                        // Checked before the rest so a newer format is not parsed as this one.
+                        if !(1..=FORMAT_VERSION).contains(&value) {
+                            return Err(serde::de::Error::custom(format!(
+                                "Unsupported graph format version {value}. This version of tux-graph supports versions 1 to {FORMAT_VERSION}"
+                            )));
+                        }
+                        version = Some(value);
+                    }
+                    Field::Policy => {
+                        if policy.is_some() {
+                            return Err(serde::de::Error::duplicate_field(POLICY));
+                        }
+                        policy = Some(map.next_value()?);
+                    }
+                    Field::Nodes => {
                         if nodes.is_some() {
                             return Err(serde::de::Error::duplicate_field(NODES));
                         }
                         nodes = Some(map.next_value()?);
                     }
-                    EDGES => {
+                    Field::Edges => {
                         if edges.is_some() {
                             return Err(serde::de::Error::duplicate_field(EDGES));
                         }
                         edges = Some(map.next_value()?);
                     }
-                    _ => {
-                        return Err(serde::de::Error::unknown_field(key, &["nodes", "edges"]));
+                    Field::NodeWeights => {
+                        if node_weights.is_some() {
+                            return Err(serde::de::Error::duplicate_field(NODE_WEIGHTS));
+                        }
+                        node_weights = Some(map.next_value()?);
                     }
                 }
             }
 
-            let nodes = nodes.ok_or_else(|| serde::de::Error::missing_field(NODES))?;
-            let edges = edges.ok_or_else(|| serde::de::Error::missing_field(EDGES))?;
-            Ok(AdjListGraph {
+            let nodes: Vec<Node<T>> =
+                nodes.ok_or_else(|| serde::de::Error::missing_field(NODES))?;
+            let edges: Vec<Edge> = edges.ok_or_else(|| serde::de::Error::missing_field(EDGES))?;
+            let node_weights: Vec<u32> = node_weights.unwrap_or_default();
+            if !node_weights.is_empty() && node_weights.len() != nodes.len() {
+                return Err(serde::de::Error::invalid_length(
+                    node_weights.len(),
+                    &"one node weight per node",
+                ));
+            }
+            let empty_node_slots = cleared_slots(&nodes, |node| {
+                if node.optional_value().is_some() {
+                    return Ok(false);
+                }
+                if node.edges.is_empty() {
+                    Ok(true)
+                } else {
+                    Err("it has no value but lists edges")
+                }
+            })?;
+            let empty_edge_slots = cleared_slots(&edges, |edge| {
+                let cleared = NodeID(usize::MAX);
+                if edge.node_a == cleared && edge.node_b == cleared {
+                    return Ok(true);
+                }
+                if edge.node_a == cleared || edge.node_b == cleared {
+                    return Err("only one of its nodes is cleared");
+                }
+                if [edge.node_a, edge.node_b]
+                    .iter()
+                    .any(|node| node.0 >= nodes.len())
+                {
+                    return Err("it connects a node that does not exist");
+                }
+                if [edge.node_a, edge.node_b]
+                    .iter()
+                    .any(|node| empty_node_slots.contains(node))
+                {
+                    Err("it connects a removed node")
+                } else {
+                    Ok(false)
+                }
+            })?;
+            trace!(
+                nodes = nodes.len(),
+                edges = edges.len(),
+                dead_nodes = empty_node_slots.len(),
+                dead_edges = empty_edge_slots.len(),
+                "Deserialized graph"
+            );
+            let graph = AdjListGraph {
                 nodes,
                 edges,
-                empty_edge_slots: Default::default(),
-                empty_node_slots: Default::default(),
-            })
+                empty_edge_slots,
+                empty_node_slots,
+                policy: policy.unwrap_or_default(),
+                node_weights,
+                ..Default::default()
+            };
+            // Catches the references between nodes and edges that the checks above do not look at.
+            // Policy violations are allowed, so a graph built with [AdjListGraph::from_raw_parts] still round trips and can be fixed after loading.
+            graph
+                .validate_references()
+                .map_err(serde::de::Error::custom)?;
+            Ok(graph)
         }
     }
+    /// Finds the slots that look like [AdjListGraph::remove_node] or [AdjListGraph::remove_edge] cleared them.
+    ///
+    /// `is_cleared` returns an error for a slot that is only partly cleared.
+    fn cleared_slots<V, I, E>(
+        values: &[V],
+        is_cleared: impl Fn(&V) -> Result<bool, &'static str>,
+    ) -> Result<EmptySlots<I>, E>
+    where
+        I: IdType + Copy + core::fmt::Debug,
+        E: serde::de::Error,
+    {
+        let mut slots = EmptySlots::default();
+        for (index, value) in values.iter().enumerate() {
+            let id = I::from_usize(index);
+            match is_cleared(value) {
+                Ok(true) => slots.push_back(id),
+                Ok(false) => {}
+                Err(reason) => {
+                    return Err(E::custom(format!("{id:?} is invalid because {reason}")));
+                }
+            }
+        }
+        Ok(slots)
+    }
     impl<'de, T> Deserialize<'de> for AdjListGraph<T>
     where
         T: Deserialize<'de>,
@@ -112,10 +339,9 @@ mod _serde {
         where
             D: serde::Deserializer<'de>,
         {
-            // TODO: Validate that the graph is valid.
             deserializer.deserialize_struct(
                 "AdjListGraph",
-                &["nodes", "edges"],
+                FIELDS,
                 AdjGraphVisitor(Default::default()),
             )
         }
@@ -127,45 +353,248 @@ impl<T> Default for AdjListGraph<T> {
         Self {
             nodes: Vec::new(),
             edges: Vec::new(),
-            empty_edge_slots: VecDeque::new(),
-            empty_node_slots: VecDeque::new(),
+            empty_edge_slots: EmptySlots::default(),
+            empty_node_slots: EmptySlots::default(),
+            policy: GraphPolicy::default(),
+            node_weights: Vec::new(),
+            insertion_order: None,
+            content_version: 0,
+            #[cfg(feature = "std")]
+            analytics: Default::default(),
         }
     }
 }
+/// Returns the index if it points to a live slot. Otherwise panics with a message that names the ID.
+#[track_caller]
+fn live_slot<I: IdType + Copy + core::fmt::Debug>(
+    len: usize,
+    empty_slots: &EmptySlots<I>,
+    id: I,
+    getter: &str,
+) -> usize {
+    if empty_slots.contains(&id) {
+        panic!("{id:?} was removed from the graph (dead slot); use {getter} for fallible access");
+    }
+    if id.as_usize() >= len {
+        panic!("{id:?} does not exist in the graph; use {getter} for fallible access");
+    }
+    id.as_usize()
+}
 macro_rules! index {
     (
-        $ty:ty => $array:ident => $output:ty
+        $ty:ty => $array:ident => $empty_slots:ident => $output:ty, $getter:literal
     ) => {
-        impl<T> std::ops::Index<$ty> for AdjListGraph<T> {
+        impl<T> core::ops::Index<$ty> for AdjListGraph<T> {
             type Output = $output;
 
+            #[track_caller]
             fn index(&self, index: $ty) -> &Self::Output {
-                &self.$array[index.0]
+                &self.$array[live_slot(
+                    self.$array.len(),
+                    &self.$empty_slots,
+                    <$ty>::from_usize(index.0),
+                    $getter,
+                )]
             }
         }
-        impl<T> std::ops::Index<&$ty> for AdjListGraph<T> {
+        impl<T> core::ops::Index<&$ty> for AdjListGraph<T> {
             type Output = $output;
 
+            #[track_caller]
             fn index(&self, index: &$ty) -> &Self::Output {
-                &self.$array[index.0]
+                &self.$array[live_slot(
+                    self.$array.len(),
+                    &self.$empty_slots,
+                    <$ty>::from_usize(index.0),
+                    $getter,
+                )]
             }
         }
-        impl<T> std::ops::IndexMut<$ty> for AdjListGraph<T> {
+        impl<T> core::ops::IndexMut<$ty> for AdjListGraph<T> {
+            #[track_caller]
             fn index_mut(&mut self, index: $ty) -> &mut Self::Output {
-                &mut self.$array[index.0]
+                let slot = live_slot(
+                    self.$array.len(),
+                    &self.$empty_slots,
+                    <$ty>::from_usize(index.0),
+                    $getter,
+                );
+                self.touch();
+                &mut self.$array[slot]
             }
         }
-        impl<T> std::ops::IndexMut<&$ty> for AdjListGraph<T> {
+        impl<T> core::ops::IndexMut<&$ty> for AdjListGraph<T> {
+            #[track_caller]
             fn index_mut(&mut self, index: &$ty) -> &mut Self::Output {
-                &mut self.$array[index.0]
+                let slot = live_slot(
+                    self.$array.len(),
+                    &self.$empty_slots,
+                    <$ty>::from_usize(index.0),
+                    $getter,
+                );
+                self.touch();
+                &mut self.$array[slot]
             }
         }
     };
 }
-index!(NodeID => nodes => Node<T>);
-index!(EdgeID => edges => Edge);
+index!(NodeID => nodes => empty_node_slots => Node<T>, "get_node");
+index!(EdgeID => edges => empty_edge_slots => Edge, "get_edge");
 
 impl<T> AdjListGraph<T> {
+    /// Creates an empty graph with room for `nodes` nodes and `edges` edges without reallocating.
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+            ..Default::default()
+        }
+    }
+    /// Builds a graph from node values and `(node_a, node_b, weight)` edges in one pass.
+    ///
+    /// Node IDs are the index in `values` and edge IDs are the index in `edges`.
+    /// The edges are not checked against the [GraphPolicy], so passing parallel edges is up to the caller. Debug builds run [AdjListGraph::validate] and panic if it fails.
+    ///
+    /// This is what the `static_graph!` macro expands to.
+    ///
+    /// # Panics
+    /// If an edge references a node past the end of `values`.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+    ///
+    /// let graph = AdjListGraph::from_raw_parts(
+    ///     vec!['A', 'B', 'C'],
+    ///     vec![(NodeID(0), NodeID(1), 4), (NodeID(1), NodeID(2), 2)],
+    /// );
+    /// assert_eq!(graph.connection(NodeID(2), NodeID(1)).map(|(_, weight)| weight), Some(2));
+    /// ```
+    pub fn from_raw_parts(values: Vec<T>, edges: Vec<(NodeID, NodeID, u32)>) -> Self {
+        let mut degrees = vec![0usize; values.len()];
+        for (a, b, _) in &edges {
+            degrees[a.0] += 1;
+            if a != b {
+                degrees[b.0] += 1;
+            }
+        }
+        let mut nodes: Vec<Node<T>> = values
+            .into_iter()
+            .zip(degrees)
+            .map(|(value, degree)| Node::with_capacity(value, degree))
+            .collect();
+        let edges = edges
+            .into_iter()
+            .enumerate()
+            .map(|(index, (a, b, weight))| {
+                nodes[a.0].edges.insert(EdgeID(index));
+                nodes[b.0].edges.insert(EdgeID(index));
+                Edge::new(weight, a, b)
+            })
+            .collect();
+        let graph = Self {
+            nodes,
+            edges,
+            ..Default::default()
+        };
+        debug_assert_eq!(
+            graph.validate(),
+            Ok(()),
+            "from_raw_parts got an invalid graph"
+        );
+        graph
+    }
+    /// Creates an empty graph that checks new edges against `policy`.
+    pub fn with_policy(policy: GraphPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+    pub fn policy(&self) -> GraphPolicy {
+        self.policy
+    }
+    /// Sets the weight of a node. Used by the algorithms that take node weights.
+    ///
+    /// The weight is kept by clones, snapshots and the helpers that copy nodes into a new graph, such as [AdjListGraph::relabel] and [AdjListGraph::filter_nodes].
+    /// It is part of [PartialEq].
+    ///
+    /// Returns an error if the node does not exist.
+    pub fn set_node_weight(&mut self, id: NodeID, weight: u32) -> Result<(), GraphError> {
+        self.check_node_exists(id)?;
+        self.touch();
+        if self.node_weights.len() <= id.0 {
+            self.node_weights.resize(self.nodes.len(), 1);
+        }
+        self.node_weights[id.0] = weight;
+        Ok(())
+    }
+    /// The weight of a node. 1 if it was never set. `None` if the node does not exist.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+    /// graph.set_node_weight(b, 5).unwrap();
+    /// assert_eq!(graph.node_weight(a), Some(1));
+    /// assert_eq!(graph.node_weight(b), Some(5));
+    /// ```
+    pub fn node_weight(&self, id: NodeID) -> Option<u32> {
+        self.does_node_id_exist(id)
+            .then(|| self.node_weight_inner(id.0))
+    }
+    fn node_weight_inner(&self, index: usize) -> u32 {
+        self.node_weights.get(index).copied().unwrap_or(1)
+    }
+    /// Adds a copy of the value and weight of `node` to `target`.
+    fn copy_node_into(&self, target: &mut Self, node: NodeID) -> NodeID
+    where
+        T: Clone,
+    {
+        let copied = target.add_node(self[node].value().clone());
+        self.copy_node_weight(node, target, copied);
+        copied
+    }
+    /// Gives `target_node` the weight of `node`. Nothing is stored if it is the default of 1.
+    fn copy_node_weight(&self, node: NodeID, target: &mut Self, target_node: NodeID) {
+        let weight = self.node_weight_inner(node.0);
+        if weight == 1 {
+            return;
+        }
+        if target.node_weights.len() <= target_node.0 {
+            target.node_weights.resize(target.nodes.len(), 1);
+        }
+        target.node_weights[target_node.0] = weight;
+    }
+    /// A reused slot does not keep the weight of the removed node.
+    fn reset_node_weight(&mut self, id: NodeID) {
+        if let Some(weight) = self.node_weights.get_mut(id.0) {
+            *weight = 1;
+        }
+    }
+    /// A counter that changes whenever the nodes, edges or values of the graph may have changed.
+    ///
+    /// Mutable access through [IndexMut](core::ops::IndexMut) or [AdjListGraph::node_pair_mut] counts as a change even if nothing is written.
+    /// Two graphs with the same version are not necessarily equal. Clones start with the version of the original.
+    pub fn content_version(&self) -> u64 {
+        self.content_version
+    }
+    fn touch(&mut self) {
+        self.content_version = self.content_version.wrapping_add(1);
+    }
+    /// Reserves room for at least `nodes` more nodes and `edges` more edges.
+    pub fn reserve(&mut self, nodes: usize, edges: usize) {
+        self.nodes.reserve(nodes);
+        self.edges.reserve(edges);
+    }
+    /// The number of nodes the graph can hold without reallocating. Includes removed slots.
+    pub fn node_capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+    /// The number of edges the graph can hold without reallocating. Includes removed slots.
+    pub fn edge_capacity(&self) -> usize {
+        self.edges.capacity()
+    }
     /// Adds a node to the graph.
     ///
     /// # Arguments
@@ -173,12 +602,18 @@ impl<T> AdjListGraph<T> {
     /// # Returns
     /// The ID of the node.
     pub fn add_node(&mut self, value: T) -> NodeID {
-        if let Some(empty_node) = self.empty_node_slots.pop_front() {
+        self.touch();
+        let id = if let Some(empty_node) = self.empty_node_slots.pop_front() {
             self.nodes[empty_node.0].clear_and_set(value);
+            self.reset_node_weight(empty_node);
             empty_node
         } else {
             self.nodes.push_with_wrapped_id(Node::new(value))
+        };
+        if let Some(order) = &mut self.insertion_order {
+            order.record(id);
         }
+        id
     }
 
     /// Adds a node to the graph.
@@ -199,23 +634,73 @@ impl<T> AdjListGraph<T> {
         nodes
     }
 
+    /// Adds a node at a specific ID.
+    ///
+    /// If `id` is past the end of the graph, the slots in between become dead slots that [AdjListGraph::add_node] fills later.
+    /// A dead slot at `id` is taken out of the free list so it is not handed out again.
+    ///
+    /// Returns [GraphError::SlotOccupied] if a live node already has the ID.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// graph.insert_node_at(NodeID(2), "C").unwrap();
+    /// assert_eq!(graph.number_of_nodes(), 1);
+    /// assert_eq!(graph.add_node("A"), NodeID(0));
+    /// assert!(graph.insert_node_at(NodeID(2), "D").is_err());
+    /// ```
+    pub fn insert_node_at(&mut self, id: NodeID, value: T) -> Result<(), GraphError> {
+        if self.does_node_id_exist(id) {
+            return Err(GraphError::SlotOccupied { node: id });
+        }
+        self.touch();
+        if let Some(order) = &mut self.insertion_order {
+            order.record(id);
+        }
+        if id.0 < self.nodes.len() {
+            self.empty_node_slots.remove(id);
+            self.nodes[id.0].clear_and_set(value);
+            self.reset_node_weight(id);
+            return Ok(());
+        }
+        for gap in self.nodes.len()..id.0 {
+            self.nodes.push(Node::dead());
+            self.empty_node_slots.push_back(NodeID(gap));
+        }
+        self.nodes.push(Node::new(value));
+        Ok(())
+    }
+
     pub fn connect_nodes(&mut self, a: NodeID, b: NodeID) -> Result<EdgeID, GraphError> {
         self.connect_nodes_with_weight(a, b, 0)
     }
+    /// Connects two nodes.
+    ///
+    /// Returns an error if the edge is a self loop or a parallel edge and the [GraphPolicy] of the graph rejects it.
     pub fn connect_nodes_with_weight(
         &mut self,
         a: NodeID,
         b: NodeID,
         weight: u32,
     ) -> Result<EdgeID, GraphError> {
-        for edge_id in &self[a].edges {
-            let edge = &self.edges[edge_id.0];
-            let (node_a, node_b) = edge.nodes();
-            if node_a == b || node_b == b {
-                return Err(GraphError::NodesAlreadyConnected(*edge_id));
+        if a == b && self.policy.self_loops == PolicyAction::Reject {
+            return Err(GraphError::SelfLoopNotAllowed { node: a });
+        }
+        if self.policy.parallel_edges == PolicyAction::Reject {
+            for edge_id in &self[a].edges {
+                let edge = &self.edges[edge_id.0];
+                if edge.other_node(a) == b {
+                    return Err(GraphError::NodesAlreadyConnected {
+                        node_a: a,
+                        node_b: b,
+                        edge: *edge_id,
+                    });
+                }
             }
         }
 
+        self.touch();
         let id = if let Some(empty_edge) = self.empty_edge_slots.pop_front() {
             self.edges[empty_edge.0] = Edge::new(weight, a, b);
             empty_edge
@@ -226,6 +711,127 @@ impl<T> AdjListGraph<T> {
         self.nodes[b.0].edges.insert(id);
         Ok(id)
     }
+    /// Connects two nodes after checking that both of them exist and have not been removed.
+    ///
+    /// Unlike [AdjListGraph::connect_nodes_with_weight], this will never panic or connect a removed node.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let a = graph.add_node("A".to_string());
+    /// let b = graph.add_node("B".to_string());
+    /// graph.remove_node(b);
+    ///
+    /// assert!(graph.connect_nodes_checked(a, b, 1).is_err());
+    /// ```
+    pub fn connect_nodes_checked(
+        &mut self,
+        a: NodeID,
+        b: NodeID,
+        weight: u32,
+    ) -> Result<EdgeID, GraphError> {
+        self.check_node_exists(a)?;
+        self.check_node_exists(b)?;
+        self.connect_nodes_with_weight(a, b, weight)
+    }
+    /// Connects the nodes or, if they are already connected, sets the weight of the existing edge.
+    ///
+    /// Returns the edge and true if it was created. See [AdjListGraph::connect_or_update_with]
+    pub fn connect_or_update(
+        &mut self,
+        a: NodeID,
+        b: NodeID,
+        weight: u32,
+    ) -> Result<(EdgeID, bool), GraphError> {
+        self.connect_or_update_with(a, b, |_| weight)
+    }
+    /// Connects the nodes with the weight returned by `f`.
+    ///
+    /// `f` gets the weight of the existing edge, or `None` if the nodes are not connected.
+    /// If more than one edge connects the nodes, the one returned by [AdjListGraph::connection] is updated.
+    ///
+    /// Returns the edge and true if it was created. Returns an error if either node does not exist.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+    /// graph.connect_nodes_with_weight(a, b, 5).unwrap();
+    ///
+    /// // Keep the lightest weight.
+    /// let (edge, created) = graph
+    ///     .connect_or_update_with(a, b, |old| old.map_or(3, |old| old.min(3)))
+    ///     .unwrap();
+    /// assert!(!created);
+    /// assert_eq!(graph[edge].weight(), 3);
+    /// ```
+    pub fn connect_or_update_with(
+        &mut self,
+        a: NodeID,
+        b: NodeID,
+        f: impl FnOnce(Option<u32>) -> u32,
+    ) -> Result<(EdgeID, bool), GraphError> {
+        self.check_node_exists(a)?;
+        self.check_node_exists(b)?;
+        if let Some((edge, weight)) = self.connection(a, b) {
+            self.touch();
+            self.edges[edge.0].weight = f(Some(weight));
+            return Ok((edge, false));
+        }
+        let edge = self.connect_nodes_with_weight(a, b, f(None))?;
+        Ok((edge, true))
+    }
+    /// Connects every `(node_a, node_b, weight)` with [AdjListGraph::connect_nodes_checked].
+    ///
+    /// An edge that fails is recorded in [BatchResult::failures] with its position in the input and the rest are still connected.
+    /// With `abort_on_error` the batch stops at the first failure instead. The edges connected before it stay in the graph.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+    ///
+    /// let result = graph.connect_many([(a, b, 1), (a, NodeID(7), 2), (b, b, 3)], false);
+    /// assert_eq!(result.created.len(), 2);
+    /// assert_eq!(result.failures[0].0, 1);
+    /// ```
+    pub fn connect_many(
+        &mut self,
+        edges: impl IntoIterator<Item = (NodeID, NodeID, u32)>,
+        abort_on_error: bool,
+    ) -> BatchResult {
+        let mut result = BatchResult::default();
+        for (index, (a, b, weight)) in edges.into_iter().enumerate() {
+            match self.connect_nodes_checked(a, b, weight) {
+                Ok(edge) => result.created.push(edge),
+                Err(error) => {
+                    debug!(index, ?error, "Failed to connect an edge of the batch");
+                    result.failures.push((index, error));
+                    if abort_on_error {
+                        break;
+                    }
+                }
+            }
+        }
+        result
+    }
+    /// Builds a graph from `(value_a, value_b, weight)` triples.
+    ///
+    /// Equal values become the same node. Nodes are added in the order their value first appears.
+    /// If two nodes are connected more than once, only the first edge is kept.
+    pub fn from_named_edges(edges: impl Iterator<Item = (T, T, u32)>) -> Self
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut ids: HashMap<T, NodeID> = HashMap::new();
+        Self::from_edges_by_value(edges, |graph, value| {
+            *ids.entry(value)
+                .or_insert_with_key(|value| graph.add_node(value.clone()))
+        })
+    }
     ///
     /// Returns the nodes connected to the given node.
     ///
@@ -281,7 +887,9 @@ impl<T> AdjListGraph<T> {
     pub fn is_node_connected_to_itself(&self, node: NodeID) -> bool {
         self.is_node_connected_to_node(node, node)
     }
-    /// Returns true if the given node is connected to itself.
+    /// Returns true if an edge connects the two nodes.
+    ///
+    /// Only edges that actually reference both nodes count. Removed edges are skipped, even if the node still lists them.
     /// ```rust
     /// use tux_graph::adjacency_list::AdjListGraph;
     ///
@@ -297,13 +905,130 @@ impl<T> AdjListGraph<T> {
     /// ```
     pub fn is_node_connected_to_node(&self, node_a: NodeID, node_b: NodeID) -> bool {
         self[node_a].edges.iter().any(|edge_id| {
-            let edge = &self[*edge_id];
-            let (edge_node_a, edge_node_b) = edge.nodes();
-            edge_node_a == node_b || edge_node_b == node_b
+            self.get_edge(*edge_id).is_some_and(|edge| {
+                (edge.node_a == node_a && edge.node_b == node_b)
+                    || (edge.node_a == node_b && edge.node_b == node_a)
+            })
         })
     }
 
+    /// Returns the edge connecting the two nodes and its weight.
+    ///
+    /// If multiple edges connect the nodes, the one with the lowest weight is returned.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let a = graph.add_node("A".to_string());
+    /// let b = graph.add_node("B".to_string());
+    /// let c = graph.add_node("C".to_string());
+    ///
+    /// let edge = graph.connect_nodes_with_weight(a, b, 5).unwrap();
+    ///
+    /// assert_eq!(graph.connection(b, a), Some((edge, 5)));
+    /// assert_eq!(graph.connection(a, c), None);
+    /// ```
+    pub fn connection(&self, a: NodeID, b: NodeID) -> Option<(EdgeID, u32)> {
+        self.adjacent(a)
+            .filter(|(_, other, _)| *other == b)
+            .map(|(edge, _, weight)| (edge, weight))
+            .min_by_key(|(edge, weight)| (*weight, *edge))
+    }
+    /// The `k` neighbors connected by the lightest edges. Sorted by ascending weight, then by edge ID.
+    ///
+    /// Self loops are skipped. Use [AdjListGraph::select_neighbors] to include them.
+    ///
+    /// Returns an error if `node` does not exist.
+    pub fn nearest_neighbors(
+        &self,
+        node: NodeID,
+        k: usize,
+    ) -> Result<Vec<(NodeID, EdgeID, u32)>, GraphError> {
+        self.select_neighbors(node, k, NeighborSelection::default())
+    }
+    /// The `k` neighbors connected by the heaviest edges. Sorted by descending weight, then by ascending edge ID.
+    ///
+    /// Self loops are skipped. Use [AdjListGraph::select_neighbors] to include them.
+    ///
+    /// Returns an error if `node` does not exist.
+    pub fn heaviest_neighbors(
+        &self,
+        node: NodeID,
+        k: usize,
+    ) -> Result<Vec<(NodeID, EdgeID, u32)>, GraphError> {
+        let selection = NeighborSelection {
+            order: NeighborOrder::Heaviest,
+            ..Default::default()
+        };
+        self.select_neighbors(node, k, selection)
+    }
+    /// Selects up to `k` neighbors of the node by the weight of the connecting edge.
+    ///
+    /// Removed edges are skipped. Only the selected neighbors are sorted, so this is O(degree + k log k).
+    ///
+    /// Returns an error if `node` does not exist.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{AdjListGraph, NeighborSelection};
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+    /// graph.connect_nodes_with_weight(a, b, 3).unwrap();
+    /// graph.connect_nodes_with_weight(a, c, 1).unwrap();
+    /// graph.connect_nodes_with_weight(a, a, 2).unwrap();
+    ///
+    /// let selection = NeighborSelection {
+    ///     include_self_loops: true,
+    ///     ..Default::default()
+    /// };
+    /// let nearest = graph.select_neighbors(a, 2, selection).unwrap();
+    /// assert_eq!(
+    ///     nearest.iter().map(|(node, _, _)| *node).collect::<Vec<_>>(),
+    ///     vec![c, a]
+    /// );
+    /// ```
+    pub fn select_neighbors(
+        &self,
+        node: NodeID,
+        k: usize,
+        selection: NeighborSelection,
+    ) -> Result<Vec<(NodeID, EdgeID, u32)>, GraphError> {
+        self.check_node_exists(node)?;
+        let mut neighbors: Vec<(NodeID, EdgeID, u32)> = self[node]
+            .edges
+            .iter()
+            .filter_map(|edge_id| {
+                let edge = self.get_edge(*edge_id)?;
+                let next = edge.other_node(node);
+                (selection.include_self_loops || next != node).then_some((
+                    next,
+                    *edge_id,
+                    edge.weight(),
+                ))
+            })
+            .collect();
+        let cmp = |(_, edge_a, weight_a): &(NodeID, EdgeID, u32),
+                   (_, edge_b, weight_b): &(NodeID, EdgeID, u32)| {
+            let by_weight = match selection.order {
+                NeighborOrder::Lightest => weight_a.cmp(weight_b),
+                NeighborOrder::Heaviest => weight_b.cmp(weight_a),
+            };
+            by_weight.then(edge_a.cmp(edge_b))
+        };
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        if k < neighbors.len() {
+            neighbors.select_nth_unstable_by(k - 1, cmp);
+            neighbors.truncate(k);
+        }
+        neighbors.sort_unstable_by(cmp);
+        Ok(neighbors)
+    }
+
     pub fn remove_edge(&mut self, edge: EdgeID) {
+        self.touch();
         let (node_a, node_b) = { &self.edges[edge.0].nodes() };
         self[node_a].remove_edge(edge);
         self[node_b].remove_edge(edge);
@@ -320,13 +1045,74 @@ impl<T> AdjListGraph<T> {
     ///
     /// Removed Node and connected edges will be pushed into the empty slots.
     pub fn remove_node(&mut self, node: NodeID) -> Option<T> {
+        self.touch();
         let node_value = mem::take(&mut self.nodes[node.0].edges);
         for edge in node_value {
             self.remove_edge(edge);
         }
         self.empty_node_slots.push_back(node);
+        if let Some(order) = &mut self.insertion_order {
+            order.forget(node);
+        }
         self.nodes[node.0].clear()
     }
+    /// Removes many edges. Edges that do not exist, were already removed or appear more than once are skipped.
+    pub fn remove_edges(&mut self, ids: &[EdgeID]) {
+        for edge in ids {
+            if self.does_edge_id_exist(*edge) {
+                self.remove_edge(*edge);
+            }
+        }
+    }
+    /// Removes many nodes and the edges connected to them.
+    ///
+    /// Returns the removed values in the order of `ids`. `None` for nodes that do not exist, were already removed or appear more than once.
+    ///
+    /// Edges between two removed nodes are only removed once, and the edge sets of the removed nodes are dropped instead of updated.
+    pub fn remove_nodes(&mut self, ids: &[NodeID]) -> Vec<Option<T>> {
+        self.touch();
+        let mut removing = vec![false; self.nodes.len()];
+        for id in ids {
+            if self.does_node_id_exist(*id) {
+                removing[id.0] = true;
+            }
+        }
+        let mut values = Vec::with_capacity(ids.len());
+        for id in ids {
+            if !removing.get(id.0).is_some_and(|removing| *removing)
+                || self.empty_node_slots.contains(id)
+            {
+                values.push(None);
+                continue;
+            }
+            for edge in mem::take(&mut self.nodes[id.0].edges) {
+                if self.empty_edge_slots.contains(&edge) {
+                    // Shared with a node removed earlier in this batch.
+                    continue;
+                }
+                let other = self.edges[edge.0].other_node(*id);
+                if !removing[other.0] {
+                    self.nodes[other.0].remove_edge(edge);
+                }
+                self.edges[edge.0].clear();
+                self.empty_edge_slots.push_back(edge);
+            }
+            self.empty_node_slots.push_back(*id);
+            if let Some(order) = &mut self.insertion_order {
+                order.forget(*id);
+            }
+            values.push(self.nodes[id.0].clear());
+        }
+        values
+    }
+    /// [AdjListGraph::remove_nodes] followed by [AdjListGraph::remove_dead_values].
+    ///
+    /// The node and edge arrays are each rewritten once, no matter how many nodes are removed. Slots that were dead before the call are compacted too.
+    pub fn remove_nodes_and_compact(&mut self, ids: &[NodeID]) -> Vec<Option<T>> {
+        let values = self.remove_nodes(ids);
+        self.remove_dead_values();
+        values
+    }
     pub fn number_of_nodes(&self) -> usize {
         self.nodes.len() - self.empty_node_slots.len()
     }
@@ -352,61 +1138,62 @@ impl<T> AdjListGraph<T> {
     /// Removes all nodes and edges that are in the unused slots.
     ///
     /// This will update the indexes of the nodes and edges.
-    pub fn remove_dead_values(&mut self)
-    where
-        T: Clone,
-    {
+    pub fn remove_dead_values(&mut self) {
+        let _span = debug_span!(
+            "remove_dead_values",
+            dead_nodes = self.empty_node_slots.len(),
+            dead_edges = self.empty_edge_slots.len()
+        )
+        .entered();
+        self.touch();
         if !self.empty_edge_slots.is_empty() {
             self.remove_dead_edges();
         }
         if !self.empty_node_slots.is_empty() {
             self.remove_dead_nodes();
         }
+        debug!(
+            nodes = self.nodes.len(),
+            edges = self.edges.len(),
+            "Compacted the graph"
+        );
     }
-    fn remove_dead_nodes(&mut self)
-    where
-        T: Clone,
-    {
+    fn remove_dead_nodes(&mut self) {
         let Self {
             nodes,
             empty_node_slots,
             edges,
+            node_weights,
+            insertion_order,
             ..
         } = self;
-
-        let mut empty_node_slots: Vec<_> = mem::take(empty_node_slots).into();
-        empty_node_slots.sort();
-
-        let first_index = empty_node_slots.first().map(|x| x.0).unwrap_or(usize::MAX);
-        let mut new_nodes = Vec::with_capacity(nodes.len() - empty_node_slots.len());
-
-        for (old_index, node) in nodes.iter().enumerate().map(|(i, x)| (NodeID(i), x)) {
-            if old_index < first_index {
-                // The node index did not change.
-                new_nodes.push(node.clone());
-                continue;
-            }
-            if empty_node_slots.binary_search_contains(&old_index) {
-                // This is a dead node. So we skip it.
-                continue;
-            }
-            // Alright this node is not dead.
-
-            // First Update All the edges with the new index.
-            let new_index = NodeID(new_nodes.len());
+        let dead = mem::take(empty_node_slots)
+            .into_iter()
+            .map(|x| x.0)
+            .collect();
+        remove_dead_slots(nodes, dead, |old_index, new_index, node| {
+            // Update all the edges with the new index.
             for edge in &node.edges {
                 let Edge { node_a, node_b, .. } = &mut edges[edge.0];
                 if *node_a == old_index {
-                    *node_a = new_index;
+                    *node_a = NodeID(new_index);
                 }
                 if *node_b == old_index {
-                    *node_b = new_index;
+                    *node_b = NodeID(new_index);
                 }
             }
-            // Push the new node.
-            new_nodes.push(node.clone());
+            // Nodes past the end of the weights have the default of 1, which must not keep the weight of the previous occupant.
+            if new_index < node_weights.len() {
+                node_weights[new_index] = node_weights.get(old_index).copied().unwrap_or(1);
+            }
+            if let Some(order) = insertion_order.as_mut() {
+                order.moved(old_index, new_index);
+            }
+        });
+        node_weights.truncate(nodes.len());
+        if let Some(order) = insertion_order {
+            order.truncate(nodes.len());
         }
-        *nodes = new_nodes;
     }
     fn remove_dead_edges(&mut self) {
         let Self {
@@ -415,51 +1202,101 @@ impl<T> AdjListGraph<T> {
             empty_edge_slots,
             ..
         } = self;
-        let mut replace_node_edges =
-            |node: NodeID, old_index_as_edge_id: EdgeID, new_index: EdgeID| {
+        let dead = mem::take(empty_edge_slots)
+            .into_iter()
+            .map(|x| x.0)
+            .collect();
+        remove_dead_slots(edges, dead, |old_index, new_index, edge| {
+            // Update the nodes to reflect the new index.
+            let (old_index, new_index) = (EdgeID(old_index), EdgeID(new_index));
+            for node in [edge.node_a, edge.node_b] {
                 let node = &mut nodes[node.0];
-                if node.edges.remove(&old_index_as_edge_id) {
+                if node.edges.remove(&old_index) {
                     node.edges.insert(new_index);
                 }
-            };
-
-        let mut empty_edge_slots: Vec<_> = mem::take(empty_edge_slots).into();
-        empty_edge_slots.sort();
-
-        let first_index = empty_edge_slots.first().map(|x| x.0).unwrap_or(usize::MAX);
-        let mut new_edges = Vec::with_capacity(edges.len() - empty_edge_slots.len());
-        // TODO: Optimize this by mutating the original edges array instead of creating a new one.
-        for (old_index, edge) in edges.iter().enumerate() {
-            let old_index_as_edge_id = EdgeID(old_index);
-            if old_index < first_index {
-                // The edge index did not change.
-                new_edges.push(edge.clone());
-                continue;
             }
-            if empty_edge_slots.binary_search_contains(&old_index_as_edge_id) {
-                // This is a dead edge. So we skip it.
-                continue;
-            }
-            // Alright this edge is not dead.
-            let (node_a, node_b) = edge.nodes();
-
-            // Push the new edge.
-            let new_index: EdgeID = new_edges.push_with_wrapped_id(edge.clone());
-            // Update the nodes to reflect the new index.
-            replace_node_edges(node_a, old_index_as_edge_id, new_index);
-            replace_node_edges(node_b, old_index_as_edge_id, new_index);
-        }
-        *edges = new_edges;
+        });
     }
 
+    /// True if the node is in range and has not been removed. Use this before trusting a stored ID.
+    ///
+    /// [AdjListGraph::does_node_id_exist] is the same check.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{AdjListGraph, NodeID};
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let a = graph.add_node('A');
+    /// assert!(graph.is_node_alive(a));
+    ///
+    /// graph.remove_node(a);
+    /// assert!(!graph.is_node_alive(a));
+    /// assert!(!graph.is_node_alive(NodeID(10)));
+    /// ```
+    pub fn is_node_alive(&self, id: NodeID) -> bool {
+        id.0 < self.nodes.len() && !self.empty_node_slots.contains(&id)
+    }
+    /// True if the edge is in range and has not been removed. Removing either of its nodes also removes the edge.
+    ///
+    /// [AdjListGraph::does_edge_id_exist] is the same check.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+    /// let edge = graph.connect_nodes(a, b).unwrap();
+    /// assert!(graph.is_edge_alive(edge));
+    ///
+    /// graph.remove_node(b);
+    /// assert!(!graph.is_edge_alive(edge));
+    /// ```
+    pub fn is_edge_alive(&self, id: EdgeID) -> bool {
+        id.0 < self.edges.len() && !self.empty_edge_slots.contains(&id)
+    }
+    /// Returns the node. `None` if it does not exist or has been removed.
     pub fn get_node(&self, id: NodeID) -> Option<&Node<T>> {
-        self.nodes.get(id.0)
+        self.nodes.get(id.0).filter(|_| self.is_node_alive(id))
+    }
+    /// Returns the edge. `None` if it does not exist or has been removed.
+    pub fn get_edge(&self, id: EdgeID) -> Option<&Edge> {
+        self.edges.get(id.0).filter(|_| self.is_edge_alive(id))
+    }
+    /// Returns mutable references to two different nodes at the same time.
+    ///
+    /// Returns `None` if `a == b` or either node does not exist.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let a = graph.add_node("A".to_string());
+    /// let b = graph.add_node("B".to_string());
+    ///
+    /// let (node_a, node_b) = graph.node_pair_mut(a, b).unwrap();
+    /// std::mem::swap(node_a.value_mut(), node_b.value_mut());
+    ///
+    /// assert_eq!(graph[a].value(), "B");
+    /// assert!(graph.node_pair_mut(a, a).is_none());
+    /// ```
+    pub fn node_pair_mut(&mut self, a: NodeID, b: NodeID) -> Option<(&mut Node<T>, &mut Node<T>)> {
+        if a == b || !self.does_node_id_exist(a) || !self.does_node_id_exist(b) {
+            return None;
+        }
+        self.touch();
+        if a < b {
+            let (first, second) = self.nodes.split_at_mut(b.0);
+            Some((&mut first[a.0], &mut second[0]))
+        } else {
+            let (first, second) = self.nodes.split_at_mut(a.0);
+            Some((&mut second[0], &mut first[b.0]))
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::adjacency_list::*;
+    use crate::{adjacency_list::*, GraphError};
 
     #[test]
     pub fn basic_graph() {
@@ -478,6 +1315,269 @@ mod test {
         println!("{:#?}", graph);
     }
     #[test]
+    pub fn with_capacity() {
+        let mut graph = AdjListGraph::with_capacity(100, 99);
+        let (node_capacity, edge_capacity) = (graph.node_capacity(), graph.edge_capacity());
+        assert!(node_capacity >= 100 && edge_capacity >= 99);
+
+        let nodes = graph.add_nodes_from_iterator((0..100).map(|i| i.to_string()));
+        for pair in nodes.windows(2) {
+            graph.connect_nodes(pair[0], pair[1]).unwrap();
+        }
+        assert_eq!(graph.node_capacity(), node_capacity);
+        assert_eq!(graph.edge_capacity(), edge_capacity);
+
+        graph.reserve(10, 0);
+        assert!(graph.node_capacity() >= 110);
+    }
+    #[test]
+    pub fn weighted_connection() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node("A".to_string());
+        let b = graph.add_node("B".to_string());
+        let c = graph.add_node("C".to_string());
+
+        graph.connect_nodes_with_weight(a, b, 3).unwrap();
+        let edge = graph.connect_nodes_with_weight(b, c, 7).unwrap();
+
+        assert_eq!(graph.connection(c, b), Some((edge, 7)));
+        assert_eq!(graph.connection(b, c), Some((edge, 7)));
+        assert_eq!(graph.connection(a, c), None);
+    }
+    #[test]
+    pub fn swap_values_through_pair() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node("A".to_string());
+        let b = graph.add_node("B".to_string());
+        let c = graph.add_node("C".to_string());
+
+        let (node_c, node_a) = graph.node_pair_mut(c, a).unwrap();
+        std::mem::swap(node_c.value_mut(), node_a.value_mut());
+        assert_eq!(graph[a].value(), "C");
+        assert_eq!(graph[c].value(), "A");
+
+        graph.remove_node(b);
+        assert!(graph.node_pair_mut(a, b).is_none());
+        assert!(graph.node_pair_mut(a, NodeID(10)).is_none());
+    }
+    #[test]
+    pub fn connect_checked() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node("A".to_string());
+        let b = graph.add_node("B".to_string());
+        let c = graph.add_node("C".to_string());
+        graph.remove_node(c);
+
+        assert_eq!(
+            graph.connect_nodes_checked(a, c, 1),
+            Err(GraphError::NodeDoesNotExist { node: c })
+        );
+        assert_eq!(
+            graph.connect_nodes_checked(NodeID(10), a, 1),
+            Err(GraphError::NodeDoesNotExist { node: NodeID(10) })
+        );
+        assert!(!graph.nodes[c.0].has_edge(EdgeID(0)));
+        assert_eq!(graph.number_of_edges(), 0);
+
+        let edge = graph.connect_nodes_checked(a, b, 1).unwrap();
+        assert_eq!(graph.connection(a, b), Some((edge, 1)));
+    }
+    #[test]
+    #[should_panic(expected = "NodeID(1) was removed from the graph (dead slot); use get_node")]
+    pub fn index_removed_node() {
+        let mut graph = AdjListGraph::default();
+        let [_, b] = graph.add_nodes_from_sized_array(["A", "B"]);
+        graph.remove_node(b);
+        assert!(graph.get_node(b).is_none());
+        let _ = &graph[b];
+    }
+    #[test]
+    #[should_panic(expected = "EdgeID(0) was removed from the graph (dead slot); use get_edge")]
+    pub fn index_removed_edge() {
+        let mut graph = AdjListGraph::default();
+        let [a, b] = graph.add_nodes_from_sized_array(["A", "B"]);
+        let edge = graph.connect_nodes(a, b).unwrap();
+        graph.remove_edge(edge);
+        assert!(graph.get_edge(edge).is_none());
+        graph[edge].weight = 1;
+    }
+    #[test]
+    #[should_panic(expected = "NodeID(5) does not exist in the graph")]
+    pub fn index_out_of_range() {
+        let graph = AdjListGraph::<&str>::default();
+        let _ = &graph[NodeID(5)];
+    }
+    #[test]
+    pub fn serde_version() {
+        let mut graph = AdjListGraph::default();
+        let [a, b] = graph.add_nodes_from_sized_array(["A".to_string(), "B".to_string()]);
+        graph.connect_nodes_with_weight(a, b, 3).unwrap();
+
+        let json = serde_json::to_value(&graph).unwrap();
+        assert_eq!(json["version"], 2);
+        assert!(json.get("node_weights").is_none());
+        crate::testing::assert_round_trips_json(&graph);
+
+        // Written before the version field existed.
+        let mut legacy = json.clone();
+        legacy.as_object_mut().unwrap().remove("version");
+        let legacy: AdjListGraph<String> = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy, graph);
+
+        let mut newer = json;
+        newer["version"] = 4.into();
+        let error = serde_json::from_value::<AdjListGraph<String>>(newer).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Unsupported graph format version 4"),
+            "{error}"
+        );
+    }
+    #[test]
+    pub fn connected_to_itself_with_dangling_edge() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d] = graph.add_nodes_from_sized_array(['A', 'B', 'C', 'D']);
+        let edge = graph.connect_nodes(a, b).unwrap();
+        graph.remove_edge(edge);
+        // A dangling reference to the removed edge. Its endpoints are both cleared.
+        graph[a].edges.insert(edge);
+        assert!(!graph.is_node_connected_to_itself(a));
+        assert!(!graph.is_node_connected_to_node(a, b));
+
+        // The slot is reused by an edge that does not touch `a`.
+        assert_eq!(graph.connect_nodes(c, d).unwrap(), edge);
+        assert!(!graph.is_node_connected_to_node(a, c));
+        assert!(!graph.is_node_connected_to_itself(a));
+        assert!(graph.is_node_connected_to_node(c, d));
+
+        graph.connect_nodes(a, a).unwrap();
+        assert!(graph.is_node_connected_to_itself(a));
+    }
+    #[test]
+    pub fn connect_or_update() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+
+        let (edge, created) = graph.connect_or_update(a, b, 4).unwrap();
+        assert!(created);
+        assert_eq!(graph.connect_or_update(b, a, 7).unwrap(), (edge, false));
+        assert_eq!(graph.connection(a, b), Some((edge, 7)));
+        assert_eq!(graph.number_of_edges(), 1);
+
+        let keep_min =
+            |weight: u32| move |old: Option<u32>| old.map_or(weight, |old| old.min(weight));
+        let (edge, created) = graph.connect_or_update_with(b, c, keep_min(9)).unwrap();
+        assert!(created);
+        graph.connect_or_update_with(b, c, keep_min(12)).unwrap();
+        assert_eq!(graph[edge].weight(), 9);
+        graph.connect_or_update_with(b, c, keep_min(2)).unwrap();
+        assert_eq!(graph[edge].weight(), 2);
+
+        graph.remove_node(c);
+        assert_eq!(
+            graph.connect_or_update(b, c, 1),
+            Err(GraphError::NodeDoesNotExist { node: c })
+        );
+        assert!(graph.connect_or_update(a, NodeID(10), 1).is_err());
+    }
+    #[test]
+    pub fn policies() {
+        for self_loops in [PolicyAction::Allow, PolicyAction::Reject] {
+            for parallel_edges in [PolicyAction::Allow, PolicyAction::Reject] {
+                let policy = GraphPolicy {
+                    self_loops,
+                    parallel_edges,
+                };
+                let mut graph = AdjListGraph::with_policy(policy);
+                let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+                let first = graph.connect_nodes_with_weight(a, b, 1).unwrap();
+
+                let self_loop = graph.connect_nodes(a, a);
+                match self_loops {
+                    PolicyAction::Allow => assert!(self_loop.is_ok()),
+                    PolicyAction::Reject => {
+                        assert_eq!(self_loop, Err(GraphError::SelfLoopNotAllowed { node: a }))
+                    }
+                }
+                let parallel = graph.connect_nodes_with_weight(b, a, 2);
+                match parallel_edges {
+                    PolicyAction::Allow => {
+                        assert!(parallel.is_ok());
+                        assert_eq!(graph.connection(a, b), Some((first, 1)));
+                    }
+                    PolicyAction::Reject => assert_eq!(
+                        parallel,
+                        Err(GraphError::NodesAlreadyConnected {
+                            node_a: b,
+                            node_b: a,
+                            edge: first
+                        })
+                    ),
+                }
+                assert_eq!(graph.validate(), Ok(()));
+
+                let json = serde_json::to_string(&graph).unwrap();
+                let round_trip: AdjListGraph<char> = serde_json::from_str(&json).unwrap();
+                assert_eq!(round_trip.policy(), policy);
+            }
+        }
+        assert_eq!(
+            AdjListGraph::<char>::default().policy(),
+            GraphPolicy {
+                self_loops: PolicyAction::Allow,
+                parallel_edges: PolicyAction::Reject
+            }
+        );
+    }
+    #[test]
+    pub fn serde_after_removal() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] =
+            graph.add_nodes_from_sized_array(["A".to_string(), "B".to_string(), "C".to_string()]);
+        graph.connect_nodes_with_weight(a, b, 1).unwrap();
+        graph.connect_nodes_with_weight(b, c, 2).unwrap();
+        graph.remove_node(a);
+        assert!(serde_json::to_string(&graph).is_err());
+        graph.remove_dead_values();
+        crate::testing::assert_round_trips_json(&graph);
+    }
+    #[test]
+    pub fn batch_removal_matches_sequential() {
+        let graph = crate::adjacency_list::test_graphs::random_graph(200, 600, 7);
+        let ids: Vec<NodeID> = graph.node_ids().step_by(3).collect();
+        let mut input = ids.clone();
+        // Duplicates and removed nodes are skipped.
+        input.push(ids[0]);
+        input.push(NodeID(10_000));
+
+        let mut sequential = graph.clone();
+        let mut expected: Vec<Option<usize>> =
+            ids.iter().map(|id| sequential.remove_node(*id)).collect();
+        expected.extend([None, None]);
+
+        let mut batch = graph.clone();
+        assert_eq!(batch.remove_nodes(&input), expected);
+        assert_eq!(batch.number_of_nodes(), sequential.number_of_nodes());
+        assert_eq!(batch.number_of_edges(), sequential.number_of_edges());
+        assert!(batch.validate().is_ok());
+        crate::testing::assert_same_graph(&sequential, &batch);
+
+        let mut compacted = graph.clone();
+        assert_eq!(compacted.remove_nodes_and_compact(&input), expected);
+        assert!(!compacted.has_dead_nodes() && !compacted.has_dead_edges());
+        crate::testing::assert_same_graph(&sequential, &compacted);
+
+        let edges: Vec<EdgeID> = sequential.edge_ids().step_by(2).collect();
+        let mut edges_batch = sequential.clone();
+        for edge in &edges {
+            sequential.remove_edge(*edge);
+        }
+        edges_batch.remove_edges(&[edges.as_slice(), edges.as_slice()].concat());
+        assert_eq!(edges_batch.number_of_edges(), sequential.number_of_edges());
+        crate::testing::assert_same_graph(&sequential, &edges_batch);
+    }
+    #[test]
     pub fn cleanup_tests() {
         let mut graph = AdjListGraph::default();
         let a = graph.add_node("A".to_string());
@@ -504,4 +1604,484 @@ mod test {
         assert_eq!(graph.nodes.len(), 2);
         assert_eq!(graph.edges.len(), 1);
     }
+    /// The live edges by the values of their nodes, sorted so two graphs can be compared no matter their IDs.
+    fn edges_by_value(graph: &AdjListGraph<usize>) -> Vec<(usize, usize, u32)> {
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|edge| {
+                let (a, b) = edge.endpoints();
+                let (a, b) = (*graph[a].value(), *graph[b].value());
+                (a.min(b), a.max(b), edge.weight())
+            })
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+    fn assert_intact(graph: &AdjListGraph<usize>, step: &str) {
+        assert!(!graph.has_invalid_nodes(), "{step}");
+        assert!(!graph.has_invalid_edges(), "{step}");
+        assert_eq!(graph.validate(), Ok(()), "{step}");
+        for node in graph.node_ids() {
+            for edge in &graph[node].edges {
+                assert!(graph[*edge].nodes().0 == node || graph[*edge].nodes().1 == node);
+            }
+        }
+    }
+    #[test]
+    pub fn compaction_after_edge_then_node_removal() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d] = graph.add_nodes_from_sized_array([0, 1, 2, 3]);
+        let ab = graph.connect_nodes_with_weight(a, b, 1).unwrap();
+        graph.connect_nodes_with_weight(b, c, 2).unwrap();
+        graph.connect_nodes_with_weight(c, d, 3).unwrap();
+        graph.connect_nodes_with_weight(b, b, 4).unwrap();
+        graph.connect_nodes_with_weight(d, a, 5).unwrap();
+
+        // `b` was an endpoint of the removed edge, which must not be freed a second time.
+        graph.remove_edge(ab);
+        assert_intact(&graph, "remove edge");
+        graph.remove_node(b);
+        assert_intact(&graph, "remove node");
+        assert_eq!(graph.number_of_edges(), 2);
+        assert_eq!(edges_by_value(&graph), vec![(0, 3, 5), (2, 3, 3)]);
+
+        graph.remove_dead_values();
+        assert_intact(&graph, "compact");
+        assert!(!graph.has_dead_nodes() && !graph.has_dead_edges());
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(edges_by_value(&graph), vec![(0, 3, 5), (2, 3, 3)]);
+
+        let before = graph.clone();
+        graph.remove_dead_values();
+        crate::testing::assert_same_graph(&before, &graph);
+    }
+    #[test]
+    pub fn compaction_with_interleaved_removals() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut graph = crate::adjacency_list::test_graphs::random_graph(40, 120, 3);
+        let mut expected = edges_by_value(&graph);
+        let mut next_value = 40;
+        for step in 0..400 {
+            let nodes: Vec<NodeID> = graph.node_ids().collect();
+            let edges: Vec<EdgeID> = graph.edge_ids().collect();
+            let name = match rng.gen_range(0..10) {
+                0..=2 if !edges.is_empty() => {
+                    let edge = edges[rng.gen_range(0..edges.len())];
+                    let (a, b) = graph[edge].nodes();
+                    let (a, b) = (*graph[a].value(), *graph[b].value());
+                    let key = (a.min(b), a.max(b), graph[edge].weight());
+                    let index = expected.binary_search(&key).unwrap();
+                    expected.remove(index);
+                    graph.remove_edge(edge);
+                    "remove edge"
+                }
+                3..=4 if nodes.len() > 1 => {
+                    let node = nodes[rng.gen_range(0..nodes.len())];
+                    let value = graph.remove_node(node).unwrap();
+                    expected.retain(|(a, b, _)| *a != value && *b != value);
+                    "remove node"
+                }
+                5 => {
+                    graph.remove_dead_values();
+                    "compact"
+                }
+                _ => {
+                    // Reuses a dead slot if there is one.
+                    let node = graph.add_node(next_value);
+                    next_value += 1;
+                    for _ in 0..2 {
+                        let other = nodes[rng.gen_range(0..nodes.len())];
+                        let weight = rng.gen_range(0..100);
+                        graph.connect_nodes_checked(node, other, weight).unwrap();
+                        let other = *graph[other].value();
+                        expected.push((other, next_value - 1, weight));
+                    }
+                    let weight = rng.gen_range(0..100);
+                    graph.connect_nodes_checked(node, node, weight).unwrap();
+                    expected.push((next_value - 1, next_value - 1, weight));
+                    expected.sort_unstable();
+                    "add node"
+                }
+            };
+            let step = format!("step {step}: {name}");
+            assert_intact(&graph, &step);
+            assert_eq!(edges_by_value(&graph), expected, "{step}");
+        }
+        graph.remove_dead_values();
+        assert_intact(&graph, "final compaction");
+        assert_eq!(graph.nodes.len(), graph.number_of_nodes());
+        assert_eq!(graph.edges.len(), graph.number_of_edges());
+        assert_eq!(edges_by_value(&graph), expected);
+    }
+    #[test]
+    pub fn bulk_connect() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        let edges = graph.connect_many([(a, b, 1), (b, c, 2)], true);
+        assert!(edges.is_ok());
+        assert_eq!(edges.created.len(), 2);
+        assert_eq!(graph.number_of_edges(), 2);
+
+        let result = graph.connect_many([(c, a, 3), (b, a, 4), (a, a, 5)], true);
+        assert_eq!(
+            result.failures,
+            vec![(
+                1,
+                GraphError::NodesAlreadyConnected {
+                    node_a: b,
+                    node_b: a,
+                    edge: edges.created[0]
+                }
+            )]
+        );
+        // `c -- a` was connected before the error. `a -- a` was never reached.
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(graph.number_of_edges(), 3);
+    }
+    #[test]
+    pub fn bulk_connect_collects_errors() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d] = graph.add_nodes_from_sized_array(["A", "B", "C", "D"]);
+        graph.connect_nodes(a, b).unwrap();
+        let missing = NodeID(10);
+        let result = graph.connect_many(
+            [(b, c, 1), (a, b, 2), (c, d, 3), (missing, a, 4), (d, a, 5)],
+            false,
+        );
+        assert!(!result.is_ok());
+        assert_eq!(result.failures.len(), 2);
+        assert_eq!(result.failures[0].0, 1);
+        assert!(matches!(
+            result.failures[0].1,
+            GraphError::NodesAlreadyConnected { .. }
+        ));
+        assert_eq!(
+            result.failures[1],
+            (3, GraphError::NodeDoesNotExist { node: missing })
+        );
+        assert_eq!(result.created.len(), 3);
+        for (edge, (x, y)) in result.created.iter().zip([(b, c), (c, d), (d, a)]) {
+            assert_eq!(graph.connection(x, y).unwrap().0, *edge);
+        }
+        assert_eq!(graph.number_of_edges(), 4);
+    }
+    #[test]
+    pub fn from_named_edges() {
+        let graph = AdjListGraph::from_named_edges(
+            [
+                ("A".to_string(), "B".to_string(), 1),
+                ("B".to_string(), "C".to_string(), 2),
+                ("C".to_string(), "A".to_string(), 3),
+                ("B".to_string(), "A".to_string(), 4),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(graph.number_of_nodes(), 3);
+        assert_eq!(graph.number_of_edges(), 3);
+        let a = graph.find_node_with_that_equals(&"A".to_string()).unwrap();
+        let b = graph.find_node_with_that_equals(&"B".to_string()).unwrap();
+        assert_eq!((a, b), (NodeID(0), NodeID(1)));
+        assert_eq!(graph.connection(a, b).map(|(_, weight)| weight), Some(1));
+    }
+    #[test]
+    pub fn nearest_and_heaviest_neighbors() {
+        let mut graph = AdjListGraph::default();
+        let center = graph.add_node(0);
+        let leaves: Vec<NodeID> = (1..=5).map(|value| graph.add_node(value)).collect();
+        let weights = [4, 1, 5, 3, 2];
+        let edges: Vec<EdgeID> = leaves
+            .iter()
+            .zip(weights)
+            .map(|(leaf, weight)| {
+                graph
+                    .connect_nodes_with_weight(center, *leaf, weight)
+                    .unwrap()
+            })
+            .collect();
+        graph.connect_nodes_with_weight(center, center, 0).unwrap();
+
+        assert_eq!(
+            graph.nearest_neighbors(center, 3).unwrap(),
+            vec![
+                (leaves[1], edges[1], 1),
+                (leaves[4], edges[4], 2),
+                (leaves[3], edges[3], 3)
+            ]
+        );
+        assert_eq!(
+            graph.heaviest_neighbors(center, 2).unwrap(),
+            vec![(leaves[2], edges[2], 5), (leaves[0], edges[0], 4)]
+        );
+        // More than the degree returns every neighbor.
+        let all = graph.nearest_neighbors(center, 100).unwrap();
+        assert_eq!(
+            all.iter().map(|(_, _, weight)| *weight).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(graph.nearest_neighbors(center, 0).unwrap().is_empty());
+        let with_loops = NeighborSelection {
+            include_self_loops: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            graph.select_neighbors(center, 1, with_loops).unwrap()[0].0,
+            center
+        );
+        assert_eq!(
+            graph.nearest_neighbors(leaves[0], 5).unwrap(),
+            vec![(center, edges[0], 4)]
+        );
+
+        // Ties are broken by edge ID in both directions.
+        graph.remove_edge(edges[2]);
+        let tie = graph
+            .connect_nodes_with_weight(center, leaves[2], 1)
+            .unwrap();
+        assert_eq!(
+            graph.nearest_neighbors(center, 2).unwrap()[1],
+            (leaves[2], tie, 1)
+        );
+        assert_eq!(
+            graph.heaviest_neighbors(center, 5).unwrap()[4],
+            (leaves[2], tie, 1)
+        );
+
+        graph.remove_node(leaves[0]);
+        assert_eq!(
+            graph.nearest_neighbors(leaves[0], 1),
+            Err(GraphError::NodeDoesNotExist { node: leaves[0] })
+        );
+        assert_eq!(
+            graph.select_neighbors(NodeID(100), 1, with_loops),
+            Err(GraphError::NodeDoesNotExist { node: NodeID(100) })
+        );
+    }
+    #[test]
+    pub fn insert_node_at() {
+        let mut graph = AdjListGraph::default();
+        graph.insert_node_at(NodeID(10), "K").unwrap();
+        assert_eq!(graph.number_of_nodes(), 1);
+        assert_eq!(graph[NodeID(10)].value(), &"K");
+        graph.validate().unwrap();
+
+        let a = graph.add_node("A");
+        assert!(a.0 < 10);
+        graph.connect_nodes(a, NodeID(10)).unwrap();
+        graph.validate().unwrap();
+
+        assert_eq!(
+            graph.insert_node_at(NodeID(10), "X"),
+            Err(GraphError::SlotOccupied { node: NodeID(10) })
+        );
+        graph.insert_node_at(NodeID(5), "F").unwrap();
+        graph.validate().unwrap();
+        let mut filled = vec![a, NodeID(5), NodeID(10)];
+        while graph.has_dead_nodes() {
+            let node = graph.add_node("_");
+            assert!(!filled.contains(&node));
+            filled.push(node);
+        }
+        assert_eq!(graph.number_of_nodes(), 11);
+        assert_eq!(graph.add_node("L"), NodeID(11));
+        graph.validate().unwrap();
+    }
+    #[test]
+    pub fn from_raw_parts_matches_connecting() {
+        let graph = AdjListGraph::from_raw_parts(
+            vec!['A', 'B', 'C'],
+            vec![
+                (NodeID(0), NodeID(1), 2),
+                (NodeID(1), NodeID(1), 5),
+                (NodeID(2), NodeID(0), 3),
+            ],
+        );
+        let mut expected = AdjListGraph::default();
+        let [a, b, c] = expected.add_nodes_from_sized_array(['A', 'B', 'C']);
+        expected.connect_nodes_with_weight(a, b, 2).unwrap();
+        expected.connect_nodes_with_weight(b, b, 5).unwrap();
+        expected.connect_nodes_with_weight(c, a, 3).unwrap();
+        assert_eq!(graph, expected);
+        assert_eq!(graph.connection(a, c), Some((EdgeID(2), 3)));
+        assert!(graph.is_node_connected_to_itself(b));
+
+        let empty: AdjListGraph<u8> = AdjListGraph::from_raw_parts(vec![], vec![]);
+        assert!(empty.is_empty());
+    }
+    #[test]
+    #[should_panic]
+    pub fn from_raw_parts_out_of_range() {
+        AdjListGraph::from_raw_parts(vec!['A'], vec![(NodeID(0), NodeID(1), 0)]);
+    }
+    #[test]
+    pub fn node_weights() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, _, d] = graph.add_nodes_from_sized_array(['A', 'B', 'C', 'D']);
+        graph.connect_nodes(a, d).unwrap();
+        graph.set_node_weight(b, 4).unwrap();
+        graph.set_node_weight(d, 9).unwrap();
+        assert_eq!(graph.node_weight(a), Some(1));
+        assert_eq!(graph.node_weight(NodeID(7)), None);
+        assert_eq!(
+            graph.set_node_weight(NodeID(7), 2),
+            Err(GraphError::NodeDoesNotExist { node: NodeID(7) })
+        );
+
+        graph.remove_node(b);
+        assert_eq!(graph.node_weight(b), None);
+        graph.remove_dead_values();
+        let [c, d] = [&'C', &'D'].map(|value| graph.find_node_with_that_equals(value).unwrap());
+        assert_eq!(graph.node_weight(d), Some(9));
+        assert_eq!(graph.node_weight(c), Some(1));
+
+        let json = serde_json::to_value(&graph).unwrap();
+        assert_eq!(json["version"], 3);
+        assert_eq!(json["node_weights"], serde_json::json!([1, 1, 9]));
+        let copy: AdjListGraph<char> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            copy.node_ids()
+                .map(|node| copy.node_weight(node))
+                .collect::<Vec<_>>(),
+            vec![Some(1), Some(1), Some(9)]
+        );
+        let mut short = json;
+        short["node_weights"] = serde_json::json!([1]);
+        assert!(serde_json::from_value::<AdjListGraph<char>>(short).is_err());
+
+        assert_eq!(copy, graph);
+        let mut lighter = graph.clone();
+        lighter.set_node_weight(d, 2).unwrap();
+        assert_ne!(lighter, graph);
+
+        // Copies into a new graph keep the weights.
+        let (filtered, ids) = graph.filter_nodes(|value| *value != 'C');
+        assert_eq!(filtered.node_weight(ids[&d]), Some(9));
+        let mapping: crate::collections::HashMap<NodeID, NodeID> = graph
+            .node_ids()
+            .map(|node| (node, NodeID(node.0 + 1)))
+            .collect();
+        let relabeled = graph.relabel(&mapping).unwrap();
+        assert_eq!(relabeled.node_weight(NodeID(d.0 + 1)), Some(9));
+        assert_eq!(relabeled, graph);
+
+        // A node added after the weights were stored moves into a slot with a weight.
+        let mut late = AdjListGraph::default();
+        let [a, b] = late.add_nodes_from_sized_array(['A', 'B']);
+        late.set_node_weight(a, 5).unwrap();
+        late.set_node_weight(b, 7).unwrap();
+        late.add_node('C');
+        late.remove_node(a);
+        late.remove_dead_values();
+        let c = late.find_node_with_that_equals(&'C').unwrap();
+        assert_eq!(late.node_weight(c), Some(1));
+        assert_eq!(late.node_weight(NodeID(0)), Some(7));
+
+        // A reused slot starts over at 1.
+        graph.remove_node(d);
+        assert_eq!(graph.add_node('E'), d);
+        assert_eq!(graph.node_weight(d), Some(1));
+    }
+    #[test]
+    pub fn deserialize_cleared_slots() {
+        let json = serde_json::json!({
+            "version": 3,
+            "nodes": [
+                { "value": 'A', "edges": [0] },
+                { "value": null, "edges": [] },
+                { "value": 'C', "edges": [0] },
+            ],
+            "edges": [
+                { "weight": 2, "node_a": 0, "node_b": 2 },
+                { "weight": 0, "node_a": usize::MAX, "node_b": usize::MAX },
+            ],
+        });
+        let graph: AdjListGraph<char> = serde_json::from_value(json.clone()).unwrap();
+        graph.validate().unwrap();
+        assert_eq!(graph.number_of_nodes(), 2);
+        assert_eq!(graph.number_of_edges(), 1);
+        assert!(graph.get_node(NodeID(1)).is_none());
+        assert!(graph.get_edge(EdgeID(1)).is_none());
+        assert_eq!(
+            graph
+                .iter_nodes()
+                .map(|(_, value)| *value)
+                .collect::<Vec<_>>(),
+            vec!['A', 'C']
+        );
+        let mut graph = graph;
+        assert_eq!(graph.add_node('B'), NodeID(1));
+        assert_eq!(
+            graph.connect_nodes(NodeID(0), NodeID(1)).unwrap(),
+            EdgeID(1)
+        );
+
+        let mut partly_cleared_node = json.clone();
+        partly_cleared_node["nodes"][1]["edges"] = serde_json::json!([0]);
+        let error = serde_json::from_value::<AdjListGraph<char>>(partly_cleared_node).unwrap_err();
+        assert!(
+            error.to_string().contains("NodeID(1) is invalid"),
+            "{error}"
+        );
+
+        let mut to_removed_node = json;
+        to_removed_node["edges"][0]["node_b"] = 1.into();
+        let error = serde_json::from_value::<AdjListGraph<char>>(to_removed_node).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("EdgeID(0) is invalid because it connects a removed node"),
+            "{error}"
+        );
+    }
+    #[test]
+    pub fn deserialize_edges_to_missing_nodes() {
+        let json = serde_json::json!({
+            "version": 3,
+            "nodes": [
+                { "value": 'A', "edges": [0] },
+                { "value": 'B', "edges": [] },
+            ],
+            "edges": [
+                { "weight": 2, "node_a": 0, "node_b": usize::MAX },
+            ],
+        });
+        let error = serde_json::from_value::<AdjListGraph<char>>(json.clone()).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("EdgeID(0) is invalid because only one of its nodes is cleared"),
+            "{error}"
+        );
+
+        let mut out_of_range = json;
+        out_of_range["edges"][0]["node_b"] = 2.into();
+        let error = serde_json::from_value::<AdjListGraph<char>>(out_of_range).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("EdgeID(0) is invalid because it connects a node that does not exist"),
+            "{error}"
+        );
+    }
+    #[test]
+    pub fn deserialize_dangling_edge_reference() {
+        let json = serde_json::json!({
+            "version": 2,
+            "nodes": [
+                { "value": 'A', "edges": [0, 7] },
+                { "value": 'B', "edges": [0] },
+            ],
+            "edges": [
+                { "weight": 2, "node_a": 0, "node_b": 1 },
+            ],
+        });
+        let error = serde_json::from_value::<AdjListGraph<char>>(json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(&GraphError::InvalidNode { node: NodeID(0) }.to_string()),
+            "{error}"
+        );
+    }
 }
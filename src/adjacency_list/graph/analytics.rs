@@ -0,0 +1,181 @@
+//! Results of the read only algorithms, cached until the graph changes.
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::adjacency_list::*;
+
+use super::{AdjListGraph, GraphStats};
+
+impl<T> AdjListGraph<T> {
+    /// Cached versions of the analysis functions.
+    ///
+    /// Results are shared with [Arc] and reused until [AdjListGraph::content_version] changes.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph: AdjListGraph<_> = [("a", "b"), ("c", "d")].into();
+    /// let components = graph.analytics().components();
+    /// assert_eq!(components.len(), 2);
+    /// assert!(Arc::ptr_eq(&components, &graph.analytics().components()));
+    ///
+    /// graph.connect_nodes(components[0][0], components[1][0]).unwrap();
+    /// assert_eq!(graph.analytics().components().len(), 1);
+    /// ```
+    pub fn analytics(&self) -> CachedAnalytics<'_, T> {
+        CachedAnalytics { graph: self }
+    }
+}
+/// Created by [AdjListGraph::analytics].
+pub struct CachedAnalytics<'a, T> {
+    graph: &'a AdjListGraph<T>,
+}
+impl<T> CachedAnalytics<'_, T> {
+    /// [AdjListGraph::connected_components]
+    pub fn components(&self) -> Arc<Vec<Vec<NodeID>>> {
+        self.cached(
+            |cache| &mut cache.components,
+            || self.graph.connected_components(),
+        )
+    }
+    /// [AdjListGraph::degree_histogram]
+    pub fn degree_histogram(&self) -> Arc<Vec<usize>> {
+        self.cached(
+            |cache| &mut cache.degree_histogram,
+            || self.graph.degree_histogram(),
+        )
+    }
+    /// [AdjListGraph::strength_histogram]
+    pub fn strength_histogram(&self) -> Arc<BTreeMap<u64, usize>> {
+        self.cached(
+            |cache| &mut cache.strength_histogram,
+            || self.graph.strength_histogram(),
+        )
+    }
+    /// [AdjListGraph::stats]. Reuses the cached components.
+    pub fn stats(&self) -> Arc<GraphStats> {
+        let components = self.components().len();
+        self.cached(
+            |cache| &mut cache.stats,
+            || self.graph.stats_with_components(components),
+        )
+    }
+    fn cached<V>(
+        &self,
+        slot: impl FnOnce(&mut Cache) -> &mut Option<(u64, Arc<V>)>,
+        compute: impl FnOnce() -> V,
+    ) -> Arc<V> {
+        let version = self.graph.content_version;
+        // A panic while computing leaves the slot untouched, so the cache is still usable.
+        let mut cache = self
+            .graph
+            .analytics
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let slot = slot(&mut cache);
+        match slot {
+            Some((cached_version, value)) if *cached_version == version => value.clone(),
+            _ => {
+                let value = Arc::new(compute());
+                *slot = Some((version, value.clone()));
+                value
+            }
+        }
+    }
+}
+#[derive(Default, Clone)]
+struct Cache {
+    components: Option<(u64, Arc<Vec<Vec<NodeID>>>)>,
+    degree_histogram: Option<(u64, Arc<Vec<usize>>)>,
+    strength_histogram: Option<(u64, Arc<BTreeMap<u64, usize>>)>,
+    stats: Option<(u64, Arc<GraphStats>)>,
+}
+/// Stored in the graph. Clones share the cached results.
+#[derive(Default)]
+pub(super) struct AnalyticsCache(Mutex<Cache>);
+impl Clone for AnalyticsCache {
+    fn clone(&self) -> Self {
+        let cache = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        Self(Mutex::new(cache.clone()))
+    }
+}
+impl core::fmt::Debug for AnalyticsCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AnalyticsCache").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::adjacency_list::{test_graphs::random_graph, *};
+
+    #[test]
+    pub fn repeated_calls_share_results() {
+        let graph = random_graph(100, 80, 4);
+        let analytics = graph.analytics();
+        assert!(Arc::ptr_eq(
+            &analytics.components(),
+            &analytics.components()
+        ));
+        assert!(Arc::ptr_eq(
+            &analytics.degree_histogram(),
+            &graph.analytics().degree_histogram()
+        ));
+        assert!(Arc::ptr_eq(&analytics.stats(), &analytics.stats()));
+
+        assert_eq!(*analytics.components(), graph.connected_components());
+        assert_eq!(*analytics.degree_histogram(), graph.degree_histogram());
+        assert_eq!(*analytics.stats(), graph.stats());
+        assert_eq!(*analytics.strength_histogram(), graph.strength_histogram());
+
+        let clone = graph.clone();
+        assert!(Arc::ptr_eq(
+            &clone.analytics().components(),
+            &analytics.components()
+        ));
+    }
+    #[test]
+    pub fn mutations_invalidate() {
+        let mut graph = random_graph(60, 40, 9);
+        let components = graph.analytics().components();
+        let stats = graph.analytics().stats();
+        let version = graph.content_version();
+
+        let node = graph.add_node(0);
+        assert_ne!(graph.content_version(), version);
+        let updated = graph.analytics().components();
+        assert!(!Arc::ptr_eq(&components, &updated));
+        assert_eq!(updated.len(), components.len() + 1);
+        assert_eq!(graph.analytics().stats().nodes, stats.nodes + 1);
+
+        let version = graph.content_version();
+        graph.connect_nodes(node, NodeID(0)).unwrap();
+        assert_ne!(graph.content_version(), version);
+        assert_eq!(
+            *graph.analytics().components(),
+            graph.connected_components()
+        );
+
+        let version = graph.content_version();
+        *graph[node].value_mut() = 7;
+        assert_ne!(graph.content_version(), version);
+
+        let version = graph.content_version();
+        graph.remove_node(node);
+        graph.remove_dead_values();
+        assert_ne!(graph.content_version(), version);
+        assert_eq!(*graph.analytics().stats(), graph.stats());
+        assert_eq!(
+            *graph.analytics().degree_histogram(),
+            graph.degree_histogram()
+        );
+        assert_eq!(
+            *graph.analytics().strength_histogram(),
+            graph.strength_histogram()
+        );
+    }
+}
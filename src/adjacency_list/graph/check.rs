@@ -1,9 +1,17 @@
 //! The functions defined in this module are used to check if the graph is in a valid state.
 //!
 //! These checks check for things that shouldn't happen in a graph. However, they are great for testing the graph's integrity.
+use crate::collections::HashMap;
+use crate::prelude::*;
+
 use super::AdjListGraph;
 use crate::adjacency_list::*;
-use crate::utils::IdType;
+use crate::utils::{
+    logging::{debug, debug_span},
+    IdType,
+};
+use crate::GraphError;
+use serde::{Deserialize, Serialize};
 
 macro_rules! valid_values {
     (
@@ -11,6 +19,7 @@ macro_rules! valid_values {
         is_valid_fn: $is_valid_fn:ident,
         $(#[$does_id_exist_docs:meta])*
         does_id_exist: $does_id_exist:ident,
+        alive_fn: $alive_fn:ident,
         $(#[$get_fn_docs:meta])*
         get_fn: $fn_name:ident,
         $(#[$has_fn_docs:meta])*
@@ -29,11 +38,7 @@ macro_rules! valid_values {
         }
         $(#[$does_id_exist_docs])*
         pub fn $does_id_exist(&self, id: $id_ty) -> bool {
-            if self.$empty_slots.contains(&id) {
-                return false;
-            }
-
-            self.$values.get(id.0).is_some()
+            self.$alive_fn(id)
         }
         $(#[$get_fn_docs])*
         pub fn $fn_name(&self) -> Vec<$id_ty> {
@@ -83,8 +88,9 @@ impl<T> AdjListGraph<T> {
         /// Checks if the edge is valid.
         /// Checks if the id exists and if the nodes associated with the edge exist.
         is_valid_fn: is_valid_edge,
-        /// Checks if the edge id exists.
+        /// Checks if the edge id exists. Same as [AdjListGraph::is_edge_alive].
         does_id_exist: does_edge_id_exist,
+        alive_fn: is_edge_alive,
         /// Gets all the invalid edges.
         get_fn: invalid_edges,
         /// Checks if there are any invalid edges.
@@ -97,8 +103,9 @@ impl<T> AdjListGraph<T> {
     valid_values! {
         /// Checks if the node is valid.
         is_valid_fn: is_valid_node,
-        /// Checks if the node id exists.
+        /// Checks if the node id exists. Same as [AdjListGraph::is_node_alive].
         does_id_exist: does_node_id_exist,
+        alive_fn: is_node_alive,
         /// Gets all the invalid nodes.
         get_fn: invalid_nodes,
         /// Checks if there are any invalid nodes.
@@ -109,13 +116,161 @@ impl<T> AdjListGraph<T> {
         check_fn: is_valid_node_inner
     }
 
+    /// Checks if the graph has two edges connecting the same pair of nodes.
+    ///
+    /// `connect_nodes` prevents this unless the [GraphPolicy] allows parallel edges. However, a deserialized graph can still contain them.
+    pub fn has_parallel_edges(&self) -> bool {
+        self.find_parallel_edges().is_some()
+    }
+    /// Finds the first pair of edges connecting the same nodes.
+    fn find_parallel_edges(&self) -> Option<(EdgeID, EdgeID)> {
+        let mut seen = HashMap::with_capacity(self.edges.len());
+        for (index, edge) in self.edges.iter().enumerate() {
+            if self.is_edge_empty(index) {
+                continue;
+            }
+            let key = if edge.node_a <= edge.node_b {
+                (edge.node_a, edge.node_b)
+            } else {
+                (edge.node_b, edge.node_a)
+            };
+            if let Some(existing) = seen.insert(key, EdgeID(index)) {
+                return Some((existing, EdgeID(index)));
+            }
+        }
+        None
+    }
+    /// Runs all the integrity checks on the graph.
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), GraphError> {
+        let _span = debug_span!(
+            "validate",
+            nodes = self.nodes.len(),
+            edges = self.edges.len()
+        )
+        .entered();
+        let result = self.validate_inner();
+        if let Err(error) = &result {
+            debug!(%error, "Graph failed validation");
+        }
+        result
+    }
+    fn validate_inner(&self) -> Result<(), GraphError> {
+        self.validate_references()?;
+        if self.policy.parallel_edges == PolicyAction::Reject {
+            if let Some((edge_a, edge_b)) = self.find_parallel_edges() {
+                return Err(GraphError::ParallelEdges { edge_a, edge_b });
+            }
+        }
+        if self.policy.self_loops == PolicyAction::Reject {
+            let mut edges = self.edge_ids().map(|edge| &self[edge]);
+            if let Some(edge) = edges.find(|edge| edge.node_a == edge.node_b) {
+                return Err(GraphError::SelfLoopNotAllowed { node: edge.node_a });
+            }
+        }
+        Ok(())
+    }
+    /// The checks of [AdjListGraph::validate] that do not depend on the [GraphPolicy]. Every node and edge must only reference live slots that reference it back.
+    pub(crate) fn validate_references(&self) -> Result<(), GraphError> {
+        if let Some(node) = self.invalid_nodes().first() {
+            return Err(GraphError::InvalidNode { node: *node });
+        }
+        if let Some(edge) = self.invalid_edges().first() {
+            return Err(GraphError::InvalidEdge { edge: *edge });
+        }
+        Ok(())
+    }
+    /// Fixes the integrity problems that can be fixed without guessing.
+    ///
+    /// These come from hand edited files or from mutating the graph through [IndexMut](core::ops::IndexMut).
+    /// - Live and out of range slots in the free lists are taken out, so they are not handed out again.
+    /// - Removed slots that are missing from the free lists are added back.
+    /// - Edges with a dead or out of range node are removed.
+    /// - Edge IDs are removed from the nodes that list an edge that does not exist or does not connect to them.
+    ///
+    /// After this [AdjListGraph::validate] only fails if the graph breaks its [GraphPolicy].
+    pub fn repair(&mut self) -> RepairReport {
+        let _span =
+            debug_span!("repair", nodes = self.nodes.len(), edges = self.edges.len()).entered();
+        let mut report = RepairReport::default();
+        let Self {
+            nodes,
+            edges,
+            empty_node_slots,
+            empty_edge_slots,
+            ..
+        } = self;
+        report.live_slots_in_free_lists += empty_node_slots.retain(|node| {
+            nodes
+                .get(node.0)
+                .is_some_and(|node| node.optional_value().is_none())
+        });
+        let cleared = NodeID(usize::MAX);
+        report.live_slots_in_free_lists += empty_edge_slots.retain(|edge| {
+            edges
+                .get(edge.0)
+                .is_some_and(|edge| edge.node_a == cleared && edge.node_b == cleared)
+        });
+        for index in 0..self.nodes.len() {
+            if !self.is_node_empty(index) && self.nodes[index].optional_value().is_none() {
+                self.nodes[index].edges.clear();
+                self.empty_node_slots.push_back(NodeID(index));
+                report.unlisted_empty_slots += 1;
+            }
+        }
+        for index in 0..self.edges.len() {
+            if self.is_edge_empty(index) {
+                continue;
+            }
+            let edge = &self.edges[index];
+            if edge.node_a == NodeID(usize::MAX) && edge.node_b == NodeID(usize::MAX) {
+                // Cleared by remove_edge.
+                self.empty_edge_slots.push_back(EdgeID(index));
+                report.unlisted_empty_slots += 1;
+                continue;
+            }
+            if self.is_valid_edge_inner(edge) {
+                continue;
+            }
+            let (node_a, node_b) = edge.nodes();
+            for node in [node_a, node_b] {
+                if self.does_node_id_exist(node) {
+                    self.nodes[node.0].remove_edge(EdgeID(index));
+                }
+            }
+            self.edges[index].clear();
+            self.empty_edge_slots.push_back(EdgeID(index));
+            report.edges_to_dead_nodes += 1;
+        }
+        for index in 0..self.nodes.len() {
+            if self.is_node_empty(index) {
+                continue;
+            }
+            let Self { nodes, edges, .. } = self;
+            let empty_edge_slots = &self.empty_edge_slots;
+            let node = NodeID(index);
+            let before = nodes[index].edges.len();
+            nodes[index].edges.retain(|edge| {
+                !empty_edge_slots.contains(edge)
+                    && edges
+                        .get(edge.0)
+                        .is_some_and(|edge| edge.node_a == node || edge.node_b == node)
+            });
+            report.dangling_edge_references += before - nodes[index].edges.len();
+        }
+        if !report.is_clean() {
+            self.touch();
+            debug!(?report, "Repaired graph");
+        }
+        report
+    }
     /// Checks if all the nodes edges exist
     #[inline]
     fn is_valid_node_inner(&self, node: &Node<T>) -> bool {
-        return node
-            .edges
+        node.edges
             .iter()
-            .any(|edge_id| self.does_edge_id_exist(*edge_id));
+            .all(|edge_id| self.does_edge_id_exist(*edge_id))
     }
     /// Checks if the nodes associated with the edge exist
     #[inline]
@@ -124,6 +279,25 @@ impl<T> AdjListGraph<T> {
     }
 }
 
+/// What [AdjListGraph::repair] fixed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairReport {
+    /// Edge IDs removed from nodes because the edge does not exist or does not connect to the node.
+    pub dangling_edge_references: usize,
+    /// Edges removed because one of their nodes is dead or out of range.
+    pub edges_to_dead_nodes: usize,
+    /// Removed node and edge slots that were missing from the free lists.
+    pub unlisted_empty_slots: usize,
+    /// Live or out of range node and edge slots that were in the free lists.
+    pub live_slots_in_free_lists: usize,
+}
+impl RepairReport {
+    /// True if nothing had to be fixed.
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::adjacency_list::*;
@@ -156,4 +330,149 @@ mod tests {
         println!("{:?}", graph);
         assert!(graph.has_invalid_edges());
     }
+    #[test]
+    pub fn test_deserialized_parallel_edges() {
+        let json = r#"{
+            "nodes": [
+                { "value": "A", "edges": [0, 1] },
+                { "value": "B", "edges": [0, 1] }
+            ],
+            "edges": [
+                { "weight": 1, "node_a": 0, "node_b": 1 },
+                { "weight": 2, "node_a": 1, "node_b": 0 }
+            ]
+        }"#;
+        let graph: AdjListGraph<String> = serde_json::from_str(json).unwrap();
+        assert!(graph.has_parallel_edges());
+        assert_eq!(
+            graph.validate(),
+            Err(crate::GraphError::ParallelEdges {
+                edge_a: EdgeID(0),
+                edge_b: EdgeID(1)
+            })
+        );
+    }
+    #[test]
+    pub fn test_validate_valid_graph() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        graph.connect_nodes(c, a).unwrap();
+        // A node without any edges is still valid.
+        graph.add_node("D");
+        assert!(!graph.has_parallel_edges());
+        assert_eq!(graph.validate(), Ok(()));
+    }
+    #[test]
+    pub fn repair_dangling_edge_references() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        let edge = graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        // An edge that does not exist and an edge that does not touch C.
+        graph[a].edges.insert(EdgeID(10));
+        graph[c].edges.insert(edge);
+        assert!(graph.validate().is_err());
+
+        let report = graph.repair();
+        assert_eq!(
+            report,
+            RepairReport {
+                dangling_edge_references: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(graph.validate(), Ok(()));
+        assert_eq!(graph.number_of_edges(), 2);
+        assert!(graph.repair().is_clean());
+    }
+    #[test]
+    pub fn repair_edges_to_dead_nodes() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        let to_dead = graph.connect_nodes(a, b).unwrap();
+        let out_of_range = graph.connect_nodes(b, c).unwrap();
+        let kept = graph.connect_nodes(a, c).unwrap();
+        graph.remove_node(b);
+        // Point the removed edges back at the nodes, as a buggy mutation could.
+        graph.edges[to_dead.0] = Edge::new(0, a, b);
+        graph[a].edges.insert(to_dead);
+        graph.edges[out_of_range.0] = Edge::new(0, c, NodeID(50));
+        graph[c].edges.insert(out_of_range);
+        let edge_slots = graph.empty_edge_slots.len();
+        graph.empty_edge_slots = Default::default();
+        assert!(graph.validate().is_err());
+
+        let report = graph.repair();
+        assert_eq!(
+            report,
+            RepairReport {
+                dangling_edge_references: 0,
+                edges_to_dead_nodes: 2,
+                unlisted_empty_slots: 0,
+                live_slots_in_free_lists: 0,
+            }
+        );
+        assert_eq!(graph.empty_edge_slots.len(), edge_slots);
+        assert_eq!(graph.validate(), Ok(()));
+        assert_eq!(graph.edge_ids().collect::<Vec<_>>(), vec![kept]);
+        assert_eq!(graph[a].edges.len(), 1);
+        assert_eq!(graph[c].edges.len(), 1);
+    }
+    #[test]
+    pub fn repair_unlisted_empty_slots() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        graph.remove_node(c);
+        // Forget that C and its edge were removed.
+        graph.empty_node_slots = Default::default();
+        graph.empty_edge_slots = Default::default();
+        assert_eq!(graph.number_of_nodes(), 3);
+
+        let report = graph.repair();
+        assert_eq!(
+            report,
+            RepairReport {
+                unlisted_empty_slots: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(graph.validate(), Ok(()));
+        assert_eq!(graph.number_of_nodes(), 2);
+        assert_eq!(graph.number_of_edges(), 1);
+        // The slot is reused.
+        assert_eq!(graph.add_node("D"), c);
+    }
+    #[test]
+    pub fn repair_live_slot_in_free_list() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        let ab = graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        // List live slots as free, as a buggy mutation could.
+        graph.empty_node_slots.push_back(b);
+        graph.empty_edge_slots.push_back(ab);
+        graph.empty_node_slots.push_back(NodeID(50));
+        assert!(graph.validate().is_err());
+
+        let report = graph.repair();
+        assert_eq!(
+            report,
+            RepairReport {
+                live_slots_in_free_lists: 3,
+                ..Default::default()
+            }
+        );
+        assert_eq!(graph.validate(), Ok(()));
+        assert_eq!(graph.number_of_nodes(), 3);
+        assert_eq!(graph.number_of_edges(), 2);
+        // The next node gets a new slot instead of overwriting B.
+        let d = graph.add_node("D");
+        assert_eq!(d, NodeID(3));
+        assert_eq!(graph[b].value(), &"B");
+        assert!(graph.repair().is_clean());
+    }
 }
@@ -0,0 +1,173 @@
+//! Checking node colorings and counting them with the chromatic polynomial.
+use crate::prelude::*;
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// True if `colors` gives every live node a color and no edge connects two nodes of the same color.
+    ///
+    /// `colors` is indexed by node slot and must be as long as the slots, including the dead ones, which must be `None`.
+    /// A self loop can never be colored properly.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+    /// graph.connect_nodes(a, b).unwrap();
+    /// graph.connect_nodes(b, c).unwrap();
+    ///
+    /// assert!(graph.is_proper_coloring(&[Some(0), Some(1), Some(0)]));
+    /// assert!(!graph.is_proper_coloring(&[Some(0), Some(0), Some(1)]));
+    /// ```
+    pub fn is_proper_coloring(&self, colors: &[Option<usize>]) -> bool {
+        if colors.len() != self.nodes.len() {
+            return false;
+        }
+        let slots_match = colors
+            .iter()
+            .enumerate()
+            .all(|(index, color)| color.is_some() != self.is_node_empty(index));
+        slots_match
+            && self.edges().all(|edge| {
+                let (a, b) = edge.endpoints();
+                a != b && colors[a.0] != colors[b.0]
+            })
+    }
+    /// The number of ways to color the live nodes with `k` colors so no edge connects two nodes of the same color.
+    ///
+    /// Computed by deletion–contraction, which takes O(2^E) time. Only use it on tiny graphs, up to about 12 edges.
+    /// Parallel edges count once and a self loop makes every coloring improper.
+    ///
+    /// Returns None if the count overflows. No count is larger than `k^V`, the count without any edges,
+    /// so this only happens if `k^V` does not fit in an [i128], such as for 10 colors and 39 nodes.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+    /// graph.connect_nodes(a, b).unwrap();
+    /// // k (k - 1)
+    /// assert_eq!(graph.chromatic_polynomial_at(3), Some(6));
+    /// ```
+    pub fn chromatic_polynomial_at(&self, k: u64) -> Option<u128> {
+        let mut edges = Vec::with_capacity(self.number_of_edges());
+        for edge in self.edges() {
+            let (a, b) = edge.endpoints();
+            if a == b {
+                return Some(0);
+            }
+            edges.push((a.0.min(b.0), a.0.max(b.0)));
+        }
+        edges.sort_unstable();
+        edges.dedup();
+        let polynomial = deletion_contraction(self.number_of_nodes() as u32, &edges, k as i128)?;
+        Some(polynomial as u128)
+    }
+}
+/// P(G) = P(G - e) - P(G / e) on a simple graph with `nodes` nodes. The labels in `edges` do not have to be contiguous.
+///
+/// Returns None on overflow.
+fn deletion_contraction(nodes: u32, edges: &[(usize, usize)], k: i128) -> Option<i128> {
+    let Some(((keep, merged), rest)) = edges.split_last() else {
+        return k.checked_pow(nodes);
+    };
+    let deleted = deletion_contraction(nodes, rest, k)?;
+    // Merging the endpoints can turn two edges into parallel edges, which count once.
+    let mut contracted: Vec<(usize, usize)> = rest
+        .iter()
+        .map(|(a, b)| {
+            let a = if a == merged { *keep } else { *a };
+            let b = if b == merged { *keep } else { *b };
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    contracted.sort_unstable();
+    contracted.dedup();
+    deleted.checked_sub(deletion_contraction(nodes - 1, &contracted, k)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+
+    fn triangle() -> AdjListGraph<char> {
+        [('a', 'b'), ('b', 'c'), ('c', 'a')].into()
+    }
+    #[test]
+    pub fn triangle_polynomial() {
+        let mut graph = triangle();
+        // k (k - 1) (k - 2)
+        assert_eq!(graph.chromatic_polynomial_at(3), Some(6));
+        assert_eq!(graph.chromatic_polynomial_at(2), Some(0));
+        assert_eq!(graph.chromatic_polynomial_at(5), Some(60));
+
+        // An isolated node multiplies by k.
+        let d = graph.add_node('d');
+        assert_eq!(graph.chromatic_polynomial_at(3), Some(18));
+        graph.remove_node(d);
+        assert_eq!(graph.chromatic_polynomial_at(3), Some(6));
+
+        let a = graph.find_node_with_that_equals(&'a').unwrap();
+        graph.connect_nodes(a, a).unwrap();
+        assert_eq!(graph.chromatic_polynomial_at(3), Some(0));
+        assert_eq!(
+            AdjListGraph::<u8>::default().chromatic_polynomial_at(3),
+            Some(1)
+        );
+    }
+    #[test]
+    pub fn polynomial_matches_brute_force() {
+        // Counts every assignment of `k` colors that the validator accepts.
+        let graph = example_from_video();
+        for k in 0..=3usize {
+            let nodes = graph.nodes.len() as u32;
+            let proper = (0..k.pow(nodes))
+                .filter(|assignment| {
+                    let colors: Vec<Option<usize>> = (0..nodes)
+                        .map(|node| Some(assignment / k.pow(node) % k))
+                        .collect();
+                    graph.is_proper_coloring(&colors)
+                })
+                .count();
+            assert_eq!(
+                graph.chromatic_polynomial_at(k as u64),
+                Some(proper as u128),
+                "k = {k}"
+            );
+        }
+    }
+    #[test]
+    pub fn polynomial_overflow() {
+        let mut graph = AdjListGraph::default();
+        let ids = graph.add_nodes_from_iterator(0..38);
+        assert_eq!(graph.chromatic_polynomial_at(10), Some(10u128.pow(38)));
+        // 10^39 does not fit in an i128.
+        graph.add_node(38);
+        assert_eq!(graph.chromatic_polynomial_at(10), None);
+        graph.connect_nodes(ids[0], ids[1]).unwrap();
+        assert_eq!(graph.chromatic_polynomial_at(10), None);
+        graph.add_node(39);
+        assert_eq!(graph.chromatic_polynomial_at(10), None);
+        assert_eq!(graph.chromatic_polynomial_at(1), Some(0));
+    }
+    #[test]
+    pub fn coloring_validation() {
+        let mut graph = triangle();
+        assert!(graph.is_proper_coloring(&[Some(0), Some(1), Some(2)]));
+        // `a` and `c` share a color across their edge.
+        assert!(!graph.is_proper_coloring(&[Some(0), Some(1), Some(0)]));
+        assert!(!graph.is_proper_coloring(&[Some(0), Some(1), None]));
+        assert!(!graph.is_proper_coloring(&[Some(0), Some(1)]));
+
+        let d = graph.add_node('d');
+        graph.remove_node(d);
+        assert!(graph.is_proper_coloring(&[Some(0), Some(1), Some(2), None]));
+        assert!(!graph.is_proper_coloring(&[Some(0), Some(1), Some(2), Some(0)]));
+
+        let a = graph.find_node_with_that_equals(&'a').unwrap();
+        graph.connect_nodes(a, a).unwrap();
+        assert!(!graph.is_proper_coloring(&[Some(0), Some(1), Some(2), None]));
+    }
+}
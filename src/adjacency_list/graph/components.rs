@@ -0,0 +1,225 @@
+use crate::collections::HashMap;
+use crate::prelude::*;
+
+use crate::adjacency_list::*;
+use crate::algo::GraphRead;
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// Iterates over the connected components as read only views.
+    ///
+    /// Components are found one at a time, sharing a single visited bitmap, in the same order as [AdjListGraph::connected_components].
+    /// Nothing is copied until [ComponentView::to_owned_graph] is called.
+    ///
+    /// ```
+    /// use tux_graph::graph;
+    /// let graph = graph! {
+    ///     a [value='A'];
+    ///     b [value='B'];
+    ///     c [value='C'];
+    ///     d [value='D'];
+    ///     a -- b [weight=1];
+    ///     c -- d [weight=2];
+    /// };
+    /// let msts: Vec<_> = graph
+    ///     .components()
+    ///     .map(|component| component.to_owned_graph().kruskal_find_mst().unwrap())
+    ///     .collect();
+    /// assert_eq!(msts.len(), 2);
+    /// ```
+    pub fn components(&self) -> Components<'_, T> {
+        Components {
+            graph: self,
+            visited: vec![false; self.nodes.len()],
+            next: 0,
+        }
+    }
+}
+/// Iterator over the connected components of an [AdjListGraph]. Created by [AdjListGraph::components].
+pub struct Components<'a, T> {
+    graph: &'a AdjListGraph<T>,
+    visited: Vec<bool>,
+    /// The lowest node index that may not have been visited yet.
+    next: usize,
+}
+impl<'a, T> Iterator for Components<'a, T> {
+    type Item = ComponentView<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.graph;
+        let start = (self.next..graph.nodes.len())
+            .find(|index| !self.visited[*index] && !graph.is_node_empty(*index))?;
+        self.next = start + 1;
+        self.visited[start] = true;
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut stack = vec![NodeID(start)];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            for (edge, next, _) in graph.adjacent(node) {
+                edges.push(edge);
+                if !self.visited[next.0] {
+                    self.visited[next.0] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        nodes.sort_unstable();
+        // Every edge is seen from both of its nodes.
+        edges.sort_unstable();
+        edges.dedup();
+        Some(ComponentView {
+            graph,
+            nodes,
+            edges,
+        })
+    }
+}
+/// A read only view of one connected component of an [AdjListGraph].
+///
+/// The node and edge IDs are the IDs in the original graph.
+pub struct ComponentView<'a, T> {
+    graph: &'a AdjListGraph<T>,
+    /// Sorted
+    nodes: Vec<NodeID>,
+    /// Sorted
+    edges: Vec<EdgeID>,
+}
+impl<T> ComponentView<'_, T> {
+    /// The nodes of the component in ascending order.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeID> + '_ {
+        self.nodes.iter().copied()
+    }
+    /// The edges of the component in ascending order.
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeID> + '_ {
+        self.edges.iter().copied()
+    }
+    /// The number of nodes in the component.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    /// Always false. A component has at least one node.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+    /// Copies the component into a new graph with the same policy.
+    ///
+    /// Nodes and edges keep their relative order, so the new IDs are the index of the old ID in [ComponentView::node_ids] and [ComponentView::edge_ids].
+    pub fn to_owned_graph(&self) -> AdjListGraph<T>
+    where
+        T: Clone,
+    {
+        let mut graph = AdjListGraph::with_policy(self.graph.policy);
+        graph.reserve(self.nodes.len(), self.edges.len());
+        let mut updated_node_ids = HashMap::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            updated_node_ids.insert(*node, self.graph.copy_node_into(&mut graph, *node));
+        }
+        for edge in &self.edges {
+            let edge = &self.graph[edge];
+            let _ = graph.connect_nodes_with_weight(
+                updated_node_ids[&edge.node_a],
+                updated_node_ids[&edge.node_b],
+                edge.weight(),
+            );
+        }
+        graph
+    }
+}
+impl<T> GraphRead for ComponentView<'_, T> {
+    type NodeId = NodeID;
+    type EdgeId = EdgeID;
+    fn node_ids(&self) -> impl Iterator<Item = NodeID> + '_ {
+        ComponentView::node_ids(self)
+    }
+    fn neighbors(&self, node: NodeID) -> impl Iterator<Item = (EdgeID, NodeID)> + '_ {
+        // The neighbors of a node in the component are always in the component.
+        let contains = self.contains_node(node);
+        contains
+            .then(|| {
+                self.graph
+                    .adjacent(node)
+                    .map(|(edge, next, _)| (edge, next))
+            })
+            .into_iter()
+            .flatten()
+    }
+    fn edge_weight(&self, edge: EdgeID) -> Option<u32> {
+        self.edges
+            .binary_search(&edge)
+            .is_ok()
+            .then(|| self.graph[edge].weight())
+    }
+    fn number_of_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+    fn number_of_edges(&self) -> usize {
+        self.edges.len()
+    }
+    fn contains_node(&self, node: NodeID) -> bool {
+        self.nodes.binary_search(&node).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::*;
+    use crate::algo::{self, GraphRead};
+
+    #[test]
+    pub fn three_components() {
+        let mut graph = graph_no_import! {
+            a [value='A'];
+            b [value='B'];
+            c [value='C'];
+            d [value='D'];
+            e [value='E'];
+
+            a -- b [weight=1];
+            b -- c [weight=2];
+            c -- a [weight=3];
+            d -- e [weight=4];
+        };
+        let f = graph.add_node('F');
+        let g = graph.add_node('G');
+        graph.connect_nodes_with_weight(g, g, 5).unwrap();
+        graph.remove_node(f);
+
+        let components: Vec<_> = graph.components().collect();
+        assert_eq!(
+            components
+                .iter()
+                .map(|component| (component.len(), GraphRead::number_of_edges(component)))
+                .collect::<Vec<_>>(),
+            vec![(3, 3), (2, 1), (1, 1)]
+        );
+        assert_eq!(
+            components
+                .iter()
+                .map(|component| component.node_ids().collect())
+                .collect::<Vec<Vec<_>>>(),
+            graph.connected_components()
+        );
+
+        let first = &components[0];
+        assert!(first.contains_node(NodeID(2)));
+        assert!(!first.contains_node(NodeID(3)));
+        assert_eq!(first.edge_weight(EdgeID(3)), None);
+        assert_eq!(algo::bfs(first, NodeID(0)).unwrap().len(), 3);
+        assert_eq!(GraphRead::neighbors(first, NodeID(3)).count(), 0);
+
+        for component in &components {
+            let owned = component.to_owned_graph();
+            owned.validate().unwrap();
+            assert_eq!(owned.number_of_nodes(), component.len());
+            assert_eq!(
+                owned.number_of_edges(),
+                GraphRead::number_of_edges(component)
+            );
+        }
+        let triangle = components[0].to_owned_graph();
+        assert_eq!(triangle.weights().map(|(_, weight)| weight).sum::<u32>(), 6);
+    }
+}
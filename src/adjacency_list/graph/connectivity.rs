@@ -0,0 +1,623 @@
+use crate::adjacency_list::*;
+use crate::prelude::*;
+use crate::utils::logging::{debug, trace};
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// Returns true if every live node can reach every other live node.
+    ///
+    /// An empty graph is considered connected.
+    pub fn is_connected(&self) -> bool {
+        let Some(start) = self.first_live_node() else {
+            return true;
+        };
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![start];
+        visited[start.0] = true;
+        let mut found = 0;
+        while let Some(node) = stack.pop() {
+            found += 1;
+            for (_, next, _) in self.adjacent(node) {
+                if !visited[next.0] {
+                    visited[next.0] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        found == self.number_of_nodes()
+    }
+    /// Groups the live nodes into connected components.
+    ///
+    /// Every component is sorted by node ID and the components are sorted by their lowest node ID.
+    pub fn connected_components(&self) -> Vec<Vec<NodeID>> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut components = Vec::new();
+        for start in self.node_ids() {
+            if visited[start.0] {
+                continue;
+            }
+            visited[start.0] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for (_, next, _) in self.adjacent(node) {
+                    if !visited[next.0] {
+                        visited[next.0] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+    /// The minimum number of edges that have to be removed to disconnect the graph.
+    ///
+    /// Computed as the minimum of [AdjListGraph::edge_disjoint_paths] between a fixed node and every other node. O(V · maxflow)
+    ///
+    /// Returns 0 for disconnected graphs and graphs with less than two nodes.
+    pub fn edge_connectivity(&self) -> u32 {
+        if self.number_of_nodes() < 2 || !self.is_connected() {
+            return 0;
+        }
+        let live_nodes = self.live_node_ids();
+        let (fixed, others) = live_nodes.split_first().unwrap();
+        others
+            .iter()
+            .map(|other| self.edge_disjoint_paths(*fixed, *other))
+            .min()
+            .unwrap_or(0)
+    }
+    /// The minimum number of nodes that have to be removed to disconnect the graph.
+    ///
+    /// Computed as the minimum of [AdjListGraph::node_disjoint_paths] over every pair of non adjacent nodes. O(V² · maxflow)
+    ///
+    /// A complete graph of n nodes has a vertex connectivity of n - 1.
+    ///
+    /// Returns 0 for disconnected graphs and graphs with less than two nodes.
+    pub fn vertex_connectivity(&self) -> u32 {
+        if self.number_of_nodes() < 2 || !self.is_connected() {
+            return 0;
+        }
+        let live_nodes = self.live_node_ids();
+        let mut connectivity = live_nodes.len() as u32 - 1;
+        for (index, a) in live_nodes.iter().enumerate() {
+            for b in &live_nodes[index + 1..] {
+                if self.is_node_connected_to_node(*a, *b) {
+                    continue;
+                }
+                connectivity = connectivity.min(self.node_disjoint_paths(*a, *b));
+            }
+        }
+        connectivity
+    }
+    /// Finds every node whose removal would increase the number of connected components.
+    ///
+    /// The result is sorted by node ID.
+    pub fn articulation_points(&self) -> Vec<NodeID> {
+        let mut state = ArticulationState {
+            discovered: vec![None; self.nodes.len()],
+            low: vec![0; self.nodes.len()],
+            is_articulation: vec![false; self.nodes.len()],
+            time: 0,
+        };
+        for index in 0..self.nodes.len() {
+            if self.is_node_empty(index) || state.discovered[index].is_some() {
+                continue;
+            }
+            self.articulation_points_from(NodeID(index), &mut state);
+        }
+        state
+            .is_articulation
+            .iter()
+            .enumerate()
+            .filter(|(_, is_articulation)| **is_articulation)
+            .map(|(index, _)| NodeID(index))
+            .collect()
+    }
+    /// Tarjan's algorithm over the component of `root`, with an explicit stack so long paths do not overflow the call stack.
+    fn articulation_points_from(&self, root: NodeID, state: &mut ArticulationState) {
+        state.discover(root);
+        let mut root_children = 0;
+        // Each frame is a node on the current path, the edge it was reached by and the edges it has not looked at yet.
+        let mut stack = vec![(root, None, self.adjacent(root))];
+        while let Some((node, parent_edge, unexplored)) = stack.last_mut() {
+            let (node, parent_edge) = (*node, *parent_edge);
+            let Some((edge, next, _)) = unexplored.next() else {
+                stack.pop();
+                if let Some((parent, grandparent_edge, _)) = stack.last() {
+                    state.low[parent.0] = state.low[parent.0].min(state.low[node.0]);
+                    if grandparent_edge.is_some()
+                        && Some(state.low[node.0]) >= state.discovered[parent.0]
+                    {
+                        state.is_articulation[parent.0] = true;
+                    }
+                }
+                continue;
+            };
+            if Some(edge) == parent_edge || next == node {
+                continue;
+            }
+            if let Some(next_discovered) = state.discovered[next.0] {
+                // Back edge.
+                state.low[node.0] = state.low[node.0].min(next_discovered);
+                continue;
+            }
+            if parent_edge.is_none() {
+                root_children += 1;
+            }
+            state.discover(next);
+            stack.push((next, Some(edge), self.adjacent(next)));
+        }
+        if root_children > 1 {
+            state.is_articulation[root.0] = true;
+        }
+    }
+    /// Finds every edge whose removal would increase the number of connected components.
+    ///
+    /// These are the edges left over by the chain decomposition of every component. The result is sorted by edge ID.
+    pub fn bridges(&self) -> Vec<EdgeID> {
+        let mut covered = vec![false; self.edges.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        for root in self.node_ids() {
+            if visited[root.0] {
+                continue;
+            }
+            let tree = self.dfs_tree(root);
+            for node in &tree.preorder {
+                visited[node.0] = true;
+            }
+            for chain in self.chains(&tree) {
+                for edge in chain {
+                    covered[edge.0] = true;
+                }
+            }
+        }
+        self.edge_ids().filter(|edge| !covered[edge.0]).collect()
+    }
+    /// Splits the edges into ears, certifying that the graph is 2-edge-connected.
+    ///
+    /// The first ear is a cycle through `root`. Every later ear is a path or cycle that starts and ends on nodes of the earlier ears,
+    /// with all of its other nodes new. Each ear lists its edge IDs in order, and together the ears contain every edge exactly once.
+    /// A later ear is only ever a cycle if the graph is not 2-vertex-connected.
+    ///
+    /// Built with a chain decomposition over a depth first spanning tree. O(V + E)
+    ///
+    /// Returns `None` if `root` does not exist or the graph is not 2-edge-connected. A graph with a single node and no edges has no ears.
+    pub fn ear_decomposition(&self, root: NodeID) -> Option<Vec<Vec<EdgeID>>> {
+        if !self.does_node_id_exist(root) {
+            return None;
+        }
+        let tree = self.dfs_tree(root);
+        if tree.preorder.len() != self.number_of_nodes() {
+            debug!("Graph is disconnected. No ear decomposition");
+            return None;
+        }
+        let chains = self.chains(&tree);
+        let covered: usize = chains.iter().map(Vec::len).sum();
+        if covered != self.number_of_edges() {
+            debug!(
+                uncovered = self.number_of_edges() - covered,
+                "Graph has bridges. No ear decomposition"
+            );
+            return None;
+        }
+        Some(chains)
+    }
+    /// A fundamental cycle basis. There is one cycle for every edge outside of a depth first spanning forest, so there are `E - V + C` cycles.
+    ///
+    /// Each cycle starts with its edge outside of the forest, going from the first node it was connected with, and follows the forest back around.
+    /// A self loop is a cycle of its own and parallel edges make cycles of two edges.
+    ///
+    /// Cycles are sorted by their first edge.
+    pub fn cycle_basis(&self) -> Vec<Vec<EdgeID>> {
+        let mut parent_edge = vec![None; self.nodes.len()];
+        let mut depth = vec![0usize; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        for root in self.node_ids() {
+            if visited[root.0] {
+                continue;
+            }
+            let tree = self.dfs_tree(root);
+            // A parent is always before its children in preorder.
+            for node in &tree.preorder {
+                visited[node.0] = true;
+                parent_edge[node.0] = tree.parent_edge[node.0];
+                if let Some(edge) = tree.parent_edge[node.0] {
+                    depth[node.0] = depth[self[edge].other_node(*node).0] + 1;
+                }
+            }
+        }
+        let mut cycles = Vec::new();
+        for edge in self.edge_ids() {
+            let (node_a, node_b) = self[edge].nodes();
+            if parent_edge[node_a.0] == Some(edge) || parent_edge[node_b.0] == Some(edge) {
+                continue;
+            }
+            // Climb from both ends until they meet. The path from `node_b` is walked up and the one from `node_a` is walked down.
+            let (mut a, mut b) = (node_a, node_b);
+            let mut up_from_b = vec![edge];
+            let mut up_from_a = Vec::new();
+            while a != b {
+                if depth[a.0] >= depth[b.0] {
+                    let parent = parent_edge[a.0].unwrap();
+                    up_from_a.push(parent);
+                    a = self[parent].other_node(a);
+                } else {
+                    let parent = parent_edge[b.0].unwrap();
+                    up_from_b.push(parent);
+                    b = self[parent].other_node(b);
+                }
+            }
+            up_from_b.extend(up_from_a.into_iter().rev());
+            cycles.push(up_from_b);
+        }
+        cycles
+    }
+    /// A depth first spanning tree of the component containing `root`.
+    fn dfs_tree(&self, root: NodeID) -> DfsTree {
+        let mut tree = DfsTree {
+            preorder: vec![root],
+            discovered: vec![None; self.nodes.len()],
+            parent_edge: vec![None; self.nodes.len()],
+        };
+        tree.discovered[root.0] = Some(0);
+        // The node and the edges that have not been looked at yet.
+        let mut stack = vec![(root, self.adjacent(root))];
+        while let Some((_, edges)) = stack.last_mut() {
+            let Some((edge, next, _)) = edges.next() else {
+                stack.pop();
+                continue;
+            };
+            if tree.discovered[next.0].is_none() {
+                tree.discovered[next.0] = Some(tree.preorder.len());
+                tree.parent_edge[next.0] = Some(edge);
+                tree.preorder.push(next);
+                stack.push((next, self.adjacent(next)));
+            }
+        }
+        tree
+    }
+    /// Schmidt's chain decomposition.
+    ///
+    /// Every edge outside of the tree starts a chain at its upper node, goes down the edge and climbs the tree until it reaches a node that is already in a chain.
+    /// Tree edges that end up in no chain are bridges.
+    fn chains(&self, tree: &DfsTree) -> Vec<Vec<EdgeID>> {
+        let mut in_chain = vec![false; self.nodes.len()];
+        let mut used = vec![false; self.edges.len()];
+        let mut chains = Vec::new();
+        for node in &tree.preorder {
+            let discovered = tree.discovered[node.0];
+            for (edge, next, _) in self.adjacent(*node) {
+                if used[edge.0]
+                    || tree.discovered[next.0] < discovered
+                    || tree.parent_edge[next.0] == Some(edge)
+                {
+                    continue;
+                }
+                used[edge.0] = true;
+                in_chain[node.0] = true;
+                let mut chain = vec![edge];
+                let mut current = next;
+                while !in_chain[current.0] {
+                    in_chain[current.0] = true;
+                    // Only the root has no parent and it is the first node to be put in a chain.
+                    let parent_edge = tree.parent_edge[current.0].unwrap();
+                    chain.push(parent_edge);
+                    current = self[parent_edge].other_node(current);
+                }
+                trace!(?node, length = chain.len(), "Found chain");
+                chains.push(chain);
+            }
+        }
+        chains
+    }
+}
+struct DfsTree {
+    /// The nodes in the order they were discovered.
+    preorder: Vec<NodeID>,
+    /// The index of the node in `preorder`.
+    discovered: Vec<Option<usize>>,
+    parent_edge: Vec<Option<EdgeID>>,
+}
+struct ArticulationState {
+    discovered: Vec<Option<usize>>,
+    low: Vec<usize>,
+    is_articulation: Vec<bool>,
+    time: usize,
+}
+impl ArticulationState {
+    fn discover(&mut self, node: NodeID) {
+        self.discovered[node.0] = Some(self.time);
+        self.low[node.0] = self.time;
+        self.time += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::{
+        test_graphs::{example_from_video, random_graph},
+        *,
+    };
+
+    #[test]
+    pub fn components_skip_dead_nodes() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d, e] = graph.add_nodes_from_sized_array(['A', 'B', 'C', 'D', 'E']);
+        graph.connect_nodes(a, d).unwrap();
+        graph.connect_nodes(c, e).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![a, d], vec![b, c, e]]
+        );
+        graph.remove_node(c);
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![a, d], vec![b], vec![e]]
+        );
+    }
+    #[test]
+    pub fn components_cover_every_live_node() {
+        let graph = random_graph(500, 300, 7);
+        let mut nodes: Vec<NodeID> = graph.connected_components().concat();
+        nodes.sort_unstable();
+        assert_eq!(nodes, graph.live_node_ids());
+    }
+    #[test]
+    pub fn tree_and_cycle_connectivity() {
+        let mut graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+
+            a -- b;
+            b -- c;
+            b -- d;
+        };
+        assert_eq!(graph.edge_connectivity(), 1);
+        assert_eq!(graph.vertex_connectivity(), 1);
+        assert_eq!(graph.articulation_points(), vec![NodeID(1)]);
+
+        // Turn it into the cycle A B D C
+        graph.remove_edge(EdgeID(1));
+        graph.connect_nodes(NodeID(3), NodeID(2)).unwrap();
+        graph.connect_nodes(NodeID(2), NodeID(0)).unwrap();
+        assert_eq!(graph.edge_connectivity(), 2);
+        assert_eq!(graph.vertex_connectivity(), 2);
+        assert!(graph.articulation_points().is_empty());
+    }
+    #[test]
+    pub fn complete_graph_connectivity() {
+        let graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+
+            a -- b;
+            a -- c;
+            a -- d;
+            b -- c;
+            b -- d;
+            c -- d;
+        };
+        assert_eq!(graph.vertex_connectivity(), 3);
+        assert_eq!(graph.edge_connectivity(), 3);
+    }
+    #[test]
+    pub fn articulation_point_connectivity() {
+        // Two triangles sharing the node C.
+        let mut graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+            e [value = 'E'];
+
+            a -- b;
+            b -- c;
+            c -- a;
+            c -- d;
+            d -- e;
+            e -- c;
+        };
+        assert_eq!(graph.vertex_connectivity(), 1);
+        assert_eq!(graph.edge_connectivity(), 2);
+        assert_eq!(graph.articulation_points(), vec![NodeID(2)]);
+
+        graph.remove_node(NodeID(2));
+        assert!(!graph.is_connected());
+        assert_eq!(graph.edge_connectivity(), 0);
+        assert_eq!(graph.vertex_connectivity(), 0);
+    }
+    #[test]
+    pub fn articulation_points_of_long_path() {
+        let mut graph = AdjListGraph::default();
+        let nodes = graph.add_nodes_from_iterator(0..100_000u32);
+        for pair in nodes.windows(2) {
+            graph.connect_nodes(pair[0], pair[1]).unwrap();
+        }
+        assert_eq!(graph.articulation_points(), nodes[1..nodes.len() - 1]);
+    }
+    #[test]
+    pub fn articulation_points_match_removal() {
+        for seed in 0..5 {
+            let graph = random_graph(40, 50, seed);
+            let components = graph.connected_components().len();
+            let expected: Vec<NodeID> = graph
+                .node_ids()
+                .filter(|node| {
+                    let mut removed = graph.clone();
+                    removed.remove_node(*node);
+                    removed.connected_components().len() > components
+                })
+                .collect();
+            assert_eq!(graph.articulation_points(), expected, "seed {seed}");
+        }
+    }
+    /// Checks that the ears contain every edge once and that each ear is a path or cycle that starts and ends on earlier ears.
+    fn assert_valid_ears<T>(graph: &AdjListGraph<T>, root: NodeID, ears: &[Vec<EdgeID>]) {
+        let mut edges: Vec<EdgeID> = ears.concat();
+        edges.sort_unstable();
+        assert_eq!(edges, graph.edge_ids().collect::<Vec<_>>());
+
+        let mut seen = vec![false; graph.nodes.len()];
+        for (index, ear) in ears.iter().enumerate() {
+            // An ear starts on the node of its first edge that the second edge does not continue from.
+            let (a, b) = graph[ear[0]].nodes();
+            let mut current = match ear.get(1).map(|next| graph[*next].nodes()) {
+                Some((c, d)) if a == c || a == d => b,
+                _ => a,
+            };
+            if index == 0 {
+                assert_eq!(current, root, "The first ear starts at the root");
+            } else {
+                assert!(seen[current.0], "Ears start on an earlier ear");
+            }
+            let first = current;
+            seen[current.0] = true;
+            for (position, edge) in ear.iter().enumerate() {
+                current = graph[*edge].other_node(current);
+                if position + 1 < ear.len() {
+                    assert!(!seen[current.0], "Inner nodes of an ear are new");
+                }
+                seen[current.0] = true;
+            }
+            if index == 0 {
+                assert_eq!(current, first, "The first ear is a cycle");
+            }
+        }
+    }
+    #[test]
+    pub fn cycle_is_one_ear() {
+        let mut graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+
+            a -- b;
+            b -- c;
+            c -- d;
+            d -- a;
+        };
+        let ears = graph.ear_decomposition(NodeID(0)).unwrap();
+        assert_eq!(ears.len(), 1);
+        assert_valid_ears(&graph, NodeID(0), &ears);
+        assert!(graph.bridges().is_empty());
+
+        graph.connect_nodes(NodeID(0), NodeID(2)).unwrap();
+        let ears = graph.ear_decomposition(NodeID(1)).unwrap();
+        assert_eq!(ears.len(), 2);
+        assert_valid_ears(&graph, NodeID(1), &ears);
+    }
+    #[test]
+    pub fn bridge_has_no_ears() {
+        // Two triangles joined by the edge C -- D.
+        let mut graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+            e [value = 'E'];
+            f [value = 'F'];
+
+            a -- b;
+            b -- c;
+            c -- a;
+            c -- d;
+            d -- e;
+            e -- f;
+            f -- d;
+        };
+        let bridge = graph.connection(NodeID(2), NodeID(3)).unwrap().0;
+        assert_eq!(graph.bridges(), vec![bridge]);
+        assert_eq!(graph.ear_decomposition(NodeID(0)), None);
+
+        // A second edge across makes it 2-edge-connected.
+        graph.connect_nodes(NodeID(0), NodeID(5)).unwrap();
+        assert!(graph.bridges().is_empty());
+        let ears = graph.ear_decomposition(NodeID(4)).unwrap();
+        assert_valid_ears(&graph, NodeID(4), &ears);
+
+        assert_eq!(graph.ear_decomposition(NodeID(100)), None);
+        graph.remove_node(NodeID(2));
+        assert_eq!(graph.ear_decomposition(NodeID(2)), None);
+        assert_eq!(graph.ear_decomposition(NodeID(0)), None);
+        assert_eq!(graph.bridges().len(), 2);
+    }
+    #[test]
+    pub fn bridges_in_every_component() {
+        let graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+            e [value = 'E'];
+
+            a -- b;
+            c -- d;
+            d -- e;
+            e -- c;
+        };
+        assert_eq!(graph.bridges(), vec![EdgeID(0)]);
+        let single = {
+            let mut graph = AdjListGraph::default();
+            graph.add_node('A');
+            graph
+        };
+        assert_eq!(single.ear_decomposition(NodeID(0)), Some(vec![]));
+    }
+    /// Walks the cycle and checks that it comes back to where it started.
+    fn assert_closed_walk<T>(graph: &AdjListGraph<T>, cycle: &[EdgeID]) {
+        let (start, mut current) = graph[cycle[0]].nodes();
+        for edge in &cycle[1..] {
+            let (a, b) = graph[edge].nodes();
+            assert!(a == current || b == current, "{cycle:?} is not a walk");
+            current = graph[edge].other_node(current);
+        }
+        assert_eq!(current, start, "{cycle:?} is not closed");
+    }
+    #[test]
+    pub fn cycle_basis_of_video_example() {
+        let graph = example_from_video();
+        let cycles = graph.cycle_basis();
+        // 10 edges, 7 nodes and 1 component.
+        assert_eq!(cycles.len(), 10 - 7 + 1);
+        for cycle in &cycles {
+            assert_closed_walk(&graph, cycle);
+        }
+        let mut edges: Vec<EdgeID> = cycles.concat();
+        edges.sort_unstable();
+        edges.dedup();
+        // F -- G is a bridge, so it is in no cycle.
+        assert!(!edges.contains(&EdgeID(9)));
+        assert_eq!(edges.len(), 9);
+
+        let mst = graph.kruskal_find_mst().unwrap();
+        assert!(mst.cycle_basis().is_empty());
+    }
+    #[test]
+    pub fn cycle_basis_counts() {
+        let mut graph = random_graph(200, 300, 11);
+        let a = graph.add_node(0);
+        let self_loop = graph.connect_nodes(a, a).unwrap();
+        let cycles = graph.cycle_basis();
+        assert_eq!(
+            cycles.len(),
+            graph.number_of_edges() + graph.connected_components().len() - graph.number_of_nodes()
+        );
+        for cycle in &cycles {
+            assert_closed_walk(&graph, cycle);
+        }
+        assert!(cycles.contains(&vec![self_loop]));
+    }
+}
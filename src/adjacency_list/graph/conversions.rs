@@ -0,0 +1,115 @@
+use crate::prelude::*;
+
+use super::AdjListGraph;
+use crate::adjacency_list::NodeID;
+
+impl<T> AdjListGraph<T> {
+    /// A graph with two nodes connected by an edge with a weight of 0.
+    ///
+    /// If the values are equal it is a single node with a self loop.
+    /// ```
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    /// let graph = AdjListGraph::edge("a", "b");
+    /// assert_eq!(graph.number_of_nodes(), 2);
+    /// assert_eq!(graph.number_of_edges(), 1);
+    /// ```
+    pub fn edge(a: T, b: T) -> Self
+    where
+        T: PartialEq,
+    {
+        Self::from_pairs_by_value([(a, b, 0)])
+    }
+    /// Equal values become the same node and repeated pairs are connected once, keeping the first weight.
+    ///
+    /// Values are compared with [PartialEq] so this is O(V) per pair. Use [AdjListGraph::from_named_edges] for large inputs.
+    fn from_pairs_by_value(edges: impl IntoIterator<Item = (T, T, u32)>) -> Self
+    where
+        T: PartialEq,
+    {
+        Self::from_edges_by_value(edges, |graph, value| {
+            graph
+                .find_node(|existing| *existing == value)
+                .unwrap_or_else(|| graph.add_node(value))
+        })
+    }
+    /// Connects the nodes `node_for` finds or adds for the values of every edge. Only the first edge between two nodes is kept.
+    pub(super) fn from_edges_by_value(
+        edges: impl IntoIterator<Item = (T, T, u32)>,
+        mut node_for: impl FnMut(&mut Self, T) -> NodeID,
+    ) -> Self {
+        let mut graph = Self::default();
+        for (a, b, weight) in edges {
+            let a = node_for(&mut graph, a);
+            let b = node_for(&mut graph, b);
+            // The error is a duplicate edge. Keeping the first one.
+            let _ = graph.connect_nodes_with_weight(a, b, weight);
+        }
+        graph
+    }
+}
+/// Builds a graph from pairs of values. Every pair is an edge with a weight of 0.
+///
+/// ```
+/// use tux_graph::adjacency_list::AdjListGraph;
+/// let graph: AdjListGraph<_> = [("a", "b"), ("b", "c")].into();
+/// assert_eq!(graph.number_of_nodes(), 3);
+/// assert_eq!(graph.number_of_edges(), 2);
+/// ```
+impl<T: PartialEq, const N: usize> From<[(T, T); N]> for AdjListGraph<T> {
+    fn from(pairs: [(T, T); N]) -> Self {
+        Self::from_pairs_by_value(pairs.into_iter().map(|(a, b)| (a, b, 0)))
+    }
+}
+/// Builds a graph from `(value_a, value_b, weight)` triples.
+///
+/// ```
+/// use tux_graph::adjacency_list::AdjListGraph;
+/// let graph: AdjListGraph<_> = vec![("a", "b", 3), ("b", "c", 4)].into();
+/// assert_eq!(graph.weights().map(|(_, weight)| weight).sum::<u32>(), 7);
+/// ```
+impl<T: PartialEq> From<Vec<(T, T, u32)>> for AdjListGraph<T> {
+    fn from(edges: Vec<(T, T, u32)>) -> Self {
+        Self::from_pairs_by_value(edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+
+    #[test]
+    pub fn from_pairs() {
+        let graph: AdjListGraph<_> = [("a", "b"), ("b", "c"), ("c", "a")].into();
+        assert_eq!(graph.number_of_nodes(), 3);
+        assert_eq!(graph.number_of_edges(), 3);
+        assert_eq!(graph.find_node(|value| *value == "c"), Some(NodeID(2)));
+        assert!(graph.weights().all(|(_, weight)| weight == 0));
+
+        let empty: AdjListGraph<&str> = [].into();
+        assert!(empty.is_empty());
+    }
+    #[test]
+    pub fn duplicate_pairs_are_collapsed() {
+        let graph: AdjListGraph<_> = [("a", "b"), ("b", "a"), ("a", "b"), ("a", "a")].into();
+        assert_eq!(graph.number_of_nodes(), 2);
+        assert_eq!(graph.number_of_edges(), 2);
+
+        let graph: AdjListGraph<_> = vec![('x', 'y', 5), ('y', 'x', 1), ('y', 'z', 2)].into();
+        assert_eq!(graph.number_of_nodes(), 3);
+        assert_eq!(graph.number_of_edges(), 2);
+        assert_eq!(
+            graph
+                .connection(NodeID(0), NodeID(1))
+                .map(|(_, weight)| weight),
+            Some(5)
+        );
+    }
+    #[test]
+    pub fn single_edge() {
+        let graph = AdjListGraph::edge(1, 2);
+        assert_eq!(graph, [(2, 1)].into());
+        let self_loop = AdjListGraph::edge(1, 1);
+        assert_eq!(self_loop.number_of_nodes(), 1);
+        assert!(self_loop.is_node_connected_to_itself(NodeID(0)));
+    }
+}
@@ -0,0 +1,158 @@
+use core::fmt::{self, Debug, Formatter};
+
+use crate::prelude::*;
+
+use crate::adjacency_list::*;
+
+use super::AdjListGraph;
+
+/// Prints a summary of the slots followed by the live nodes and edges.
+///
+/// Each node is printed with its sorted neighbors and each edge with its nodes and weight.
+impl<T: Debug> Debug for AdjListGraph<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let nodes = DebugWith(|f: &mut Formatter<'_>| {
+            f.debug_map()
+                .entries(self.iter_nodes().map(|(id, value)| {
+                    // Not `adjacent`, so a graph with dangling edge IDs can still be printed.
+                    let mut neighbors: Vec<NodeID> = self[id]
+                        .edges
+                        .iter()
+                        .filter_map(|edge| self.get_edge(*edge))
+                        .map(|edge| edge.other_node(id))
+                        .collect();
+                    neighbors.sort_unstable();
+                    (
+                        inline(id),
+                        DebugWith(move |f: &mut Formatter<'_>| {
+                            write!(f, "{value:?} -> {neighbors:?}")
+                        }),
+                    )
+                }))
+                .finish()
+        });
+        let edges = DebugWith(|f: &mut Formatter<'_>| {
+            f.debug_map()
+                .entries(self.edges().map(|edge| {
+                    (
+                        inline(edge.id()),
+                        DebugWith(move |f: &mut Formatter<'_>| {
+                            let (node_a, node_b) = edge.endpoints();
+                            write!(f, "{node_a:?} -- {node_b:?} [weight={}]", edge.weight())
+                        }),
+                    )
+                }))
+                .finish()
+        });
+        f.debug_struct("AdjListGraph")
+            .field("live_nodes", &self.number_of_nodes())
+            .field("dead_nodes", &self.empty_node_slots.len())
+            .field("live_edges", &self.number_of_edges())
+            .field("dead_edges", &self.empty_edge_slots.len())
+            .field("nodes", &nodes)
+            .field("edges", &edges)
+            .finish()
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Debug output of every slot as it is stored, including the removed ones.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let a = graph.add_node('A');
+    /// graph.remove_node(a);
+    /// assert!(format!("{:?}", graph.raw_debug()).contains("value: None"));
+    /// ```
+    pub fn raw_debug(&self) -> RawDebug<'_, T> {
+        RawDebug(self)
+    }
+}
+/// Created by [AdjListGraph::raw_debug].
+pub struct RawDebug<'a, T>(&'a AdjListGraph<T>);
+impl<T: Debug> Debug for RawDebug<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let graph = self.0;
+        f.debug_struct("AdjListGraph")
+            .field("nodes", &graph.nodes)
+            .field("edges", &graph.edges)
+            .field("empty_edge_slots", &graph.empty_edge_slots)
+            .field("empty_node_slots", &graph.empty_node_slots)
+            .field("policy", &graph.policy)
+            .field("node_weights", &graph.node_weights)
+            .field("insertion_order", &graph.insertion_order)
+            .field("content_version", &graph.content_version)
+            .finish()
+    }
+}
+/// Prints the value on one line, even in the alternate `{:#?}` format.
+fn inline<D: Debug>(value: D) -> impl Debug {
+    DebugWith(move |f: &mut Formatter<'_>| write!(f, "{value:?}"))
+}
+/// Debug through a closure.
+struct DebugWith<F>(F);
+impl<F: Fn(&mut Formatter<'_>) -> fmt::Result> Debug for DebugWith<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+
+    fn small_graph() -> AdjListGraph<char> {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+        graph.connect_nodes_with_weight(a, b, 2).unwrap();
+        graph.connect_nodes_with_weight(c, a, 3).unwrap();
+        graph.connect_nodes_with_weight(b, b, 1).unwrap();
+        graph
+    }
+    #[test]
+    pub fn summary() {
+        let graph = small_graph();
+        assert_eq!(
+            format!("{graph:?}"),
+            "AdjListGraph { live_nodes: 3, dead_nodes: 0, live_edges: 3, dead_edges: 0, \
+             nodes: {NodeID(0): 'A' -> [NodeID(1), NodeID(2)], NodeID(1): 'B' -> [NodeID(0), NodeID(1)], NodeID(2): 'C' -> [NodeID(0)]}, \
+             edges: {EdgeID(0): NodeID(0) -- NodeID(1) [weight=2], EdgeID(1): NodeID(2) -- NodeID(0) [weight=3], EdgeID(2): NodeID(1) -- NodeID(1) [weight=1]} }"
+        );
+        assert_eq!(
+            format!("{graph:#?}"),
+            r#"AdjListGraph {
+    live_nodes: 3,
+    dead_nodes: 0,
+    live_edges: 3,
+    dead_edges: 0,
+    nodes: {
+        NodeID(0): 'A' -> [NodeID(1), NodeID(2)],
+        NodeID(1): 'B' -> [NodeID(0), NodeID(1)],
+        NodeID(2): 'C' -> [NodeID(0)],
+    },
+    edges: {
+        EdgeID(0): NodeID(0) -- NodeID(1) [weight=2],
+        EdgeID(1): NodeID(2) -- NodeID(0) [weight=3],
+        EdgeID(2): NodeID(1) -- NodeID(1) [weight=1],
+    },
+}"#
+        );
+    }
+    #[test]
+    pub fn removed_slots_are_hidden() {
+        let mut graph = small_graph();
+        graph.remove_node(NodeID(2));
+        let debug = format!("{graph:?}");
+        assert!(debug.starts_with(
+            "AdjListGraph { live_nodes: 2, dead_nodes: 1, live_edges: 2, dead_edges: 1,"
+        ));
+        assert!(!debug.contains("None"));
+        assert!(!debug.contains(&format!("{}", usize::MAX)));
+        assert!(!debug.contains("NodeID(2)"));
+
+        let raw = format!("{:?}", graph.raw_debug());
+        assert!(raw.contains("value: None"));
+        assert!(raw.contains(&format!("NodeID({})", usize::MAX)));
+    }
+}
@@ -0,0 +1,195 @@
+//! Building a graph from a text edge list.
+use std::io::BufRead;
+
+use thiserror::Error;
+
+use crate::collections::HashMap;
+use crate::{adjacency_list::*, GraphError};
+
+use super::AdjListGraph;
+
+/// How [AdjListGraph::from_edge_list_reader] reads its input.
+#[derive(Debug, Clone)]
+pub struct EdgeListOptions {
+    /// Splits the columns. `None` splits on any whitespace.
+    pub separator: Option<char>,
+    /// Everything after this character is ignored.
+    pub comment: char,
+    /// The weight of edges without a weight column.
+    pub default_weight: u32,
+    /// The expected number of nodes and edges. Used to reserve memory up front.
+    pub capacity_hint: Option<(usize, usize)>,
+    /// Decides what happens to self loops and duplicate edges.
+    pub policy: GraphPolicy,
+}
+impl Default for EdgeListOptions {
+    fn default() -> Self {
+        Self {
+            separator: None,
+            comment: '#',
+            default_weight: 0,
+            capacity_hint: None,
+            policy: GraphPolicy::default(),
+        }
+    }
+}
+#[derive(Debug, Error)]
+pub enum EdgeListError {
+    #[error("Failed to read the edge list: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("Line {line}: {source}")]
+    Graph {
+        line: usize,
+        #[source]
+        source: GraphError,
+    },
+}
+impl AdjListGraph<u64> {
+    /// Reads an edge list with one `node node [weight]` edge per line. The node values are the IDs used in the file.
+    ///
+    /// The input is read one line at a time, so the file is never held in memory.
+    /// Empty lines and comments are skipped. Line numbers in errors start at 1.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{AdjListGraph, EdgeListOptions};
+    ///
+    /// let input = "# from to weight\n1 2 5\n2 3\n";
+    /// let graph = AdjListGraph::from_edge_list_reader(input.as_bytes(), EdgeListOptions::default()).unwrap();
+    /// assert_eq!(graph.number_of_nodes(), 3);
+    /// assert_eq!(graph.number_of_edges(), 2);
+    /// ```
+    pub fn from_edge_list_reader<R: BufRead>(
+        mut reader: R,
+        options: EdgeListOptions,
+    ) -> Result<Self, EdgeListError> {
+        let (nodes, edges) = options.capacity_hint.unwrap_or_default();
+        let mut graph = AdjListGraph::with_policy(options.policy);
+        graph.reserve(nodes, edges);
+        let mut ids: HashMap<u64, NodeID> = HashMap::with_capacity(nodes);
+
+        let mut buffer = String::new();
+        let mut line = 0;
+        loop {
+            buffer.clear();
+            if reader.read_line(&mut buffer)? == 0 {
+                break;
+            }
+            line += 1;
+            let content = match buffer.find(options.comment) {
+                Some(comment) => &buffer[..comment],
+                None => &buffer,
+            };
+            let Some((a, b, weight)) = parse_line(content, &options)
+                .map_err(|message| EdgeListError::Parse { line, message })?
+            else {
+                continue;
+            };
+            let a = *ids.entry(a).or_insert_with(|| graph.add_node(a));
+            let b = *ids.entry(b).or_insert_with(|| graph.add_node(b));
+            graph
+                .connect_nodes_with_weight(a, b, weight)
+                .map_err(|source| EdgeListError::Graph { line, source })?;
+        }
+        Ok(graph)
+    }
+}
+/// Returns `None` for a line without content.
+fn parse_line(line: &str, options: &EdgeListOptions) -> Result<Option<(u64, u64, u32)>, String> {
+    let columns: Vec<&str> = match options.separator {
+        Some(separator) => line.trim().split(separator).map(str::trim).collect(),
+        None => line.split_whitespace().collect(),
+    };
+    let (a, b, weight) = match columns.as_slice() {
+        [] | [""] => return Ok(None),
+        [a, b] => (a, b, None),
+        [a, b, weight] => (a, b, Some(weight)),
+        _ => {
+            return Err(format!(
+                "Expected 2 or 3 columns but found {}",
+                columns.len()
+            ))
+        }
+    };
+    let node = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|error| format!("Invalid node `{value}`: {error}"))
+    };
+    let weight = match weight {
+        Some(weight) => weight
+            .parse::<u32>()
+            .map_err(|error| format!("Invalid weight `{weight}`: {error}"))?,
+        None => options.default_weight,
+    };
+    Ok(Some((node(a)?, node(b)?, weight)))
+}
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn read(input: &str, options: EdgeListOptions) -> Result<AdjListGraph<u64>, EdgeListError> {
+        AdjListGraph::from_edge_list_reader(Cursor::new(input), options)
+    }
+    #[test]
+    pub fn comments_and_missing_weights() {
+        let input = "# A comment\n\n10 20 5\n20 30 # No weight\n  30\t10 7\n";
+        let graph = read(input, EdgeListOptions::default()).unwrap();
+        assert_eq!(graph.number_of_nodes(), 3);
+        assert_eq!(graph.number_of_edges(), 3);
+        let [a, b, c] = [10, 20, 30].map(|value| graph.find_node_with_that_equals(&value).unwrap());
+        assert_eq!(graph.connection(a, b).unwrap().1, 5);
+        assert_eq!(graph.connection(b, c).unwrap().1, 0);
+        assert_eq!(graph.connection(c, a).unwrap().1, 7);
+
+        let options = EdgeListOptions {
+            separator: Some(','),
+            default_weight: 1,
+            capacity_hint: Some((2, 1)),
+            ..Default::default()
+        };
+        let graph = read("1, 2\n", options).unwrap();
+        assert_eq!(graph.weights().next().unwrap().1, 1);
+    }
+    #[test]
+    pub fn bad_lines() {
+        let error = read("1 2\n1 x 3\n", EdgeListOptions::default()).unwrap_err();
+        assert!(
+            matches!(&error, EdgeListError::Parse { line: 2, message } if message.starts_with("Invalid node `x`")),
+            "{error}"
+        );
+        let error = read("1 2 3 4\n", EdgeListOptions::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 1: Expected 2 or 3 columns but found 4"
+        );
+        let error = read("1 2 -1\n", EdgeListOptions::default()).unwrap_err();
+        assert!(matches!(error, EdgeListError::Parse { line: 1, .. }));
+    }
+    #[test]
+    pub fn duplicate_edges() {
+        let input = "1 2 5\n# Again\n2 1 3\n";
+        let error = read(input, EdgeListOptions::default()).unwrap_err();
+        assert!(matches!(
+            error,
+            EdgeListError::Graph {
+                line: 3,
+                source: GraphError::NodesAlreadyConnected { .. }
+            }
+        ));
+
+        let options = EdgeListOptions {
+            policy: GraphPolicy {
+                parallel_edges: PolicyAction::Allow,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let graph = read(input, options).unwrap();
+        assert_eq!(graph.number_of_edges(), 2);
+        assert!(graph.has_parallel_edges());
+    }
+}
@@ -1,28 +1,134 @@
+use core::fmt::{Display, Write};
+
+use crate::prelude::*;
+
 use super::AdjListGraph;
+use crate::adjacency_list::Edge;
 
 impl<T> PartialEq for AdjListGraph<T>
 where
     T: PartialEq,
 {
+    /// Equal if the live nodes have the same values and weights and the edges connect the same values with the same weights.
+    ///
+    /// Node and edge IDs and empty slots are ignored. See [AdjListGraph::eq_with].
     fn eq(&self, other: &Self) -> bool {
-        // Equals ignoring location and empty slots.
-        for (index, node_a) in self.nodes.iter().enumerate() {
-            if self.is_node_empty(index) {
-                // Node is marked as dead it doesn't need to be checked.
-                continue;
-            }
-            // Finds a node with an equivalent value.
-            let Some(equivalent_item) = other.find_equivalent_node_value(node_a) else {
+        self.eq_with(other, |a, b| a.weight() == b.weight())
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Compares the nodes and the shape of the graphs, ignoring the edge weights.
+    ///
+    /// Useful to check that two runs with different weights produced the same tree.
+    pub fn eq_ignoring_weights(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.eq_with(other, |_, _| true)
+    }
+    /// Compares the graphs by value, using `edge_eq` to decide if two edges between the same values are equal.
+    ///
+    /// Every live node must match exactly one node with an equal value and node weight in `other`, and every live edge exactly one edge
+    /// connecting the same values for which `edge_eq` returns true. The direction an edge was connected in does not matter.
+    ///
+    /// `edge_eq` must be an equivalence relation, such as comparing a property of both edges.
+    /// Edges are paired with the first match, so a predicate like "the weights differ by at most 1" can return false
+    /// even though another pairing of the edges would match.
+    pub fn eq_with<F>(&self, other: &Self, edge_eq: F) -> bool
+    where
+        T: PartialEq,
+        F: Fn(&Edge, &Edge) -> bool,
+    {
+        if self.number_of_nodes() != other.number_of_nodes()
+            || self.number_of_edges() != other.number_of_edges()
+        {
+            return false;
+        }
+        let mut matched_nodes = vec![false; other.nodes.len()];
+        for (id, value) in self.iter_nodes() {
+            let weight = self.node_weight_inner(id.0);
+            let found = other.iter_nodes().find(|(other_id, other_value)| {
+                !matched_nodes[other_id.0]
+                    && value == *other_value
+                    && weight == other.node_weight_inner(other_id.0)
+            });
+            let Some((id, _)) = found else {
                 return false;
             };
-            // Checks if the two nodes are equal.
-            if !node_a.are_nodes_truly_equal(self, equivalent_item, other) {
+            matched_nodes[id.0] = true;
+        }
+        let mut matched_edges = vec![false; other.edges.len()];
+        for id in self.edge_ids() {
+            let edge = &self[id];
+            let (a, b) = (self[edge.node_a].value(), self[edge.node_b].value());
+            let found = other.edge_ids().find(|other_id| {
+                let other_edge = &other[*other_id];
+                let (other_a, other_b) = (
+                    other[other_edge.node_a].value(),
+                    other[other_edge.node_b].value(),
+                );
+                !matched_edges[other_id.0]
+                    && ((a == other_a && b == other_b) || (a == other_b && b == other_a))
+                    && edge_eq(edge, other_edge)
+            });
+            let Some(other_id) = found else {
                 return false;
-            }
+            };
+            matched_edges[other_id.0] = true;
         }
         true
     }
 }
+impl<T> AdjListGraph<T> {
+    /// A stable hex string identifying the structure of the graph.
+    ///
+    /// Built from a canonical form where the node values are sorted and the edges are sorted by the values of their nodes and weight.
+    /// So graphs that are equal produce the same fingerprint, no matter the order they were built in or the dead slots they contain.
+    ///
+    /// The canonical form is hashed with 64 bit FNV-1a, which does not change between versions or platforms.
+    /// It is suitable as a cache key or file name, not as a cryptographic hash.
+    pub fn fingerprint(&self) -> String
+    where
+        T: Ord + Display,
+    {
+        let mut nodes: Vec<&T> = self.iter_nodes().map(|(_, value)| value).collect();
+        nodes.sort();
+        let mut edges: Vec<(&T, &T, u32)> = self
+            .edge_ids()
+            .map(|id| {
+                let edge = &self[id];
+                let a = self[edge.node_a].value();
+                let b = self[edge.node_b].value();
+                (a.min(b), a.max(b), edge.weight())
+            })
+            .collect();
+        edges.sort();
+
+        let mut canonical = String::new();
+        for value in nodes {
+            push_value(&mut canonical, value);
+        }
+        canonical.push('|');
+        for (a, b, weight) in edges {
+            push_value(&mut canonical, a);
+            push_value(&mut canonical, b);
+            let _ = write!(canonical, "/{weight};");
+        }
+        format!("{:016x}", fnv1a(canonical.as_bytes()))
+    }
+}
+/// Values are length prefixed so they can not run into each other.
+fn push_value<T: Display>(canonical: &mut String, value: &T) {
+    let value = value.to_string();
+    let _ = write!(canonical, "{}:{value}", value.len());
+}
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(PRIME)
+    })
+}
 
 #[cfg(test)]
 mod tests {
@@ -66,4 +172,97 @@ mod tests {
 
         assert_eq!(graph_a, graph_b);
     }
+    #[test]
+    pub fn fingerprint_ignores_build_order() {
+        let graph_a = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+            a -- c;
+        };
+        let mut graph_b = graph_no_import! {
+            x [value = "X"];
+            c [value = "C"];
+            b [value = "B"];
+            a [value = "A"];
+
+            c -- a;
+            x -- a;
+            c -- b [weight = 2];
+            b -- a [weight = 1];
+        };
+        graph_b.remove_node(NodeID(0));
+
+        assert_eq!(graph_a.fingerprint(), graph_b.fingerprint());
+        assert_eq!(graph_a.fingerprint().len(), 16);
+        assert_eq!(graph_a, graph_b);
+
+        graph_b.connect_nodes(NodeID(2), NodeID(2)).unwrap();
+        assert_ne!(graph_a.fingerprint(), graph_b.fingerprint());
+    }
+    #[test]
+    pub fn weights_are_compared() {
+        let graph_a = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+        };
+        let graph_b = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            b -- a [weight = 11];
+            c -- b [weight = 22];
+        };
+        assert_ne!(graph_a, graph_b);
+        assert!(graph_a.eq_ignoring_weights(&graph_b));
+        let same_last_digit = |a: &Edge, b: &Edge| a.weight() % 10 == b.weight() % 10;
+        assert!(graph_a.eq_with(&graph_b, same_last_digit));
+        assert!(graph_b.eq_with(&graph_a, same_last_digit));
+
+        let mut graph_c = graph_b.clone();
+        let edge = graph_c.edge_ids().next().unwrap();
+        graph_c[edge].weight = 15;
+        assert!(!graph_a.eq_with(&graph_c, same_last_digit));
+    }
+    #[test]
+    pub fn different_shapes_are_not_equal() {
+        let path = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            a -- b;
+            b -- c;
+        };
+        let other_path = graph_no_import! {
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            a -- b;
+            a -- c;
+        };
+        assert!(!path.eq_ignoring_weights(&other_path));
+        assert_ne!(path, other_path);
+
+        // An extra edge in the other graph is not ignored.
+        let mut triangle = path.clone();
+        triangle.connect_nodes(NodeID(0), NodeID(2)).unwrap();
+        assert_ne!(path, triangle);
+        assert_ne!(triangle, path);
+
+        // Neither is an extra node with a duplicate value.
+        let mut duplicate = path.clone();
+        duplicate.add_node("A");
+        assert_ne!(path, duplicate);
+        assert_ne!(duplicate, path);
+    }
 }
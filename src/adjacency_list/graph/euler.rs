@@ -0,0 +1,234 @@
+//! Eulerian circuits, walks that use every edge exactly once.
+use crate::prelude::*;
+use alloc::collections::BinaryHeap;
+use core::cmp::Reverse;
+
+use crate::utils::logging::debug;
+use crate::{adjacency_list::*, GraphError};
+
+use super::AdjListGraph;
+
+/// Above this many odd nodes [AdjListGraph::eulerize] pairs them greedily. The exact pairing takes O(2^k k) time.
+const MAX_EXACT_ODD_NODES: usize = 20;
+
+impl<T> AdjListGraph<T> {
+    /// True if a closed walk can use every live edge exactly once.
+    ///
+    /// That is the case when every node has an even degree, with self loops counted twice, and all edges are in one component.
+    pub fn has_eulerian_circuit(&self) -> bool {
+        if self.node_ids().any(|node| self.euler_degree(node) % 2 == 1) {
+            return false;
+        }
+        self.connected_components()
+            .iter()
+            .filter(|component| component.iter().any(|node| !self[*node].edges.is_empty()))
+            .count()
+            <= 1
+    }
+    /// Copies the graph and duplicates edges until every node has an even degree, so the copy has an Eulerian circuit.
+    ///
+    /// The nodes with an odd degree are paired up so the shortest paths between the pairs have the lowest total weight,
+    /// and every edge on those paths is duplicated. With more than 20 odd nodes the closest pairs are taken first instead,
+    /// so the added weight may not be the lowest possible.
+    ///
+    /// The copy keeps all IDs of the graph and its [GraphPolicy] allows parallel edges.
+    ///
+    /// Returns the copy and the original edges that were duplicated. Returns [GraphError::Disconnected] if the graph is not connected.
+    pub fn eulerize(&self) -> Result<(AdjListGraph<T>, Vec<EdgeID>), GraphError>
+    where
+        T: Clone,
+    {
+        if !self.is_connected() {
+            let components = self.connected_components().len();
+            debug!(components, "Graph is disconnected");
+            return Err(GraphError::Disconnected { components });
+        }
+        let odd: Vec<NodeID> = self
+            .node_ids()
+            .filter(|node| self.euler_degree(*node) % 2 == 1)
+            .collect();
+        let trees: Vec<_> = odd
+            .iter()
+            .map(|node| self.shortest_path_tree(*node))
+            .collect();
+        let distance = |a: usize, b: usize| trees[a].0[odd[b].0];
+        let pairs = if odd.len() <= MAX_EXACT_ODD_NODES {
+            exact_pairs(odd.len(), distance)
+        } else {
+            greedy_pairs(odd.len(), distance)
+        };
+        let mut eulerized = self.clone();
+        eulerized.policy.parallel_edges = PolicyAction::Allow;
+        let mut duplicated = Vec::new();
+        for (a, b) in pairs {
+            let parents = &trees[a].1;
+            let mut node = odd[b];
+            while let Some(edge_id) = parents[node.0] {
+                let edge = &self[edge_id];
+                eulerized
+                    .connect_nodes_with_weight(edge.node_a, edge.node_b, edge.weight)
+                    .expect("Shortest paths have no self loops and parallel edges are allowed");
+                duplicated.push(edge_id);
+                node = edge.other_node(node);
+            }
+        }
+        Ok((eulerized, duplicated))
+    }
+    /// The number of edge ends at the node. Unlike [Node::edges], a self loop counts twice.
+    fn euler_degree(&self, node: NodeID) -> usize {
+        self.adjacent(node)
+            .map(|(_, other, _)| if other == node { 2 } else { 1 })
+            .sum()
+    }
+    /// The distance from `from` to every node and the edge each node is reached through.
+    ///
+    /// Unreachable nodes have a distance of [u64::MAX].
+    fn shortest_path_tree(&self, from: NodeID) -> (Vec<u64>, Vec<Option<EdgeID>>) {
+        let mut distances = vec![u64::MAX; self.nodes.len()];
+        let mut parents = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        distances[from.0] = 0;
+        queue.push(Reverse((0, from)));
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if distance > distances[node.0] {
+                continue;
+            }
+            for (edge, next, weight) in self.adjacent(node) {
+                let next_distance = distance + weight as u64;
+                if next_distance < distances[next.0] {
+                    distances[next.0] = next_distance;
+                    parents[next.0] = Some(edge);
+                    queue.push(Reverse((next_distance, next)));
+                }
+            }
+        }
+        (distances, parents)
+    }
+}
+/// The pairing of `0..count` with the lowest total distance. `count` must be even.
+fn exact_pairs(count: usize, distance: impl Fn(usize, usize) -> u64) -> Vec<(usize, usize)> {
+    let full = (1usize << count) - 1;
+    // The lowest cost of pairing up the set bits, and the partner of the lowest set bit.
+    let mut best = vec![(u64::MAX, 0); full + 1];
+    best[0].0 = 0;
+    for mask in 1..=full {
+        if mask.count_ones() % 2 == 1 {
+            continue;
+        }
+        let first = mask.trailing_zeros() as usize;
+        let rest = mask & !(1 << first);
+        let mut others = rest;
+        while others != 0 {
+            let other = others.trailing_zeros() as usize;
+            others &= others - 1;
+            let cost = best[rest & !(1 << other)]
+                .0
+                .saturating_add(distance(first, other));
+            if cost < best[mask].0 {
+                best[mask] = (cost, other);
+            }
+        }
+    }
+    let mut pairs = Vec::with_capacity(count / 2);
+    let mut mask = full;
+    while mask != 0 {
+        let first = mask.trailing_zeros() as usize;
+        let other = best[mask].1;
+        pairs.push((first, other));
+        mask &= !(1 << first | 1 << other);
+    }
+    pairs
+}
+/// Pairs the closest of the remaining `0..count` first. `count` must be even.
+fn greedy_pairs(count: usize, distance: impl Fn(usize, usize) -> u64) -> Vec<(usize, usize)> {
+    let distance = &distance;
+    let mut candidates: Vec<(u64, usize, usize)> = (0..count)
+        .flat_map(|a| (a + 1..count).map(move |b| (distance(a, b), a, b)))
+        .collect();
+    candidates.sort_unstable();
+    let mut paired = vec![false; count];
+    candidates
+        .into_iter()
+        .filter(|(_, a, b)| {
+            if paired[*a] || paired[*b] {
+                return false;
+            }
+            paired[*a] = true;
+            paired[*b] = true;
+            true
+        })
+        .map(|(_, a, b)| (a, b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+    use crate::GraphError;
+
+    fn assert_even(graph: &AdjListGraph<impl Sized>) {
+        for node in graph.node_ids() {
+            let degree: usize = graph
+                .neighbor_edges(node)
+                .map(|edge| if edge.is_self_loop() { 2 } else { 1 })
+                .sum();
+            assert_eq!(degree % 2, 0, "{node:?}");
+        }
+        assert!(graph.has_eulerian_circuit());
+    }
+    #[test]
+    pub fn eulerize_video_example() {
+        let graph = example_from_video();
+        assert!(!graph.has_eulerian_circuit());
+        let (eulerized, mut duplicated) = graph.eulerize().unwrap();
+        assert_even(&eulerized);
+        assert!(eulerized.has_parallel_edges());
+        assert_eq!(
+            eulerized.number_of_edges(),
+            graph.number_of_edges() + duplicated.len()
+        );
+        // A, B, D, E, F and G are odd. The cheapest pairing is F-G (9), A-D (3) and B-E (3).
+        let added: u64 = duplicated
+            .iter()
+            .map(|edge| graph[*edge].weight() as u64)
+            .sum();
+        assert_eq!(added, 15);
+        duplicated.sort_unstable();
+        assert_eq!(duplicated, vec![EdgeID(2), EdgeID(4), EdgeID(9)]);
+    }
+    #[test]
+    pub fn eulerize_many_odd_nodes() {
+        // Every leaf of the star is odd, which is too many for the exact pairing.
+        let mut graph = AdjListGraph::default();
+        let center = graph.add_node(0);
+        for leaf in 1..=22 {
+            let leaf_node = graph.add_node(leaf);
+            graph
+                .connect_nodes_with_weight(center, leaf_node, leaf)
+                .unwrap();
+        }
+        let (eulerized, duplicated) = graph.eulerize().unwrap();
+        assert_even(&eulerized);
+        // Every leaf edge has to be doubled no matter how the leaves are paired.
+        assert_eq!(duplicated.len(), 22);
+    }
+    #[test]
+    pub fn eulerize_edge_cases() {
+        let mut graph: AdjListGraph<_> = [("a", "b"), ("b", "c"), ("c", "a")].into();
+        let a = graph.find_node_with_that_equals(&"a").unwrap();
+        graph.connect_nodes(a, a).unwrap();
+        assert!(graph.has_eulerian_circuit());
+        let (eulerized, duplicated) = graph.eulerize().unwrap();
+        assert!(duplicated.is_empty());
+        assert_eq!(eulerized, graph);
+
+        graph.add_node("d");
+        // An isolated node does not break the circuit but it can not be reached.
+        assert!(graph.has_eulerian_circuit());
+        assert_eq!(
+            graph.eulerize().unwrap_err(),
+            GraphError::Disconnected { components: 2 }
+        );
+        assert!(AdjListGraph::<u8>::default().has_eulerian_circuit());
+    }
+}
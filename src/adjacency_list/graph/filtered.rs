@@ -0,0 +1,145 @@
+use alloc::boxed::Box;
+
+use crate::adjacency_list::*;
+use crate::algo::GraphRead;
+
+use super::AdjListGraph;
+
+type NodeFilter<'a> = Box<dyn Fn(NodeID) -> bool + 'a>;
+type EdgeFilter<'a> = Box<dyn Fn(EdgeID, &Edge) -> bool + 'a>;
+/// A read only view of an [AdjListGraph] that hides some of its nodes and edges.
+///
+/// Created by [AdjListGraph::filtered]. Nothing is copied. Use the functions in [crate::algo] to run algorithms on the view.
+pub struct FilteredGraph<'a, T> {
+    graph: &'a AdjListGraph<T>,
+    node_ok: NodeFilter<'a>,
+    edge_ok: EdgeFilter<'a>,
+}
+impl<T> AdjListGraph<T> {
+    /// Creates a view that only contains the nodes where `node_ok` returns true and the edges where `edge_ok` returns true.
+    ///
+    /// An edge is also hidden if either of its nodes is hidden.
+    ///
+    /// ```
+    /// use tux_graph::{algo, graph};
+    /// let graph = graph! {
+    ///     a [value='A'];
+    ///     b [value='B'];
+    ///     c [value='C'];
+    ///     a -- b;
+    ///     b -- c;
+    /// };
+    /// let without_b = graph.filtered(|node| graph[node].value() != &'B', |_, _| true);
+    /// assert_eq!(algo::connected_components(&without_b).len(), 2);
+    /// ```
+    pub fn filtered<'a>(
+        &'a self,
+        node_ok: impl Fn(NodeID) -> bool + 'a,
+        edge_ok: impl Fn(EdgeID, &Edge) -> bool + 'a,
+    ) -> FilteredGraph<'a, T> {
+        FilteredGraph {
+            graph: self,
+            node_ok: Box::new(node_ok),
+            edge_ok: Box::new(edge_ok),
+        }
+    }
+}
+impl<T> FilteredGraph<'_, T> {
+    /// True if the node is alive in the graph and not hidden by the filter.
+    ///
+    /// ```
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+    /// graph.remove_node(a);
+    /// let view = graph.filtered(|_| true, |_, _| true);
+    /// assert!(!view.is_node_alive(a));
+    /// assert!(view.is_node_alive(b));
+    /// ```
+    pub fn is_node_alive(&self, id: NodeID) -> bool {
+        self.graph.is_node_alive(id) && (self.node_ok)(id)
+    }
+    /// True if the edge is alive in the graph and neither it nor its nodes are hidden by the filter.
+    pub fn is_edge_alive(&self, id: EdgeID) -> bool {
+        let Some(edge) = self.graph.get_edge(id) else {
+            return false;
+        };
+        (self.edge_ok)(id, edge) && (self.node_ok)(edge.node_a) && (self.node_ok)(edge.node_b)
+    }
+}
+impl<T> GraphRead for FilteredGraph<'_, T> {
+    type NodeId = NodeID;
+    type EdgeId = EdgeID;
+    fn node_ids(&self) -> impl Iterator<Item = NodeID> + '_ {
+        self.graph.node_ids().filter(|node| (self.node_ok)(*node))
+    }
+    fn neighbors(&self, node: NodeID) -> impl Iterator<Item = (EdgeID, NodeID)> + '_ {
+        self.graph
+            .adjacent(node)
+            .filter(|(edge, _, _)| self.is_edge_alive(*edge))
+            .map(|(edge, next, _)| (edge, next))
+    }
+    fn edge_weight(&self, edge: EdgeID) -> Option<u32> {
+        self.is_edge_alive(edge).then(|| self.graph[edge].weight())
+    }
+    fn number_of_nodes(&self) -> usize {
+        GraphRead::node_ids(self).count()
+    }
+    fn number_of_edges(&self) -> usize {
+        self.graph
+            .edge_ids()
+            .filter(|edge| self.is_edge_alive(*edge))
+            .count()
+    }
+    fn contains_node(&self, node: NodeID) -> bool {
+        self.is_node_alive(node)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::*;
+    use crate::algo::{self, GraphRead};
+
+    #[test]
+    pub fn masked_bridge() {
+        let graph = graph_no_import! {
+            a [value='A'];
+            b [value='B'];
+            c [value='C'];
+            d [value='D'];
+            e [value='E'];
+            f [value='F'];
+
+            a -- b;
+            b -- c;
+            c -- a;
+            d -- e;
+            e -- f;
+            f -- d;
+            c -- d [weight=10];
+        };
+        let bridge = graph.connection(NodeID(2), NodeID(3)).unwrap().0;
+        let view = graph.filtered(|_| true, |edge, _| edge != bridge);
+
+        assert_eq!(algo::connected_components(&view).len(), 2);
+        assert_eq!(view.number_of_edges(), 6);
+        assert_eq!(algo::bfs(&view, NodeID(0)).unwrap().len(), 3);
+        // The underlying graph is untouched.
+        assert_eq!(graph.connected_components().len(), 1);
+        assert_eq!(graph.number_of_edges(), 7);
+
+        // Hiding `d` hides its edges too.
+        let view = graph.filtered(|node| node != NodeID(3), |_, _| true);
+        assert_eq!(view.number_of_nodes(), 5);
+        assert_eq!(view.number_of_edges(), 4);
+        assert_eq!(view.edge_weight(bridge), None);
+        assert!(!view.contains_node(NodeID(3)));
+        assert_eq!(
+            algo::dijkstra_with_node_costs(&view, NodeID(0), NodeID(4), |_| 0),
+            None
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Maximum flow based path counting.
+//!
+//! By Menger's theorem the maximum number of disjoint paths between two nodes equals the connectivity between them.
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+
+use crate::adjacency_list::*;
+
+use super::AdjListGraph;
+
+/// A directed flow network where every arc is stored next to its reverse arc.
+///
+/// The reverse arc of arc `i` is `i ^ 1`.
+#[derive(Debug, Clone)]
+struct FlowNetwork {
+    arcs_from: Vec<Vec<usize>>,
+    arc_targets: Vec<usize>,
+    capacities: Vec<u32>,
+}
+impl FlowNetwork {
+    fn new(nodes: usize) -> Self {
+        Self {
+            arcs_from: vec![Vec::new(); nodes],
+            arc_targets: Vec::new(),
+            capacities: Vec::new(),
+        }
+    }
+    fn add_arc(&mut self, from: usize, to: usize, capacity: u32) {
+        self.arcs_from[from].push(self.arc_targets.len());
+        self.arc_targets.push(to);
+        self.capacities.push(capacity);
+        self.arcs_from[to].push(self.arc_targets.len());
+        self.arc_targets.push(from);
+        self.capacities.push(0);
+    }
+    /// Edmonds-Karp. Every augmenting path found by the BFS pushes one unit of flow.
+    fn max_flow(mut self, source: usize, sink: usize) -> u32 {
+        let mut flow = 0;
+        loop {
+            let mut parent_arc: Vec<Option<usize>> = vec![None; self.arcs_from.len()];
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                if node == sink {
+                    break;
+                }
+                for &arc in &self.arcs_from[node] {
+                    let next = self.arc_targets[arc];
+                    if self.capacities[arc] > 0 && next != source && parent_arc[next].is_none() {
+                        parent_arc[next] = Some(arc);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            if parent_arc[sink].is_none() {
+                return flow;
+            }
+            let mut node = sink;
+            while let Some(arc) = parent_arc[node] {
+                self.capacities[arc] -= 1;
+                self.capacities[arc ^ 1] += 1;
+                node = self.arc_targets[arc ^ 1];
+            }
+            flow += 1;
+        }
+    }
+}
+
+impl<T> AdjListGraph<T> {
+    /// The maximum number of paths between `a` and `b` that do not share an edge.
+    ///
+    /// Equal to the edge connectivity between the two nodes. Self-loops are ignored.
+    ///
+    /// Returns 0 if `a == b` or either node does not exist.
+    pub fn edge_disjoint_paths(&self, a: NodeID, b: NodeID) -> u32 {
+        if a == b || !self.does_node_id_exist(a) || !self.does_node_id_exist(b) {
+            return 0;
+        }
+        let mut network = FlowNetwork::new(self.nodes.len());
+        for (index, edge) in self.edges.iter().enumerate() {
+            if self.is_edge_empty(index) || edge.node_a == edge.node_b {
+                continue;
+            }
+            network.add_arc(edge.node_a.0, edge.node_b.0, 1);
+            network.add_arc(edge.node_b.0, edge.node_a.0, 1);
+        }
+        network.max_flow(a.0, b.0)
+    }
+    /// The maximum number of paths between `a` and `b` that do not share any node other than `a` and `b`.
+    ///
+    /// Equal to the vertex connectivity between the two nodes. A direct edge between `a` and `b` counts as one path.
+    ///
+    /// Returns 0 if `a == b` or either node does not exist.
+    pub fn node_disjoint_paths(&self, a: NodeID, b: NodeID) -> u32 {
+        if a == b || !self.does_node_id_exist(a) || !self.does_node_id_exist(b) {
+            return 0;
+        }
+        // Every node is split into an in node (2 * index) and an out node (2 * index + 1).
+        // The arc between them limits how many paths can pass through the node.
+        let node_in = |node: NodeID| node.0 * 2;
+        let node_out = |node: NodeID| node.0 * 2 + 1;
+        let mut network = FlowNetwork::new(self.nodes.len() * 2);
+        for index in 0..self.nodes.len() {
+            let node = NodeID(index);
+            if self.is_node_empty(index) {
+                continue;
+            }
+            let capacity = if node == a || node == b { u32::MAX } else { 1 };
+            network.add_arc(node_in(node), node_out(node), capacity);
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            if self.is_edge_empty(index) || edge.node_a == edge.node_b {
+                continue;
+            }
+            network.add_arc(node_out(edge.node_a), node_in(edge.node_b), 1);
+            network.add_arc(node_out(edge.node_b), node_in(edge.node_a), 1);
+        }
+        network.max_flow(node_out(a), node_in(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::*;
+
+    fn square() -> (AdjListGraph<char>, [NodeID; 4]) {
+        let mut graph = AdjListGraph::default();
+        let nodes = graph.add_nodes_from_sized_array(['A', 'B', 'C', 'D']);
+        let [a, b, c, d] = nodes;
+        graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        graph.connect_nodes(c, d).unwrap();
+        graph.connect_nodes(d, a).unwrap();
+        (graph, nodes)
+    }
+    #[test]
+    pub fn cycle_disjoint_paths() {
+        let (mut graph, [a, b, c, d]) = square();
+        assert_eq!(graph.edge_disjoint_paths(a, c), 2);
+        assert_eq!(graph.node_disjoint_paths(a, c), 2);
+        assert_eq!(graph.edge_disjoint_paths(b, d), 2);
+        assert_eq!(graph.node_disjoint_paths(b, d), 2);
+
+        graph.connect_nodes(a, c).unwrap();
+        assert_eq!(graph.edge_disjoint_paths(a, c), 3);
+        assert_eq!(graph.node_disjoint_paths(a, c), 3);
+        // Self loops do not add paths.
+        graph.connect_nodes(a, a).unwrap();
+        assert_eq!(graph.edge_disjoint_paths(a, c), 3);
+    }
+    #[test]
+    pub fn bowtie_disjoint_paths() {
+        // Two triangles sharing the node C.
+        let graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+            e [value = 'E'];
+
+            a -- b;
+            b -- c;
+            c -- a;
+            c -- d;
+            d -- e;
+            e -- c;
+        };
+        let a = graph.find_node_with_that_equals(&'A').unwrap();
+        let e = graph.find_node_with_that_equals(&'E').unwrap();
+        assert_eq!(graph.edge_disjoint_paths(a, e), 2);
+        // Every path has to go through C.
+        assert_eq!(graph.node_disjoint_paths(a, e), 1);
+        assert_eq!(graph.edge_disjoint_paths(a, a), 0);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::adjacency_list::*;
+use crate::algo::GraphRead;
+
+use super::AdjListGraph;
+
+impl<T> GraphRead for AdjListGraph<T> {
+    type NodeId = NodeID;
+    type EdgeId = EdgeID;
+    fn node_ids(&self) -> impl Iterator<Item = NodeID> + '_ {
+        AdjListGraph::node_ids(self)
+    }
+    fn neighbors(&self, node: NodeID) -> impl Iterator<Item = (EdgeID, NodeID)> + '_ {
+        self.adjacent(node).map(|(edge, next, _)| (edge, next))
+    }
+    fn edge_weight(&self, edge: EdgeID) -> Option<u32> {
+        self.does_edge_id_exist(edge).then(|| self[edge].weight())
+    }
+    fn number_of_nodes(&self) -> usize {
+        AdjListGraph::number_of_nodes(self)
+    }
+    fn number_of_edges(&self) -> usize {
+        AdjListGraph::number_of_edges(self)
+    }
+    fn contains_node(&self, node: NodeID) -> bool {
+        self.is_node_alive(node)
+    }
+}
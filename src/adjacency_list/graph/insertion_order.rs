@@ -0,0 +1,148 @@
+//! The order the nodes were added in, for graphs that track it.
+use crate::prelude::*;
+
+use crate::adjacency_list::NodeID;
+
+use super::AdjListGraph;
+
+/// A sequence number for every node slot. Dead slots hold [InsertionOrder::DEAD].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InsertionOrder {
+    sequence: Vec<u64>,
+    next: u64,
+}
+impl InsertionOrder {
+    const DEAD: u64 = u64::MAX;
+    /// Gives the node the next sequence number.
+    pub fn record(&mut self, node: NodeID) {
+        if self.sequence.len() <= node.0 {
+            self.sequence.resize(node.0 + 1, Self::DEAD);
+        }
+        self.sequence[node.0] = self.next;
+        self.next += 1;
+    }
+    pub fn forget(&mut self, node: NodeID) {
+        if let Some(sequence) = self.sequence.get_mut(node.0) {
+            *sequence = Self::DEAD;
+        }
+    }
+    /// Follows a node that was moved by compaction.
+    pub fn moved(&mut self, old_index: usize, new_index: usize) {
+        if let Some(sequence) = self.sequence.get(old_index).copied() {
+            self.sequence[new_index] = sequence;
+        }
+    }
+    pub fn truncate(&mut self, len: usize) {
+        self.sequence.truncate(len);
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Creates an empty graph that remembers the order its nodes are added in. See [AdjListGraph::nodes_in_insertion_order].
+    pub fn with_insertion_order() -> Self {
+        let mut graph = Self::default();
+        graph.track_insertion_order();
+        graph
+    }
+    /// Starts remembering the order nodes are added in. Costs one [u64] per node slot.
+    ///
+    /// Nodes that already exist are treated as added in the order of their IDs. Does nothing if the graph already tracks the order.
+    ///
+    /// The order is not serialized. A deserialized graph does not track it until this is called again.
+    pub fn track_insertion_order(&mut self) {
+        if self.insertion_order.is_some() {
+            return;
+        }
+        let mut order = InsertionOrder::default();
+        for node in self.node_ids() {
+            order.record(node);
+        }
+        self.insertion_order = Some(order);
+    }
+    pub fn tracks_insertion_order(&self) -> bool {
+        self.insertion_order.is_some()
+    }
+    /// The live nodes, oldest first.
+    ///
+    /// A node added into a reused slot counts as added when it was added, not when the slot was first used.
+    /// Without [AdjListGraph::track_insertion_order] the nodes are in the order of their IDs.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::with_insertion_order();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+    /// graph.remove_node(a);
+    /// // `d` takes the slot of `a`, but it was added last.
+    /// let d = graph.add_node("D");
+    /// assert_eq!(d, a);
+    /// assert_eq!(graph.nodes_in_insertion_order().collect::<Vec<_>>(), vec![b, c, d]);
+    /// ```
+    pub fn nodes_in_insertion_order(&self) -> impl Iterator<Item = NodeID> {
+        let mut nodes: Vec<NodeID> = self.node_ids().collect();
+        if let Some(order) = &self.insertion_order {
+            nodes.sort_by_key(|node| order.sequence.get(node.0).copied());
+        }
+        nodes.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+
+    #[test]
+    pub fn order_survives_reuse_and_compaction() {
+        let mut graph = AdjListGraph::with_insertion_order();
+        let ids = graph.add_nodes_from_iterator(0..6);
+        graph.connect_nodes(ids[1], ids[4]).unwrap();
+        graph.remove_node(ids[1]);
+        graph.remove_node(ids[3]);
+        // Reuses the slots of 1 and then 3.
+        let six = graph.add_node(6);
+        let seven = graph.add_node(7);
+        assert_eq!((six, seven), (ids[1], ids[3]));
+        graph.insert_node_at(NodeID(8), 8).unwrap();
+        let nine = graph.add_node(9);
+        assert_eq!(nine, NodeID(6));
+
+        let values = |graph: &AdjListGraph<i32>| -> Vec<i32> {
+            graph
+                .nodes_in_insertion_order()
+                .map(|node| *graph[node].value())
+                .collect()
+        };
+        assert_eq!(values(&graph), vec![0, 2, 4, 5, 6, 7, 8, 9]);
+
+        graph.remove_nodes(&[ids[0], seven]);
+        graph.remove_dead_values();
+        assert_eq!(values(&graph), vec![2, 4, 5, 6, 8, 9]);
+        graph.add_node(10);
+        assert_eq!(values(&graph), vec![2, 4, 5, 6, 8, 9, 10]);
+        assert_eq!(
+            values(&graph.clone()),
+            vec![2, 4, 5, 6, 8, 9, 10],
+            "Clones keep the order"
+        );
+    }
+    #[test]
+    pub fn untracked_and_late_tracking() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        graph.remove_node(a);
+        let d = graph.add_node("D");
+        assert!(!graph.tracks_insertion_order());
+        assert_eq!(
+            graph.nodes_in_insertion_order().collect::<Vec<_>>(),
+            vec![d, b, c]
+        );
+
+        graph.track_insertion_order();
+        graph.remove_node(b);
+        let e = graph.add_node("E");
+        let f = graph.add_node("F");
+        assert_eq!(
+            graph.nodes_in_insertion_order().collect::<Vec<_>>(),
+            vec![d, c, e, f]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::prelude::*;
+use core::iter::FusedIterator;
+
+use crate::adjacency_list::*;
+
+use super::AdjListGraph;
+
+/// Iterates over the live nodes of a graph in ascending ID order.
+///
+/// Created by [AdjListGraph::iter_nodes]
+#[derive(Debug, Clone)]
+pub struct NodesIter<'graph, T> {
+    graph: &'graph AdjListGraph<T>,
+    index: usize,
+}
+impl<'graph, T> Iterator for NodesIter<'graph, T> {
+    type Item = (NodeID, &'graph T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.graph.nodes.len() {
+            let index = self.index;
+            self.index += 1;
+            if self.graph.is_node_empty(index) {
+                continue;
+            }
+            return Some((NodeID(index), self.graph.nodes[index].value()));
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.graph.nodes.len() - self.index))
+    }
+}
+impl<T> FusedIterator for NodesIter<'_, T> {}
+
+impl<T> AdjListGraph<T> {
+    /// Iterates over the live nodes and their values.
+    ///
+    /// Nodes are returned in ascending ID order. Removed nodes are skipped.
+    pub fn iter_nodes(&self) -> NodesIter<'_, T> {
+        NodesIter {
+            graph: self,
+            index: 0,
+        }
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Iterates over the IDs of the live nodes in ascending order.
+    ///
+    /// Unlike [AdjListGraph::iter_nodes] this does not borrow the values.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeID> + '_ {
+        (0..self.nodes.len())
+            .filter(|index| !self.is_node_empty(*index))
+            .map(NodeID)
+    }
+    /// Iterates over the IDs of the live edges in ascending order.
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeID> + '_ {
+        (0..self.edges.len())
+            .filter(|index| !self.is_edge_empty(*index))
+            .map(EdgeID)
+    }
+    /// Iterates over the live edges in ascending edge ID order.
+    pub fn edges(&self) -> impl Iterator<Item = EdgeRef<'_>> + '_ {
+        self.edge_ids()
+            .map(|id| EdgeRef::new(id, &self.edges[id.0]))
+    }
+    /// Iterates over the edges of a node in ascending edge ID order. A self loop is returned once.
+    ///
+    /// Empty if the node does not exist.
+    pub fn neighbor_edges(&self, node: NodeID) -> impl Iterator<Item = EdgeRef<'_>> + '_ {
+        let mut ids: Vec<EdgeID> = self
+            .get_node(node)
+            .map(|node| node.edges.iter().copied().collect())
+            .unwrap_or_default();
+        ids.sort_unstable();
+        ids.into_iter()
+            .map(|id| EdgeRef::new(id, &self.edges[id.0]))
+    }
+    /// The edge as an [EdgeRef]. `None` if it does not exist or has been removed.
+    pub fn edge_ref(&self, id: EdgeID) -> Option<EdgeRef<'_>> {
+        self.get_edge(id).map(|edge| EdgeRef::new(id, edge))
+    }
+    /// Iterates over every live edge exactly once as `(node_a, node_b, weight)` with `node_a <= node_b`.
+    ///
+    /// Edges are returned in ascending edge ID order. Sorting the endpoints gives stable output no matter which way the edge was connected.
+    pub fn unique_edges(&self) -> impl Iterator<Item = (NodeID, NodeID, u32)> + '_ {
+        self.edges().map(|edge| {
+            let (a, b) = edge.endpoints();
+            (a.min(b), a.max(b), edge.weight())
+        })
+    }
+    /// Iterates over the weights of the live edges in ascending edge ID order.
+    pub fn weights(&self) -> impl Iterator<Item = (EdgeID, u32)> + '_ {
+        self.edges().map(|edge| (edge.id(), edge.weight()))
+    }
+}
+impl<'graph, T> IntoIterator for &'graph AdjListGraph<T> {
+    type Item = (NodeID, &'graph T);
+    type IntoIter = NodesIter<'graph, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_nodes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+
+    #[test]
+    pub fn iterate_after_removal() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d] = graph.add_nodes_from_sized_array(["A", "B", "C", "D"]);
+        graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        graph.remove_node(b);
+
+        let mut values = Vec::new();
+        for (id, value) in &graph {
+            values.push((id, *value));
+        }
+        assert_eq!(values, vec![(a, "A"), (c, "C"), (d, "D")]);
+    }
+    #[test]
+    pub fn ids_after_removal() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        let a_b = graph.connect_nodes(a, b).unwrap();
+        let b_c = graph.connect_nodes(b, c).unwrap();
+        let c_a = graph.connect_nodes(c, a).unwrap();
+        assert_eq!(graph.edge_ids().collect::<Vec<_>>(), vec![a_b, b_c, c_a]);
+
+        graph.remove_node(b);
+        assert_eq!(graph.node_ids().collect::<Vec<_>>(), vec![a, c]);
+        assert_eq!(graph.edge_ids().collect::<Vec<_>>(), vec![c_a]);
+        assert_eq!(graph.weights().collect::<Vec<_>>(), vec![(c_a, 0)]);
+    }
+    #[test]
+    pub fn unique_edges_are_sorted() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        graph.connect_nodes_with_weight(b, a, 1).unwrap();
+        graph.connect_nodes_with_weight(c, b, 2).unwrap();
+        graph.connect_nodes_with_weight(a, c, 3).unwrap();
+        graph.connect_nodes_with_weight(c, c, 4).unwrap();
+
+        let edges: Vec<_> = graph.unique_edges().collect();
+        assert_eq!(edges, vec![(a, b, 1), (b, c, 2), (a, c, 3), (c, c, 4)]);
+        assert!(edges.iter().all(|(node_a, node_b, _)| node_a <= node_b));
+        assert_eq!(edges.len(), graph.number_of_edges());
+    }
+    #[test]
+    pub fn edge_refs() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+        let ab = graph.connect_nodes_with_weight(a, b, 4).unwrap();
+        let removed = graph.connect_nodes(a, c).unwrap();
+        let loop_b = graph.connect_nodes_with_weight(b, b, 2).unwrap();
+        let cb = graph.connect_nodes_with_weight(c, b, 7).unwrap();
+        graph.remove_edge(removed);
+
+        let edges: Vec<EdgeRef> = graph.edges().collect();
+        assert_eq!(
+            edges.iter().map(|edge| edge.id()).collect::<Vec<_>>(),
+            vec![ab, loop_b, cb]
+        );
+        let edge = edges[2];
+        assert_eq!(edge.weight(), 7);
+        assert_eq!(edge.endpoints(), (c, b));
+        assert_eq!(edge.other(b), c);
+        assert_eq!(edge.other(c), b);
+        assert!(!edge.is_self_loop());
+        assert_eq!(edge.values(&graph), (&'C', &'B'));
+        assert!(edges[1].is_self_loop());
+        assert_eq!(edges[1].other(b), b);
+
+        assert_eq!(
+            graph
+                .neighbor_edges(b)
+                .map(|edge| (edge.id(), edge.other(b)))
+                .collect::<Vec<_>>(),
+            vec![(ab, a), (loop_b, b), (cb, c)]
+        );
+        assert_eq!(graph.neighbor_edges(NodeID(9)).count(), 0);
+        assert_eq!(graph.edge_ref(cb), Some(edge));
+        assert_eq!(graph.edge_ref(removed), None);
+    }
+}
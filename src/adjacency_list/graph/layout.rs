@@ -0,0 +1,172 @@
+//! Simple 2D layouts for exporting and plotting.
+//!
+//! Every layout returns a position per node slot. Removed nodes get `None`.
+use crate::prelude::*;
+use core::f64::consts::TAU;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// Places the live nodes evenly on a unit circle in ascending ID order.
+    pub fn layout_circular(&self) -> Vec<Option<(f64, f64)>> {
+        let mut positions = vec![None; self.nodes.len()];
+        let total = self.number_of_nodes() as f64;
+        for (position, (id, _)) in self.iter_nodes().enumerate() {
+            let angle = TAU * position as f64 / total;
+            positions[id.0] = Some((angle.cos(), angle.sin()));
+        }
+        positions
+    }
+    /// Force directed layout using the Fruchterman-Reingold algorithm.
+    ///
+    /// Connected nodes attract each other while every pair of nodes repel each other.
+    /// The maximum movement per iteration cools down linearly to zero.
+    ///
+    /// The nodes start at random positions within the unit square. The same seed will always produce the same layout.
+    pub fn layout_fruchterman_reingold(
+        &self,
+        iterations: usize,
+        seed: u64,
+    ) -> Vec<Option<(f64, f64)>> {
+        let live_nodes = self.live_node_ids();
+        if live_nodes.is_empty() {
+            return vec![None; self.nodes.len()];
+        }
+        let mut random = StdRng::seed_from_u64(seed);
+        // Index into `live_nodes` for every slot.
+        let mut position_index = vec![usize::MAX; self.nodes.len()];
+        let mut positions: Vec<(f64, f64)> = Vec::with_capacity(live_nodes.len());
+        for (index, node) in live_nodes.iter().enumerate() {
+            position_index[node.0] = index;
+            positions.push((random.gen(), random.gen()));
+        }
+        let ideal_distance = (1.0 / live_nodes.len() as f64).sqrt();
+        let initial_temperature = 0.1;
+        for iteration in 0..iterations {
+            let temperature = initial_temperature * (1.0 - iteration as f64 / iterations as f64);
+            let mut displacements = vec![(0.0, 0.0); positions.len()];
+            for a in 0..positions.len() {
+                for b in (a + 1)..positions.len() {
+                    let (dx, dy, distance) = difference(positions[a], positions[b]);
+                    let force = ideal_distance * ideal_distance / distance;
+                    let (fx, fy) = (dx / distance * force, dy / distance * force);
+                    displacements[a].0 += fx;
+                    displacements[a].1 += fy;
+                    displacements[b].0 -= fx;
+                    displacements[b].1 -= fy;
+                }
+            }
+            for (index, edge) in self.edges.iter().enumerate() {
+                if self.is_edge_empty(index) || edge.node_a == edge.node_b {
+                    continue;
+                }
+                let a = position_index[edge.node_a.0];
+                let b = position_index[edge.node_b.0];
+                let (dx, dy, distance) = difference(positions[a], positions[b]);
+                let force = distance * distance / ideal_distance;
+                let (fx, fy) = (dx / distance * force, dy / distance * force);
+                displacements[a].0 -= fx;
+                displacements[a].1 -= fy;
+                displacements[b].0 += fx;
+                displacements[b].1 += fy;
+            }
+            for (position, (dx, dy)) in positions.iter_mut().zip(displacements) {
+                let length = (dx * dx + dy * dy).sqrt().max(f64::EPSILON);
+                let limited = length.min(temperature);
+                position.0 += dx / length * limited;
+                position.1 += dy / length * limited;
+            }
+        }
+        let mut result = vec![None; self.nodes.len()];
+        for (node, position) in live_nodes.into_iter().zip(positions) {
+            result[node.0] = Some(position);
+        }
+        result
+    }
+}
+/// Returns the vector from `b` to `a` and its length.
+///
+/// The length is never smaller than a small minimum to prevent dividing by zero.
+fn difference(a: (f64, f64), b: (f64, f64)) -> (f64, f64, f64) {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let distance = (dx * dx + dy * dy).sqrt().max(0.001);
+    (dx, dy, distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+
+    #[test]
+    pub fn circular_layout() {
+        let mut graph = AdjListGraph::default();
+        let [_, b, _, _, _] = graph.add_nodes_from_sized_array([1, 2, 3, 4, 5]);
+        graph.remove_node(b);
+
+        let positions = graph.layout_circular();
+        assert_eq!(positions.len(), 5);
+        assert_eq!(positions[b.0], None);
+        let mut angles: Vec<f64> = positions
+            .iter()
+            .flatten()
+            .map(|(x, y)| {
+                assert!(((x * x + y * y).sqrt() - 1.0).abs() < 1e-9);
+                y.atan2(*x).rem_euclid(std::f64::consts::TAU)
+            })
+            .collect();
+        angles.sort_by(f64::total_cmp);
+        assert_eq!(angles.len(), 4);
+        for pair in angles.windows(2) {
+            assert!((pair[1] - pair[0] - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        }
+    }
+    fn centroid(positions: &[(f64, f64)]) -> (f64, f64) {
+        let total = positions.len() as f64;
+        let (x, y) = positions
+            .iter()
+            .fold((0.0, 0.0), |(x, y), (px, py)| (x + px, y + py));
+        (x / total, y / total)
+    }
+    fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+    #[test]
+    pub fn force_directed_separates_cliques() {
+        let mut graph = AdjListGraph::default();
+        let first = graph.add_nodes_from_sized_array([0, 1, 2, 3, 4]);
+        let second = graph.add_nodes_from_sized_array([5, 6, 7, 8, 9]);
+        for clique in [first, second] {
+            for (index, a) in clique.iter().enumerate() {
+                for b in &clique[index + 1..] {
+                    graph.connect_nodes(*a, *b).unwrap();
+                }
+            }
+        }
+        graph.connect_nodes(first[0], second[0]).unwrap();
+
+        let positions = graph.layout_fruchterman_reingold(200, 42);
+        assert_eq!(positions, graph.layout_fruchterman_reingold(200, 42));
+
+        let first: Vec<_> = first
+            .iter()
+            .map(|node| positions[node.0].unwrap())
+            .collect();
+        let second: Vec<_> = second
+            .iter()
+            .map(|node| positions[node.0].unwrap())
+            .collect();
+        let (first_center, second_center) = (centroid(&first), centroid(&second));
+        let spread = first
+            .iter()
+            .map(|position| distance(*position, first_center))
+            .chain(
+                second
+                    .iter()
+                    .map(|position| distance(*position, second_center)),
+            )
+            .fold(0.0, f64::max);
+        assert!(distance(first_center, second_center) > spread);
+    }
+}
@@ -0,0 +1,363 @@
+//! Three-way merges of graphs whose nodes are matched by value.
+use crate::collections::{HashMap, HashSet};
+use crate::prelude::*;
+use core::hash::Hash;
+
+use crate::adjacency_list::NodeID;
+use crate::utils::logging::debug;
+use crate::GraphError;
+
+use super::AdjListGraph;
+
+/// Which of the changed graphs of [AdjListGraph::merge3] made a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeSide {
+    Ours,
+    Theirs,
+}
+/// Changes of [AdjListGraph::merge3] that can not both be applied. Neither change is applied, so the merged graph keeps what the base had.
+///
+/// Edges are `(value_a, value_b)` pairs in no particular order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeConflict<T> {
+    /// Both sides set the edge to a different weight, or both added it with a different weight.
+    Weight {
+        edge: (T, T),
+        ours: u32,
+        theirs: u32,
+    },
+    /// One side removed the node and the other connected it with a new edge. The node stays and the new edge is not added.
+    RemovedAndConnected {
+        node: T,
+        removed_by: MergeSide,
+        edge: (T, T, u32),
+    },
+    /// One side removed the edge, or one of its nodes, and the other changed its weight. The edge stays with the weight of the base.
+    RemovedAndChanged {
+        edge: (T, T),
+        removed_by: MergeSide,
+        weight: u32,
+    },
+}
+/// The live nodes and edges of a graph, keyed by the index of the values.
+struct Snapshot {
+    nodes: HashSet<usize>,
+    /// Keyed by the sorted pair of value indexes.
+    edges: HashMap<(usize, usize), u32>,
+}
+/// Every distinct value of the three graphs, in the order they first appear.
+struct Values<'a, T> {
+    index: HashMap<&'a T, usize>,
+    values: Vec<&'a T>,
+}
+impl<'a, T: Eq + Hash> Values<'a, T> {
+    fn snapshot(&mut self, graph: &'a AdjListGraph<T>) -> Snapshot {
+        let mut ids: HashMap<NodeID, usize> = HashMap::with_capacity(graph.number_of_nodes());
+        for (id, value) in graph.iter_nodes() {
+            let index = *self.index.entry(value).or_insert_with(|| {
+                self.values.push(value);
+                self.values.len() - 1
+            });
+            ids.insert(id, index);
+        }
+        let mut edges = HashMap::with_capacity(graph.number_of_edges());
+        for (a, b, weight) in graph.unique_edges() {
+            let (a, b) = (ids[&a], ids[&b]);
+            // Parallel edges collapse into the first one.
+            edges.entry((a.min(b), a.max(b))).or_insert(weight);
+        }
+        Snapshot {
+            nodes: ids.into_values().collect(),
+            edges,
+        }
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Applies the changes `ours` and `theirs` made to `base` and reports the changes that conflict. See [MergeConflict].
+    ///
+    /// Nodes are matched by value and edges by the pair of values they connect, so IDs do not have to line up.
+    /// The values are expected to be unique. A value that appears more than once in a graph is merged into one node,
+    /// and so are parallel edges, keeping the weight of the first one.
+    ///
+    /// The merged graph has the [GraphPolicy] of `base` and no dead slots. Nodes are in the order of `base`, followed by the nodes added by `ours` and then `theirs`.
+    /// Returns an error if an edge from `ours` or `theirs` is rejected by that policy.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let base: AdjListGraph<_> = [("a", "b")].into();
+    /// let mut ours = base.clone();
+    /// let c = ours.add_node("c");
+    /// ours.connect_nodes(c, ours.find_node_with_that_equals(&"a").unwrap()).unwrap();
+    /// let mut theirs = base.clone();
+    /// theirs.add_node("d");
+    ///
+    /// let (merged, conflicts) = AdjListGraph::merge3(&base, &ours, &theirs).unwrap();
+    /// assert!(conflicts.is_empty());
+    /// assert_eq!(merged.number_of_nodes(), 4);
+    /// assert_eq!(merged.number_of_edges(), 2);
+    /// ```
+    pub fn merge3(
+        base: &Self,
+        ours: &Self,
+        theirs: &Self,
+    ) -> Result<(Self, Vec<MergeConflict<T>>), GraphError>
+    where
+        T: Clone + Eq + Hash,
+    {
+        let mut values = Values {
+            index: HashMap::new(),
+            values: Vec::new(),
+        };
+        let base_state = values.snapshot(base);
+        let ours_state = values.snapshot(ours);
+        let theirs_state = values.snapshot(theirs);
+        let state = |side: MergeSide| match side {
+            MergeSide::Ours => &ours_state,
+            MergeSide::Theirs => &theirs_state,
+        };
+        let value = |index: usize| values.values[index].clone();
+        let mut conflicts = Vec::new();
+
+        let mut keys: Vec<(usize, usize)> = base_state
+            .edges
+            .keys()
+            .chain(ours_state.edges.keys())
+            .chain(theirs_state.edges.keys())
+            .copied()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        keys.sort_unstable();
+        let mut edges: Vec<((usize, usize), u32)> = Vec::new();
+        // Nodes that were removed by one side but stay because of a conflict.
+        let mut kept_nodes = HashSet::new();
+        for key in keys {
+            let (a, b) = key;
+            let in_base = base_state.edges.get(&key).copied();
+            let in_ours = ours_state.edges.get(&key).copied();
+            let in_theirs = theirs_state.edges.get(&key).copied();
+            let merged = match (in_base, in_ours, in_theirs) {
+                (_, ours, theirs) if ours == theirs => ours,
+                (base, ours, theirs) if ours == base => theirs,
+                (base, ours, theirs) if theirs == base => ours,
+                (base, Some(ours), Some(theirs)) => {
+                    conflicts.push(MergeConflict::Weight {
+                        edge: (value(a), value(b)),
+                        ours,
+                        theirs,
+                    });
+                    base
+                }
+                (Some(base), ours, theirs) => {
+                    let removed_by = if ours.is_none() {
+                        MergeSide::Ours
+                    } else {
+                        MergeSide::Theirs
+                    };
+                    conflicts.push(MergeConflict::RemovedAndChanged {
+                        edge: (value(a), value(b)),
+                        removed_by,
+                        weight: ours.or(theirs).expect("Only one side removed the edge"),
+                    });
+                    kept_nodes.extend([a, b]);
+                    Some(base)
+                }
+                (None, _, _) => {
+                    unreachable!("An edge missing from two graphs matches one of the cases above")
+                }
+            };
+            let Some(weight) = merged else {
+                continue;
+            };
+            if in_base.is_none() && in_ours != in_theirs {
+                // A new edge from one side to a node the other side removed.
+                let removed_by = if in_ours.is_some() {
+                    MergeSide::Theirs
+                } else {
+                    MergeSide::Ours
+                };
+                let removed: Vec<usize> = [a, b]
+                    .into_iter()
+                    .filter(|node| {
+                        base_state.nodes.contains(node) && !state(removed_by).nodes.contains(node)
+                    })
+                    .collect();
+                if !removed.is_empty() {
+                    for node in removed {
+                        conflicts.push(MergeConflict::RemovedAndConnected {
+                            node: value(node),
+                            removed_by,
+                            edge: (value(a), value(b), weight),
+                        });
+                        kept_nodes.insert(node);
+                    }
+                    continue;
+                }
+            }
+            edges.push((key, weight));
+        }
+
+        let mut merged = AdjListGraph::with_policy(base.policy);
+        let mut ids = HashMap::with_capacity(values.values.len());
+        for index in 0..values.values.len() {
+            let in_ours = ours_state.nodes.contains(&index);
+            let in_theirs = theirs_state.nodes.contains(&index);
+            let keep = if base_state.nodes.contains(&index) {
+                (in_ours && in_theirs) || kept_nodes.contains(&index)
+            } else {
+                in_ours || in_theirs
+            };
+            if keep {
+                ids.insert(index, merged.add_node(value(index)));
+            }
+        }
+        for ((a, b), weight) in edges {
+            // Both sides keep the nodes of a kept edge, unless a conflict keeps them.
+            merged.connect_nodes_with_weight(ids[&a], ids[&b], weight)?;
+        }
+        debug!(
+            nodes = merged.number_of_nodes(),
+            edges = merged.number_of_edges(),
+            conflicts = conflicts.len(),
+            "Merged graphs"
+        );
+        Ok((merged, conflicts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+    use crate::testing::assert_same_graph;
+
+    /// A graph of the nodes in `nodes` and the nodes of `edges`.
+    fn graph(nodes: &str, edges: &[(char, char, u32)]) -> AdjListGraph<char> {
+        let mut graph = AdjListGraph::default();
+        let id = |graph: &mut AdjListGraph<char>, value: char| {
+            graph
+                .find_node_with_that_equals(&value)
+                .unwrap_or_else(|| graph.add_node(value))
+        };
+        for value in nodes.chars() {
+            id(&mut graph, value);
+        }
+        for (a, b, weight) in edges {
+            let (a, b) = (id(&mut graph, *a), id(&mut graph, *b));
+            graph.connect_nodes_with_weight(a, b, *weight).unwrap();
+        }
+        graph
+    }
+    fn base() -> AdjListGraph<char> {
+        graph("abcd", &[('a', 'b', 1), ('b', 'c', 2), ('c', 'd', 3)])
+    }
+    #[test]
+    pub fn clean_merge() {
+        let base = base();
+        // Reweights b-c, adds e and connects it, and removes c-d.
+        let ours = graph("abcde", &[('a', 'b', 1), ('b', 'c', 5), ('d', 'e', 4)]);
+        // Removes a and its edge, and adds the same d-e edge.
+        let mut theirs = base.clone();
+        theirs.remove_node(theirs.find_node_with_that_equals(&'a').unwrap());
+        let d = theirs.find_node_with_that_equals(&'d').unwrap();
+        let e = theirs.add_node('e');
+        theirs.connect_nodes_with_weight(d, e, 4).unwrap();
+        let f = theirs.add_node('f');
+        theirs.connect_nodes_with_weight(f, f, 6).unwrap();
+
+        let (merged, conflicts) = AdjListGraph::merge3(&base, &ours, &theirs).unwrap();
+        assert_eq!(conflicts, vec![]);
+        assert_same_graph(
+            &graph("bcdef", &[('b', 'c', 5), ('d', 'e', 4), ('f', 'f', 6)]),
+            &merged,
+        );
+        let values: Vec<char> = merged.iter_nodes().map(|(_, value)| *value).collect();
+        assert_eq!(values, vec!['b', 'c', 'd', 'e', 'f']);
+
+        let (unchanged, conflicts) = AdjListGraph::merge3(&base, &base, &base).unwrap();
+        assert!(conflicts.is_empty());
+        assert_same_graph(&base, &unchanged);
+    }
+    #[test]
+    pub fn weight_changed_on_both_sides() {
+        let base = base();
+        let ours = graph(
+            "abcd",
+            &[('a', 'b', 7), ('b', 'c', 2), ('c', 'd', 3), ('a', 'd', 1)],
+        );
+        let theirs = graph(
+            "abcd",
+            &[('a', 'b', 8), ('b', 'c', 2), ('c', 'd', 3), ('d', 'a', 2)],
+        );
+
+        let (merged, conflicts) = AdjListGraph::merge3(&base, &ours, &theirs).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![
+                MergeConflict::Weight {
+                    edge: ('a', 'b'),
+                    ours: 7,
+                    theirs: 8
+                },
+                MergeConflict::Weight {
+                    edge: ('a', 'd'),
+                    ours: 1,
+                    theirs: 2
+                },
+            ]
+        );
+        // Neither weight is guessed. The edge both added is left out.
+        assert_same_graph(&base, &merged);
+    }
+    #[test]
+    pub fn removed_and_connected() {
+        let base = base();
+        // Connects d to a new node.
+        let ours = graph(
+            "abcde",
+            &[('a', 'b', 1), ('b', 'c', 2), ('c', 'd', 3), ('d', 'e', 4)],
+        );
+        // Removes d.
+        let theirs = graph("abc", &[('a', 'b', 1), ('b', 'c', 2)]);
+
+        let (merged, conflicts) = AdjListGraph::merge3(&base, &ours, &theirs).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![MergeConflict::RemovedAndConnected {
+                node: 'd',
+                removed_by: MergeSide::Theirs,
+                edge: ('d', 'e', 4),
+            }]
+        );
+        // d stays without the edges theirs removed, and e is added without the new edge.
+        assert_same_graph(&graph("abcde", &[('a', 'b', 1), ('b', 'c', 2)]), &merged);
+
+        let (_, conflicts) = AdjListGraph::merge3(&base, &theirs, &ours).unwrap();
+        assert!(matches!(
+            conflicts[..],
+            [MergeConflict::RemovedAndConnected {
+                removed_by: MergeSide::Ours,
+                ..
+            }]
+        ));
+    }
+    #[test]
+    pub fn removed_and_changed() {
+        let base = base();
+        // Removes the node a and with it a-b.
+        let ours = graph("bcd", &[('b', 'c', 2), ('c', 'd', 3)]);
+        // Reweights a-b and removes c-d.
+        let theirs = graph("abcd", &[('a', 'b', 9), ('b', 'c', 2)]);
+
+        let (merged, conflicts) = AdjListGraph::merge3(&base, &ours, &theirs).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![MergeConflict::RemovedAndChanged {
+                edge: ('a', 'b'),
+                removed_by: MergeSide::Ours,
+                weight: 9,
+            }]
+        );
+        assert_same_graph(&graph("abcd", &[('a', 'b', 1), ('b', 'c', 2)]), &merged);
+    }
+}
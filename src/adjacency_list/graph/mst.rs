@@ -1 +1,4 @@
 mod kruskal;
+mod sensitivity;
+
+pub use sensitivity::MstSensitivity;
@@ -1,46 +1,208 @@
-use std::fmt::Debug;
+use crate::prelude::*;
+use core::cmp::Ordering;
 
-use ahash::{HashMap, HashMapExt};
+use crate::collections::HashMap;
 use itertools::Itertools;
 
-use crate::adjacency_list::{
-    AdjListGraph, Edge, EdgeCopyResult, EdgeID, NodeID, SingleEdgeOrManyEdges,
-};
+use crate::adjacency_list::{AdjListGraph, Edge, EdgeID, NodeID, SingleEdgeOrManyEdges};
+use crate::algo::{Budget, BudgetTracker};
+use crate::utils::logging::{debug, debug_span, trace, trace_span};
+use crate::GraphError;
 
+/// True if the MST is already in the list.
+type IsDuplicate<T> = fn(&[AdjListGraph<T>], &AdjListGraph<T>) -> bool;
+/// The results and limits of a [AdjListGraph::find_all_msts] search.
+struct MstSearch<T> {
+    msts: Vec<AdjListGraph<T>>,
+    /// Set if duplicates are skipped. Keeps the `PartialEq` bound off the search itself.
+    is_duplicate: Option<IsDuplicate<T>>,
+    max_results: usize,
+    /// One step is taken per branch of the search.
+    budget: BudgetTracker,
+    /// Set if the budget ran out.
+    exhausted: Option<GraphError>,
+    /// Set once a limit is hit. Stops every remaining branch.
+    truncated: bool,
+}
+impl<T> MstSearch<T> {
+    fn new(max_results: usize) -> Self {
+        Self {
+            msts: Vec::new(),
+            is_duplicate: None,
+            max_results,
+            budget: BudgetTracker::new(Budget::unlimited()),
+            exhausted: None,
+            truncated: false,
+        }
+    }
+    fn remove_duplicates(&mut self, remove_duplicates: bool)
+    where
+        T: PartialEq,
+    {
+        self.is_duplicate = remove_duplicates.then_some(|msts, mst| msts.contains(mst));
+    }
+    /// Takes a step and checks the limits. Returns true if the search should stop.
+    fn should_stop(&mut self) -> bool {
+        if self.truncated {
+            return true;
+        }
+        if let Err(error) = self.budget.step() {
+            debug!(
+                steps_taken = self.budget.steps_taken(),
+                "Budget exhausted while finding MSTs"
+            );
+            self.exhausted = Some(error);
+            self.truncated = true;
+            return true;
+        }
+        false
+    }
+    fn push(&mut self, mst: AdjListGraph<T>) {
+        if self
+            .is_duplicate
+            .is_some_and(|is_duplicate| is_duplicate(&self.msts, &mst))
+        {
+            trace!("Skipping duplicate MST");
+            return;
+        }
+        if self.msts.len() >= self.max_results {
+            debug!(max_results = self.max_results, "MST limit reached");
+            self.truncated = true;
+            return;
+        }
+        self.msts.push(mst);
+    }
+}
 impl<T> AdjListGraph<T> {
+    /// Finds every minimum spanning tree.
+    ///
+    /// Different orders of equally weighted edges can build the same tree. Pass `remove_duplicates` to only keep the first one,
+    /// or use [AdjListGraph::find_all_msts_with_duplicates] if `T` can not be compared.
     pub fn find_all_msts(&self, remove_duplicates: bool) -> Vec<AdjListGraph<T>>
     where
-        T: Clone + PartialEq + Eq + Debug,
+        T: Clone + PartialEq,
+    {
+        let mut search = MstSearch::new(usize::MAX);
+        search.remove_duplicates(remove_duplicates);
+        self.run_mst_search(&mut search);
+        search.msts
+    }
+    /// [AdjListGraph::find_all_msts] without removing duplicates, so the values only have to be [Clone].
+    pub fn find_all_msts_with_duplicates(&self) -> Vec<AdjListGraph<T>>
+    where
+        T: Clone,
+    {
+        let mut search = MstSearch::new(usize::MAX);
+        self.run_mst_search(&mut search);
+        search.msts
+    }
+    /// Same as [AdjListGraph::find_all_msts] but stops after `max_results` MSTs or once `deadline` has passed.
+    ///
+    /// The deadline is checked like [Budget::until], every [Budget::DEADLINE_CHECK_INTERVAL] branches.
+    /// Returns the MSTs found and whether the search was cut short.
+    /// The search is depth first, so a truncated result still contains complete MSTs.
+    #[cfg(feature = "std")]
+    pub fn find_all_msts_bounded(
+        &self,
+        remove_duplicates: bool,
+        max_results: usize,
+        deadline: Option<std::time::Instant>,
+    ) -> (Vec<AdjListGraph<T>>, bool)
+    where
+        T: Clone + PartialEq,
+    {
+        let mut search = MstSearch::new(max_results);
+        search.remove_duplicates(remove_duplicates);
+        search.budget = BudgetTracker::new(deadline.map_or_else(Budget::unlimited, Budget::until));
+        self.run_mst_search(&mut search);
+        (search.msts, search.truncated)
+    }
+    /// Same as [AdjListGraph::find_all_msts] but gives up with [GraphError::BudgetExhausted] once the budget runs out.
+    ///
+    /// Every branch of the search is one step.
+    /// ```
+    /// use tux_graph::{algo::Budget, graph, GraphError};
+    /// let graph = graph! {
+    ///     a [value='A'];
+    ///     b [value='B'];
+    ///     c [value='C'];
+    ///     a -- b [weight=1];
+    ///     b -- c [weight=1];
+    ///     c -- a [weight=1];
+    /// };
+    /// let error = graph.find_all_msts_with_budget(true, Budget::steps(1)).unwrap_err();
+    /// assert_eq!(error, GraphError::BudgetExhausted { steps_taken: 1 });
+    /// assert_eq!(graph.find_all_msts_with_budget(true, Budget::unlimited()).unwrap().len(), 3);
+    /// ```
+    pub fn find_all_msts_with_budget(
+        &self,
+        remove_duplicates: bool,
+        budget: Budget,
+    ) -> Result<Vec<AdjListGraph<T>>, GraphError>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut search = MstSearch::new(usize::MAX);
+        search.remove_duplicates(remove_duplicates);
+        search.budget = BudgetTracker::new(budget);
+        self.run_mst_search(&mut search);
+        match search.exhausted {
+            Some(error) => Err(error),
+            None => Ok(search.msts),
+        }
+    }
+    fn run_mst_search(&self, search: &mut MstSearch<T>)
+    where
+        T: Clone,
     {
         let edges = self.group_same_weights_and_sort();
-        let mut result = Vec::new();
+        let _span = debug_span!(
+            "find_all_msts",
+            weight_groups = edges.len(),
+            remove_duplicates = search.is_duplicate.is_some()
+        )
+        .entered();
         self.recursive_find_all_msts(
             AdjListGraph::default(),
             HashMap::default(),
             &edges,
-            remove_duplicates,
-            &mut result,
+            0,
+            search,
+        );
+        debug!(
+            msts = search.msts.len(),
+            truncated = search.truncated,
+            "Finished finding all MSTs"
         );
-
-        result
     }
+    /// `group_offset` is the index of `edges[0]` within all the weight groups. It is only used for logging.
     fn recursive_find_all_msts(
         &self,
         mut mst: AdjListGraph<T>,
         mut updated_nodes_id: HashMap<NodeID, NodeID>,
         edges: &[SingleEdgeOrManyEdges],
-        remove_duplicates: bool,
-        msts: &mut Vec<AdjListGraph<T>>,
+        group_offset: usize,
+        search: &mut MstSearch<T>,
     ) where
-        T: Clone + PartialEq + Eq,
+        T: Clone,
     {
+        if search.should_stop() {
+            return;
+        }
         for (how_far, edge) in edges.iter().enumerate() {
             match edge {
                 SingleEdgeOrManyEdges::Single(id, edge) => {
                     maybe_copy_edge(self, &mut mst, *id, &mut updated_nodes_id, edge);
                 }
                 SingleEdgeOrManyEdges::Many(vec) => {
-                    for possible_orderings in vec.iter().permutations(vec.len()) {
+                    let weight_group = group_offset + how_far;
+                    for (branch, possible_orderings) in
+                        vec.iter().permutations(vec.len()).enumerate()
+                    {
+                        if search.truncated {
+                            return;
+                        }
+                        let _span = trace_span!("mst_branch", weight_group, branch).entered();
                         let mut mst_variant = mst.clone();
                         let mut updated_nodes_id = updated_nodes_id.clone();
                         for (id, edge) in possible_orderings {
@@ -56,8 +218,8 @@ impl<T> AdjListGraph<T> {
                             mst_variant,
                             updated_nodes_id,
                             &edges[how_far + 1..],
-                            remove_duplicates,
-                            msts,
+                            weight_group + 1,
+                            search,
                         );
                     }
                     // Skips the current iteration as we had to diverge into multiple paths.
@@ -66,36 +228,103 @@ impl<T> AdjListGraph<T> {
             }
         }
         if mst.number_of_nodes() != 0 {
-            if remove_duplicates {
-                if msts.contains(&mst) {
-                    return;
-                }
-                msts.push(mst);
-            } else {
-                msts.push(mst);
+            search.push(mst);
+        }
+    }
+    /// Finds a minimum spanning tree.
+    ///
+    /// Returns [GraphError::Disconnected] if the graph is not connected, as no tree can span it.
+    /// Use [AdjListGraph::minimum_spanning_forest] for those graphs.
+    ///
+    /// Only works if the graphs data are unique.
+    pub fn kruskal_find_mst(&self) -> Result<AdjListGraph<T>, GraphError>
+    where
+        T: Clone,
+    {
+        let _span = debug_span!(
+            "kruskal_find_mst",
+            nodes = self.number_of_nodes(),
+            edges = self.number_of_edges()
+        )
+        .entered();
+        if !self.is_connected() {
+            let components = self.connected_components().len();
+            debug!(components, "Graph is disconnected");
+            return Err(GraphError::Disconnected { components });
+        }
+        Ok(self.minimum_spanning_forest())
+    }
+    /// Finds a minimum spanning tree for every connected component.
+    ///
+    /// Nodes without any edges are included on their own.
+    ///
+    /// Only works if the graphs data are unique.
+    pub fn minimum_spanning_forest(&self) -> AdjListGraph<T>
+    where
+        T: Clone,
+    {
+        let (mut forest, updated_node_ids) =
+            self.spanning_forest_by(|a, b| a.weight().cmp(&b.weight()));
+        for id in self.node_ids() {
+            if !updated_node_ids.contains_key(&id) {
+                self.copy_node_into(&mut forest, id);
             }
         }
+        forest
     }
+    /// Greedily builds a spanning tree, taking the edges in the order given by `cmp` and skipping any edge that would create a cycle.
+    ///
+    /// Ordering by ascending weight gives the minimum spanning tree. Reversing it gives the maximum spanning tree.
+    /// ```
+    /// use tux_graph::graph;
+    /// let graph = graph! {
+    ///     a [value='A'];
+    ///     b [value='B'];
+    ///     c [value='C'];
+    ///     a -- b [weight=1];
+    ///     b -- c [weight=2];
+    ///     c -- a [weight=3];
+    /// };
+    /// let max = graph.spanning_tree_by(|a, b| b.weight().cmp(&a.weight())).unwrap();
+    /// assert_eq!(max.weights().map(|(_, weight)| weight).sum::<u32>(), 5);
+    /// ```
+    ///
     /// Only works if the graphs data are unique.
-    pub fn kruskal_find_mst(&self) -> Option<AdjListGraph<T>>
+    pub fn spanning_tree_by<F>(&self, cmp: F) -> Option<AdjListGraph<T>>
+    where
+        T: Clone,
+        F: Fn(&Edge, &Edge) -> Ordering,
+    {
+        let (tree, _) = self.spanning_forest_by(cmp);
+        if tree.number_of_nodes() == 0 {
+            None
+        } else {
+            Some(tree)
+        }
+    }
+    /// Returns the forest and the map from the original node IDs to the forest node IDs.
+    fn spanning_forest_by<F>(&self, cmp: F) -> (AdjListGraph<T>, HashMap<NodeID, NodeID>)
     where
-        T: Clone + PartialEq + Eq + Debug,
+        T: Clone,
+        F: Fn(&Edge, &Edge) -> Ordering,
     {
-        let mut mst = AdjListGraph::default();
+        let mut tree = AdjListGraph::default();
         let mut updated_node_ids = HashMap::<NodeID, NodeID>::new();
         let mut edges = self.get_edges_sorted_by_weight();
 
-        edges.sort_by_key(|(_, edge)| edge.weight());
+        edges.sort_by(|(_, a), (_, b)| cmp(a, b));
 
         for (og_index, edge) in edges {
-            maybe_copy_edge(self, &mut mst, og_index, &mut updated_node_ids, edge);
-        }
-
-        if mst.number_of_nodes() == 0 {
-            None
-        } else {
-            Some(mst)
+            if !maybe_copy_edge(self, &mut tree, og_index, &mut updated_node_ids, edge) {
+                trace!(edge = ?og_index, "Skipping edge as it would create a cycle");
+            }
         }
+        debug!(
+            nodes = tree.number_of_nodes(),
+            edges = tree.number_of_edges(),
+            "Found spanning tree"
+        );
+        (tree, updated_node_ids)
     }
 }
 fn maybe_copy_edge<T>(
@@ -136,25 +365,14 @@ fn copy_edge_and_nodes<T>(
 ) where
     T: Clone,
 {
-    let EdgeCopyResult { node_a, node_b, .. } = from
-        .copy_edge_and_referenced_nodes(target, edge, |node| {
-            if let Some(updated_node_id) = updated_node_ids.get(&node) {
-                return Some(*updated_node_id);
-            }
-            None
-        })
-        .unwrap();
-
-    if let Some((og_node_a, new_node_a)) = node_a {
-        updated_node_ids.insert(og_node_a, new_node_a);
-    }
-    if let Some((og_node_b, new_node_b)) = node_b {
-        updated_node_ids.insert(og_node_b, new_node_b);
-    }
+    // The edges come from `from` and the spanning tree never has parallel edges.
+    from.copy_edge_into(target, edge, updated_node_ids)
+        .expect("Spanning tree edges are live and never parallel");
 }
 
 mod cycle {
     use crate::adjacency_list::AdjListGraph;
+    use crate::prelude::*;
 
     pub fn would_adding_edge_cause_cycle<T>(
         graph: &AdjListGraph<T>,
@@ -193,44 +411,18 @@ mod cycle {
 }
 #[cfg(test)]
 mod test {
-    use std::path::PathBuf;
 
     use anyhow::Context;
     use tux_graph_macros::graph_no_import;
 
     use crate::adjacency_list::{
         export::graphiz::{export_graphiz, GraphizSettings},
+        test_graphs::example_from_video,
         AdjListGraph,
     };
-    // Test is based on the example found on this video https://www.youtube.com/watch?v=71UQH7Pr9kU
-    fn example_from_video() -> AdjListGraph<char> {
-        graph_no_import! {
-            a [value='A'];
-            b [value='B'];
-            c [value='C'];
-            d [value='D'];
-            e [value='E'];
-            f [value='F'];
-            g [value='G'];
-            // Edges
-            // A Connections
-            a -- b [weight=2];
-            a -- c [weight=3];
-            a -- d [weight=3];
-            // B Connections
-            b -- c [weight=4];
-            b -- e [weight=3];
-            // C Connections
-            c -- d [weight=5];
-            c -- e [weight=1];
-            // D Connections
-            d -- f [weight=7];
-            // E Connections
-            e -- f [weight=8];
-            // F Connections
-            f -- g [weight=9];
-        }
-    }
+    use crate::algo::Budget;
+    use crate::testing::golden::assert_matches_golden;
+    use crate::GraphError;
     #[test]
     pub fn test_from_video_create() -> anyhow::Result<()> {
         let example_graph = example_from_video();
@@ -252,6 +444,55 @@ mod test {
         Ok(())
     }
     #[test]
+    pub fn maximum_spanning_tree() {
+        let example_graph = example_from_video();
+        let total = |graph: &AdjListGraph<char>| graph.weights().map(|(_, w)| w).sum::<u32>();
+
+        let max = example_graph
+            .spanning_tree_by(|a, b| b.weight().cmp(&a.weight()))
+            .unwrap();
+        assert_eq!(max.number_of_nodes(), 7);
+        assert_eq!(max.number_of_edges(), 6);
+        // F-G 9, E-F 8, D-F 7, C-D 5, B-C 4 and one of the 3s to reach A.
+        assert_eq!(total(&max), 36);
+
+        let min = example_graph.kruskal_find_mst().unwrap();
+        assert_eq!(total(&min), 25);
+    }
+    #[test]
+    pub fn disconnected_mst() {
+        let graph = graph_no_import! {
+            a [value='A'];
+            b [value='B'];
+            c [value='C'];
+            d [value='D'];
+            e [value='E'];
+            f [value='F'];
+
+            a -- b [weight=1];
+            b -- c [weight=2];
+            c -- a [weight=3];
+            d -- e [weight=1];
+            e -- f [weight=2];
+            f -- d [weight=3];
+        };
+        let error = graph.kruskal_find_mst().unwrap_err();
+        assert_eq!(error, GraphError::Disconnected { components: 2 });
+        assert_eq!(error.code(), "disconnected");
+
+        let forest = graph.minimum_spanning_forest();
+        assert_eq!(forest.number_of_nodes(), 6);
+        assert_eq!(forest.number_of_edges(), 4);
+        assert_eq!(forest.connected_components().len(), 2);
+    }
+    #[test]
+    pub fn single_node_mst() {
+        let mut graph = AdjListGraph::default();
+        graph.add_node('A');
+        let mst = graph.kruskal_find_mst().unwrap();
+        assert_eq!(mst.number_of_nodes(), 1);
+    }
+    #[test]
     pub fn test_one() -> anyhow::Result<()> {
         let example_graph = example_from_video();
 
@@ -261,28 +502,16 @@ mod test {
         Ok(())
     }
 
+    /// Compares the DOT and JSON exports of the graph against the golden files `mst/kruskal/{file_name}`.
     fn save_graph(graph: &AdjListGraph<char>, file_name: &str) -> anyhow::Result<()> {
-        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("test")
-            .join("output")
-            .join("mst")
-            .join("kruskal");
-        if !path.exists() {
-            std::fs::create_dir_all(&path).context("Failed to create directory")?;
-        }
-        println!("Saving graph to {:?} with name {file_name}", path);
-        {
-            let graphiz_file = format!("{}.dot", file_name);
-            let file_name = path.join(graphiz_file);
-            let graphiz = export_graphiz(graph, &GraphizSettings::default());
-            std::fs::write(file_name, graphiz).context("Failed to write file")?
-        }
-        {
-            let json_file = format!("{}.json", file_name);
-            let file_name = path.join(json_file);
-            let json = serde_json::to_string_pretty(graph).context("Failed to serialize graph")?;
-            std::fs::write(file_name, json).context("Failed to write file")?
-        }
+        let settings = GraphizSettings {
+            show_weights: true,
+            ..Default::default()
+        };
+        let graphiz = export_graphiz(graph, &settings);
+        assert_matches_golden(&format!("mst/kruskal/{file_name}.dot"), &graphiz);
+        let json = serde_json::to_string_pretty(graph).context("Failed to serialize graph")?;
+        assert_matches_golden(&format!("mst/kruskal/{file_name}.json"), &json);
         Ok(())
     }
     /// This was a graph that was used in one of my class assignments.
@@ -325,4 +554,162 @@ mod test {
 
         assert_eq!(msts.len(), 6, "Only 6 MSTs can be created from this graph");
     }
+    #[test]
+    pub fn find_all_without_comparable_values() {
+        /// Only [Clone], so enumerating MSTs must not need any other bound.
+        #[derive(Clone)]
+        struct Position(f64);
+
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array([0.5, 1.5, 2.5]);
+        for (x, y) in [(a, b), (b, c), (c, a)] {
+            graph.connect_nodes_with_weight(x, y, 1).unwrap();
+        }
+        let msts = graph.find_all_msts_with_duplicates();
+        // Every order of the three edges, and each order keeps its first two.
+        assert_eq!(msts.len(), 6);
+        assert!(msts
+            .iter()
+            .all(|mst| mst.number_of_nodes() == 3 && mst.number_of_edges() == 2));
+        assert_eq!(graph.find_all_msts(true).len(), 3);
+
+        let positions = AdjListGraph::from_raw_parts(
+            vec![Position(0.5), Position(1.5), Position(2.5)],
+            vec![(a, b, 1), (b, c, 1), (c, a, 1)],
+        );
+        assert_eq!(positions.find_all_msts_with_duplicates().len(), 6);
+        let tree = positions.kruskal_find_mst().unwrap();
+        let sum: f64 = tree.iter_nodes().map(|(_, position)| position.0).sum();
+        assert_eq!(sum, 4.5);
+    }
+    #[test]
+    pub fn find_with_budget_for_class_assignment_9() {
+        let graph = graph_no_import! {
+            a [value='A'];
+            b [value='B'];
+            c [value='C'];
+            d [value='D'];
+            e [value='E'];
+            f [value='F'];
+
+            c -- b [weight=1];
+            a -- b [weight=2];
+            a -- d [weight=1];
+            d -- c [weight=2];
+
+            c -- e [weight=3];
+            e -- f [weight=3];
+            f -- c [weight=3];
+        };
+        assert_eq!(
+            graph.find_all_msts_with_budget(true, Budget::steps(0)),
+            Err(GraphError::BudgetExhausted { steps_taken: 0 })
+        );
+        assert_eq!(
+            graph.find_all_msts_with_budget(true, Budget::steps(3)),
+            Err(GraphError::BudgetExhausted { steps_taken: 3 })
+        );
+        let all = graph.find_all_msts(true);
+        assert_eq!(
+            graph.find_all_msts_with_budget(true, Budget::steps(10_000)),
+            Ok(all.clone())
+        );
+        assert_eq!(
+            graph.find_all_msts_with_budget(true, Budget::unlimited()),
+            Ok(all.clone())
+        );
+
+        // The step count is deterministic, so the smallest budget that finishes is always the same.
+        let needed = (0..10_000)
+            .find(|steps| {
+                graph
+                    .find_all_msts_with_budget(true, Budget::steps(*steps))
+                    .is_ok()
+            })
+            .unwrap();
+        assert_eq!(
+            graph.find_all_msts_with_budget(true, Budget::steps(needed - 1)),
+            Err(GraphError::BudgetExhausted {
+                steps_taken: needed - 1
+            })
+        );
+        assert_eq!(
+            graph.find_all_msts_with_budget(true, Budget::steps(needed)),
+            Ok(all)
+        );
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn find_bounded_for_class_assignment_9() {
+        use std::time::{Duration, Instant};
+
+        let graph = graph_no_import! {
+            a [value='A'];
+            b [value='B'];
+            c [value='C'];
+            d [value='D'];
+            e [value='E'];
+            f [value='F'];
+
+            c -- b [weight=1];
+            a -- b [weight=2];
+            a -- d [weight=1];
+            d -- c [weight=2];
+
+            c -- e [weight=3];
+            e -- f [weight=3];
+            f -- c [weight=3];
+        };
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let (msts, truncated) = graph.find_all_msts_bounded(true, 100, Some(deadline));
+        assert_eq!(msts.len(), 6);
+        assert!(!truncated);
+
+        let (msts, truncated) = graph.find_all_msts_bounded(true, 2, None);
+        assert_eq!(msts.len(), 2);
+        assert!(truncated);
+        let all = graph.find_all_msts(true);
+        assert!(msts.iter().all(|mst| all.contains(mst)));
+
+        let (msts, truncated) = graph.find_all_msts_bounded(true, 100, Some(Instant::now()));
+        assert!(msts.is_empty());
+        assert!(truncated);
+    }
+    #[cfg(all(feature = "tracing", feature = "std"))]
+    #[test]
+    pub fn mst_emits_tracing_events() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Captured(Arc<Mutex<Vec<u8>>>);
+        impl std::io::Write for Captured {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let graph = example_from_video();
+            graph.kruskal_find_mst().unwrap();
+            graph.find_all_msts(true);
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("kruskal_find_mst"), "{output}");
+        assert!(output.contains("Found spanning tree"), "{output}");
+        assert!(output.contains("would create a cycle"), "{output}");
+        assert!(output.contains("mst_branch"), "{output}");
+        assert!(output.contains("weight_group="), "{output}");
+        assert!(output.contains("Finished finding all MSTs"), "{output}");
+    }
 }
@@ -0,0 +1,170 @@
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+use crate::adjacency_list::{AdjListGraph, EdgeID, NodeID};
+use crate::utils::logging::debug;
+
+/// How far the edge weights can change before the minimum spanning tree changes. Created by [AdjListGraph::mst_sensitivity].
+///
+/// A tree edge can grow by `limit - weight` and stays in a minimum spanning tree, tied at the limit.
+/// An edge outside of the tree has to shrink by `weight - limit` to tie with the tree edge it would replace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MstSensitivity {
+    /// The edges of the tree and the weight of the lightest edge that reconnects the tree without them.
+    ///
+    /// `None` if nothing else crosses the cut, so the edge stays in the tree at any weight.
+    pub tree_edges: BTreeMap<EdgeID, Option<u32>>,
+    /// The edges outside of the tree and the weight of the heaviest tree edge on the cycle they close.
+    pub non_tree_edges: BTreeMap<EdgeID, u32>,
+}
+impl<T> AdjListGraph<T> {
+    /// Finds a minimum spanning tree and how sensitive it is to changes of each edge weight. See [MstSensitivity].
+    ///
+    /// The tree is built with Kruskal's algorithm, breaking ties by edge ID. Self loops are never in the tree and are left out.
+    /// Returns None if the graph is not connected.
+    ///
+    /// Every edge outside of the tree walks its cycle through the tree, so this is O(E V).
+    pub fn mst_sensitivity(&self) -> Option<MstSensitivity> {
+        if !self.is_connected() {
+            debug!("Graph is disconnected. No spanning tree");
+            return None;
+        }
+        let mut edges: Vec<EdgeID> = self
+            .edge_ids()
+            .filter(|edge| {
+                let edge = &self[*edge];
+                edge.node_a != edge.node_b
+            })
+            .collect();
+        edges.sort_by_key(|edge| (self[*edge].weight(), *edge));
+        let mut sets: Vec<usize> = (0..self.nodes.len()).collect();
+        let (mut tree, mut outside) = (Vec::new(), Vec::new());
+        for id in edges {
+            let edge = &self[id];
+            let (a, b) = (
+                find(&mut sets, edge.node_a.0),
+                find(&mut sets, edge.node_b.0),
+            );
+            if a == b {
+                outside.push(id);
+            } else {
+                sets[a] = b;
+                tree.push(id);
+            }
+        }
+        let Some(root) = self.first_live_node() else {
+            return Some(MstSensitivity {
+                tree_edges: BTreeMap::new(),
+                non_tree_edges: BTreeMap::new(),
+            });
+        };
+        // Root the tree so every cycle can be walked up from both ends.
+        let mut children: Vec<Vec<EdgeID>> = vec![Vec::new(); self.nodes.len()];
+        for id in &tree {
+            let edge = &self[*id];
+            children[edge.node_a.0].push(*id);
+            children[edge.node_b.0].push(*id);
+        }
+        let mut parent_edge: Vec<Option<EdgeID>> = vec![None; self.nodes.len()];
+        let mut depth = vec![0usize; self.nodes.len()];
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            for id in &children[node.0] {
+                if parent_edge[node.0] == Some(*id) {
+                    continue;
+                }
+                let next = self[*id].other_node(node);
+                parent_edge[next.0] = Some(*id);
+                depth[next.0] = depth[node.0] + 1;
+                stack.push(next);
+            }
+        }
+        let mut tree_edges: BTreeMap<EdgeID, Option<u32>> =
+            tree.iter().map(|edge| (*edge, None)).collect();
+        let mut non_tree_edges = BTreeMap::new();
+        for id in outside {
+            let edge = &self[id];
+            let weight = edge.weight();
+            let (mut a, mut b) = (edge.node_a, edge.node_b);
+            let mut heaviest = 0;
+            while a != b {
+                let climbing: &mut NodeID = if depth[a.0] >= depth[b.0] {
+                    &mut a
+                } else {
+                    &mut b
+                };
+                let up = parent_edge[climbing.0].expect("Only the root has no parent");
+                heaviest = heaviest.max(self[up].weight());
+                let limit = tree_edges
+                    .get_mut(&up)
+                    .expect("Parent edges are tree edges");
+                *limit = Some(limit.map_or(weight, |limit| limit.min(weight)));
+                *climbing = self[up].other_node(*climbing);
+            }
+            non_tree_edges.insert(id, heaviest);
+        }
+        Some(MstSensitivity {
+            tree_edges,
+            non_tree_edges,
+        })
+    }
+}
+/// The representative of the set, halving the path on the way.
+fn find(sets: &mut [usize], mut node: usize) -> usize {
+    while sets[node] != node {
+        sets[node] = sets[sets[node]];
+        node = sets[node];
+    }
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+
+    #[test]
+    pub fn video_example_slack() {
+        let graph = example_from_video();
+        let sensitivity = graph.mst_sensitivity().unwrap();
+        let edge = |a: usize, b: usize| graph.connection(NodeID(a), NodeID(b)).unwrap().0;
+        let (a, b, c, d, e, f, g) = (0, 1, 2, 3, 4, 5, 6);
+
+        let tree_weight: u32 = sensitivity
+            .tree_edges
+            .keys()
+            .map(|edge| graph[*edge].weight())
+            .sum();
+        assert_eq!(tree_weight, 25);
+        // A-B (2) is replaced by B-E (3) once it is heavier than 3.
+        assert_eq!(sensitivity.tree_edges[&edge(a, b)], Some(3));
+        // A-C (3) is already tied with B-E.
+        assert_eq!(sensitivity.tree_edges[&edge(a, c)], Some(3));
+        // A-D (3) is replaced by C-D (5).
+        assert_eq!(sensitivity.tree_edges[&edge(a, d)], Some(5));
+        assert_eq!(sensitivity.tree_edges[&edge(d, f)], Some(8));
+        // F-G is the only way to reach G.
+        assert_eq!(sensitivity.tree_edges[&edge(f, g)], None);
+
+        assert_eq!(sensitivity.non_tree_edges.len(), 4);
+        // E-F (8) has to drop below D-F (7) to enter the tree.
+        assert_eq!(sensitivity.non_tree_edges[&edge(e, f)], 7);
+        assert_eq!(sensitivity.non_tree_edges[&edge(c, d)], 3);
+        assert_eq!(sensitivity.non_tree_edges[&edge(b, c)], 3);
+    }
+    #[test]
+    pub fn self_loops_and_disconnected() {
+        let mut graph = example_from_video();
+        graph
+            .connect_nodes_with_weight(NodeID(0), NodeID(0), 1)
+            .unwrap();
+        let sensitivity = graph.mst_sensitivity().unwrap();
+        assert_eq!(sensitivity.tree_edges.len(), 6);
+        assert_eq!(sensitivity.non_tree_edges.len(), 4);
+
+        graph.add_node('H');
+        assert_eq!(graph.mst_sensitivity(), None);
+        assert!(AdjListGraph::<u8>::default()
+            .mst_sensitivity()
+            .is_some_and(|sensitivity| sensitivity.tree_edges.is_empty()));
+    }
+}
@@ -0,0 +1,160 @@
+//! Conversion between a graph of names and a map from every name to its neighbors.
+use crate::collections::HashMap;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+use crate::{adjacency_list::*, GraphError};
+
+use super::AdjListGraph;
+
+/// Every name with its neighbors and the weights of the edges to them.
+pub type NameMap = BTreeMap<String, Vec<(String, u32)>>;
+
+impl<S: AsRef<str>> AdjListGraph<S> {
+    /// Lists every node with its neighbors, sorted by name then weight.
+    ///
+    /// Each edge appears under both of its nodes. A self loop appears once. Nodes with the same name are merged.
+    pub fn to_name_map(&self) -> NameMap {
+        let mut map: NameMap = self
+            .iter_nodes()
+            .map(|(_, name)| (name.as_ref().to_string(), Vec::new()))
+            .collect();
+        for (a, b, weight) in self.unique_edges() {
+            let (a, b) = (self[a].value().as_ref(), self[b].value().as_ref());
+            map.get_mut(a)
+                .expect("Node names were added above")
+                .push((b.to_string(), weight));
+            if a != b {
+                map.get_mut(b)
+                    .expect("Node names were added above")
+                    .push((a.to_string(), weight));
+            }
+        }
+        for neighbors in map.values_mut() {
+            neighbors.sort();
+        }
+        map
+    }
+}
+impl AdjListGraph<String> {
+    /// Builds a graph from the output of [AdjListGraph::to_name_map].
+    ///
+    /// Every entry `a -> (b, weight)` needs a matching `b -> (a, weight)`. Otherwise [GraphError::AsymmetricNameMap] is returned.
+    pub fn from_name_map(map: &NameMap) -> Result<Self, GraphError> {
+        let mut graph = AdjListGraph::with_capacity(map.len(), 0);
+        let ids: HashMap<&str, NodeID> = map
+            .keys()
+            .map(|name| (name.as_str(), graph.add_node(name.clone())))
+            .collect();
+        // Number of entries for each direction of an edge.
+        let mut entries: BTreeMap<(&str, &str, u32), usize> = BTreeMap::new();
+        for (name, neighbors) in map {
+            for (neighbor, weight) in neighbors {
+                *entries
+                    .entry((name.as_str(), neighbor.as_str(), *weight))
+                    .or_default() += 1;
+            }
+        }
+        for (&(a, b, weight), &count) in &entries {
+            let asymmetric = || GraphError::AsymmetricNameMap {
+                from: a.to_string(),
+                to: b.to_string(),
+                weight,
+            };
+            if a != b && entries.get(&(b, a, weight)) != Some(&count) {
+                return Err(asymmetric());
+            }
+            if a > b {
+                // Added with the reverse direction.
+                continue;
+            }
+            let (Some(node_a), Some(node_b)) = (ids.get(a), ids.get(b)) else {
+                return Err(asymmetric());
+            };
+            for _ in 0..count {
+                graph.connect_nodes_with_weight(*node_a, *node_b, weight)?;
+            }
+        }
+        Ok(graph)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use super::NameMap;
+    use crate::{adjacency_list::*, GraphError};
+
+    fn name_map(entries: &[(&str, &[(&str, u32)])]) -> NameMap {
+        entries
+            .iter()
+            .map(|(name, neighbors)| {
+                (
+                    name.to_string(),
+                    neighbors
+                        .iter()
+                        .map(|(neighbor, weight)| (neighbor.to_string(), *weight))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+    #[test]
+    pub fn round_trip() {
+        let mut graph = graph_no_import! {
+            a [value = "A".to_string()];
+            b [value = "B".to_string()];
+            c [value = "C".to_string()];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+            c -- a [weight = 3];
+            c -- c [weight = 4];
+        };
+        graph.add_node("D".to_string());
+        let map = graph.to_name_map();
+        assert_eq!(
+            map,
+            name_map(&[
+                ("A", &[("B", 1), ("C", 3)]),
+                ("B", &[("A", 1), ("C", 2)]),
+                ("C", &[("A", 3), ("B", 2), ("C", 4)]),
+                ("D", &[]),
+            ])
+        );
+        let round_trip = AdjListGraph::from_name_map(&map).unwrap();
+        crate::testing::assert_same_graph(&graph, &round_trip);
+        assert_eq!(round_trip.to_name_map(), map);
+    }
+    #[test]
+    pub fn asymmetric() {
+        let missing_reverse = name_map(&[("A", &[("B", 1)]), ("B", &[])]);
+        assert_eq!(
+            AdjListGraph::from_name_map(&missing_reverse).unwrap_err(),
+            GraphError::AsymmetricNameMap {
+                from: "A".to_string(),
+                to: "B".to_string(),
+                weight: 1
+            }
+        );
+        let different_weight = name_map(&[("A", &[("B", 1)]), ("B", &[("A", 2)])]);
+        assert!(AdjListGraph::from_name_map(&different_weight).is_err());
+
+        let only_reverse = name_map(&[("A", &[]), ("B", &[("A", 1)])]);
+        assert_eq!(
+            AdjListGraph::from_name_map(&only_reverse).unwrap_err(),
+            GraphError::AsymmetricNameMap {
+                from: "B".to_string(),
+                to: "A".to_string(),
+                weight: 1
+            }
+        );
+        let unknown_node = name_map(&[("A", &[("Z", 1)])]);
+        assert_eq!(
+            AdjListGraph::from_name_map(&unknown_node)
+                .unwrap_err()
+                .code(),
+            "asymmetric_name_map"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Parallel versions of some of the algorithms. Requires the `rayon` feature.
+//!
+//! The results are identical to the sequential versions.
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::*;
+
+use crate::adjacency_list::*;
+use crate::prelude::*;
+use crate::GraphError;
+
+use super::AdjListGraph;
+
+const UNVISITED: usize = usize::MAX;
+
+impl<T: Sync> AdjListGraph<T> {
+    /// Parallel version of [AdjListGraph::connected_components].
+    ///
+    /// The edges are processed in parallel with a lock free union-find. A root is always hooked onto the smaller root,
+    /// so the root of every component is its lowest node ID.
+    pub fn connected_components_parallel(&self) -> Vec<Vec<NodeID>> {
+        let parents: Vec<AtomicUsize> = (0..self.nodes.len()).map(AtomicUsize::new).collect();
+        self.edges
+            .par_iter()
+            .enumerate()
+            .filter(|(index, _)| !self.is_edge_empty(*index))
+            .for_each(|(_, edge)| union(&parents, edge.node_a.0, edge.node_b.0));
+
+        let roots: Vec<usize> = (0..self.nodes.len())
+            .into_par_iter()
+            .map(|node| find(&parents, node))
+            .collect();
+        // Roots are the lowest node of the component, so visiting the nodes in order keeps the sequential ordering.
+        let mut component_index = vec![UNVISITED; self.nodes.len()];
+        let mut components: Vec<Vec<NodeID>> = Vec::new();
+        for node in self.node_ids() {
+            let root = roots[node.0];
+            if component_index[root] == UNVISITED {
+                component_index[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[component_index[root]].push(node);
+        }
+        components
+    }
+    /// Parallel version of [AdjListGraph::bfs_distances].
+    ///
+    /// Expands the frontier one level at a time. Each thread collects the next frontier in its own buffer.
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn bfs_distances_parallel(&self, start: NodeID) -> Result<Vec<Option<usize>>, GraphError> {
+        self.check_node_exists(start)?;
+        let distances: Vec<AtomicUsize> = (0..self.nodes.len())
+            .map(|_| AtomicUsize::new(UNVISITED))
+            .collect();
+        distances[start.0].store(0, Ordering::Relaxed);
+        let mut frontier = vec![start];
+        let mut level = 0;
+        while !frontier.is_empty() {
+            level += 1;
+            frontier = frontier
+                .par_iter()
+                .fold(Vec::new, |mut next_frontier, node| {
+                    for (_, next, _) in self.adjacent(*node) {
+                        // Only the thread that claims the node adds it to the next frontier.
+                        if distances[next.0]
+                            .compare_exchange(
+                                UNVISITED,
+                                level,
+                                Ordering::Relaxed,
+                                Ordering::Relaxed,
+                            )
+                            .is_ok()
+                        {
+                            next_frontier.push(next);
+                        }
+                    }
+                    next_frontier
+                })
+                .reduce(Vec::new, |mut a, mut b| {
+                    a.append(&mut b);
+                    a
+                });
+        }
+        Ok(distances
+            .into_iter()
+            .map(|distance| match distance.into_inner() {
+                UNVISITED => None,
+                distance => Some(distance),
+            })
+            .collect())
+    }
+}
+/// Finds the root of the node while halving the path.
+fn find(parents: &[AtomicUsize], mut node: usize) -> usize {
+    loop {
+        let parent = parents[node].load(Ordering::Relaxed);
+        if parent == node {
+            return node;
+        }
+        let grandparent = parents[parent].load(Ordering::Relaxed);
+        // Losing this race is fine. Another thread already moved the node closer to the root.
+        let _ = parents[node].compare_exchange(
+            parent,
+            grandparent,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+        node = grandparent;
+    }
+}
+fn union(parents: &[AtomicUsize], a: usize, b: usize) {
+    loop {
+        let (root_a, root_b) = (find(parents, a), find(parents, b));
+        if root_a == root_b {
+            return;
+        }
+        let (high, low) = if root_a > root_b {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        // Fails if `high` stopped being a root in the meantime.
+        if parents[high]
+            .compare_exchange(high, low, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            return;
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::test_graphs::{example_from_video, random_graph};
+    use crate::adjacency_list::*;
+
+    #[test]
+    pub fn components_match_sequential() {
+        for seed in 0..8 {
+            // Sparse enough to leave several components.
+            let graph = random_graph(2_000, 1_500, seed);
+            assert_eq!(
+                graph.connected_components_parallel(),
+                graph.connected_components(),
+                "seed {seed}"
+            );
+        }
+    }
+    #[test]
+    pub fn bfs_distances_match_sequential() {
+        for seed in 0..8 {
+            let graph = random_graph(2_000, 4_000, seed);
+            let start = graph.node_ids().next().unwrap();
+            assert_eq!(
+                graph.bfs_distances_parallel(start).unwrap(),
+                graph.bfs_distances(start).unwrap(),
+                "seed {seed}"
+            );
+        }
+    }
+    #[test]
+    pub fn bfs_distances_missing_start() {
+        let mut graph = example_from_video();
+        graph.remove_node(NodeID(3));
+        assert!(graph.bfs_distances_parallel(NodeID(3)).is_err());
+        assert!(graph.bfs_distances_parallel(NodeID(30)).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::collections::HashMap;
+use crate::prelude::*;
+use core::mem;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::adjacency_list::*;
+use crate::utils::logging::trace;
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// The total weight of the live edges with exactly one node in `side_a`.
+    ///
+    /// Self loops are never cut. Dead or unknown IDs in `side_a` are ignored.
+    pub fn cut_weight(&self, side_a: &[NodeID]) -> u64 {
+        let mut in_a = vec![false; self.nodes.len()];
+        for node in side_a {
+            if let Some(in_a) = in_a.get_mut(node.0) {
+                *in_a = true;
+            }
+        }
+        self.edges()
+            .filter(|edge| {
+                let (a, b) = edge.endpoints();
+                in_a[a.0] != in_a[b.0]
+            })
+            .map(|edge| edge.weight() as u64)
+            .sum()
+    }
+    /// The size of one side of a partition, to check how balanced it is.
+    ///
+    /// Counts the live nodes in `side`, or sums their [AdjListGraph::node_weight] if `use_node_weights` is set.
+    /// Dead, unknown and repeated IDs are ignored.
+    pub fn side_weight(&self, side: &[NodeID], use_node_weights: bool) -> u64 {
+        let mut counted = vec![false; self.nodes.len()];
+        side.iter()
+            .filter(|node| {
+                self.does_node_id_exist(**node) && !mem::replace(&mut counted[node.0], true)
+            })
+            .map(|node| {
+                if use_node_weights {
+                    self.node_weight_inner(node.0) as u64
+                } else {
+                    1
+                }
+            })
+            .sum()
+    }
+    /// Splits the live nodes into two halves with the Kernighan–Lin heuristic, keeping the weight of the edges between them low.
+    ///
+    /// Starts from a random split chosen by `seed` and runs up to `iterations` passes of swaps, stopping early once a pass does not improve the cut.
+    /// The same seed always gives the same result. With an odd number of nodes the halves differ by one,
+    /// and which node is the extra one is chosen by the swaps like any other.
+    ///
+    /// Returns both halves sorted by ID and the [AdjListGraph::cut_weight] between them.
+    /// The halves are balanced by node count. Use [AdjListGraph::side_weight] to compare their node weights.
+    ///
+    /// A pass is usually O(V² log V), and O(V³) in the worst case.
+    pub fn partition_kernighan_lin(
+        &self,
+        seed: u64,
+        iterations: usize,
+    ) -> (Vec<NodeID>, Vec<NodeID>, u64) {
+        let mut nodes: Vec<NodeID> = self.node_ids().collect();
+        nodes.shuffle(&mut StdRng::seed_from_u64(seed));
+        let real = nodes.len();
+        // An odd count gets a node without edges, so it can be swapped to either side.
+        let len = real + real % 2;
+        let mut index_of = vec![usize::MAX; self.nodes.len()];
+        for (index, node) in nodes.iter().enumerate() {
+            index_of[node.0] = index;
+        }
+        let mut costs: Vec<HashMap<usize, i64>> = vec![HashMap::new(); len];
+        for edge in self.edges() {
+            let (a, b) = edge.endpoints();
+            if a == b {
+                continue;
+            }
+            let (a, b) = (index_of[a.0], index_of[b.0]);
+            *costs[a].entry(b).or_default() += edge.weight() as i64;
+            *costs[b].entry(a).or_default() += edge.weight() as i64;
+        }
+        // True for side A. The first half of the shuffled nodes starts in A.
+        let mut in_a: Vec<bool> = (0..len).map(|index| index < len / 2).collect();
+        for pass in 0..iterations {
+            let gain = kernighan_lin_pass(&costs, &mut in_a);
+            trace!(pass, gain, "Kernighan-Lin pass");
+            if gain <= 0 {
+                break;
+            }
+        }
+        let (mut side_a, mut side_b): (Vec<NodeID>, Vec<NodeID>) =
+            nodes.iter().partition(|node| in_a[index_of[node.0]]);
+        side_a.sort_unstable();
+        side_b.sort_unstable();
+        let cut = self.cut_weight(&side_a);
+        (side_a, side_b, cut)
+    }
+}
+/// Swaps the best prefix of a sequence of greedy pair swaps. Returns the reduction of the cut.
+fn kernighan_lin_pass(costs: &[HashMap<usize, i64>], in_a: &mut [bool]) -> i64 {
+    let len = in_a.len();
+    let cost = |a: usize, b: usize| costs[a].get(&b).copied().unwrap_or(0);
+    // External minus internal cost.
+    let mut difference: Vec<i64> = (0..len)
+        .map(|node| {
+            costs[node]
+                .iter()
+                .map(|(other, weight)| {
+                    if in_a[*other] == in_a[node] {
+                        -weight
+                    } else {
+                        *weight
+                    }
+                })
+                .sum()
+        })
+        .collect();
+    let mut locked = vec![false; len];
+    let mut swaps = Vec::with_capacity(len / 2);
+    let mut total = 0;
+    let (mut best_total, mut best_prefix) = (0, 0);
+    for _ in 0..len / 2 {
+        let by_difference = |side: bool| {
+            let mut nodes: Vec<usize> = (0..len)
+                .filter(|node| !locked[*node] && in_a[*node] == side)
+                .collect();
+            nodes.sort_by_key(|node| (core::cmp::Reverse(difference[*node]), *node));
+            nodes
+        };
+        let (side_a, side_b) = (by_difference(true), by_difference(false));
+        let Some(first_b) = side_b.first() else {
+            break;
+        };
+        let mut best: Option<(i64, usize, usize)> = None;
+        for a in &side_a {
+            if best.is_some_and(|(gain, _, _)| difference[*a] + difference[*first_b] <= gain) {
+                break;
+            }
+            for b in &side_b {
+                let upper_bound = difference[*a] + difference[*b];
+                if best.is_some_and(|(gain, _, _)| upper_bound <= gain) {
+                    // The costs are never negative, so the rest can not beat it.
+                    break;
+                }
+                let gain = upper_bound - 2 * cost(*a, *b);
+                if best.is_none_or(|(best_gain, _, _)| gain > best_gain) {
+                    best = Some((gain, *a, *b));
+                }
+            }
+        }
+        let Some((gain, a, b)) = best else {
+            break;
+        };
+        locked[a] = true;
+        locked[b] = true;
+        for (moved, moved_in_a) in [(a, true), (b, false)] {
+            for (other, weight) in &costs[moved] {
+                if locked[*other] {
+                    continue;
+                }
+                if in_a[*other] == moved_in_a {
+                    difference[*other] += 2 * weight;
+                } else {
+                    difference[*other] -= 2 * weight;
+                }
+            }
+        }
+        swaps.push((a, b));
+        total += gain;
+        if total > best_total {
+            best_total = total;
+            best_prefix = swaps.len();
+        }
+    }
+    for (a, b) in &swaps[..best_prefix] {
+        in_a[*a] = false;
+        in_a[*b] = true;
+    }
+    best_total
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::{test_graphs::random_graph, *};
+
+    /// Two cliques of `size` nodes with heavy edges, joined by one edge of weight 1.
+    fn two_cliques(size: usize) -> (AdjListGraph<usize>, Vec<NodeID>, Vec<NodeID>) {
+        let mut graph = AdjListGraph::default();
+        let left = graph.add_nodes_from_iterator(0..size);
+        let right = graph.add_nodes_from_iterator(size..size * 2);
+        for clique in [&left, &right] {
+            for (index, a) in clique.iter().enumerate() {
+                for b in &clique[index + 1..] {
+                    graph.connect_nodes_with_weight(*a, *b, 10).unwrap();
+                }
+            }
+        }
+        graph
+            .connect_nodes_with_weight(left[0], right[0], 1)
+            .unwrap();
+        (graph, left, right)
+    }
+    #[test]
+    pub fn splits_two_cliques() {
+        let (graph, left, right) = two_cliques(6);
+        for seed in 0..10 {
+            let (a, b, cut) = graph.partition_kernighan_lin(seed, 10);
+            assert_eq!(cut, 1, "seed {seed}");
+            assert!(
+                (a == left && b == right) || (a == right && b == left),
+                "seed {seed}"
+            );
+        }
+        assert_eq!(graph.cut_weight(&left), 1);
+        assert_eq!(graph.cut_weight(&[]), 0);
+    }
+    #[test]
+    pub fn side_weights() {
+        let (mut graph, left, right) = two_cliques(3);
+        graph.set_node_weight(left[0], 10).unwrap();
+        graph.remove_node(right[2]);
+        let side = [left[0], left[1], left[0], right[2], NodeID(50)];
+        assert_eq!(graph.side_weight(&side, false), 2);
+        assert_eq!(graph.side_weight(&side, true), 11);
+        assert_eq!(graph.side_weight(&right, true), 2);
+    }
+    #[test]
+    pub fn odd_counts_and_determinism() {
+        let (mut graph, left, _) = two_cliques(4);
+        // A leaf hanging off the left clique belongs with it.
+        let leaf = graph.add_node(8);
+        graph.connect_nodes_with_weight(leaf, left[1], 5).unwrap();
+        let (a, b, cut) = graph.partition_kernighan_lin(3, 10);
+        assert_eq!(a.len().abs_diff(b.len()), 1);
+        assert_eq!(cut, 1);
+        assert!(a.contains(&leaf) == a.contains(&left[1]));
+
+        let graph = random_graph(60, 150, 2);
+        let result = graph.partition_kernighan_lin(7, 5);
+        assert_eq!(result, graph.partition_kernighan_lin(7, 5));
+        let (a, b, cut) = result;
+        assert_eq!(a.len() + b.len(), graph.number_of_nodes());
+        assert!(a.len().abs_diff(b.len()) <= 1);
+        assert_eq!(cut, graph.cut_weight(&a));
+        assert_eq!(cut, graph.cut_weight(&b));
+        // A pass is only kept if it lowers the cut, so it is never worse than the random split.
+        assert!(cut <= graph.partition_kernighan_lin(7, 0).2);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::prelude::*;
+use core::fmt::{self, Display};
+
+use serde::{ser::SerializeStruct, Serialize};
+
+use crate::adjacency_list::*;
+
+use super::AdjListGraph;
+/// A path through a graph. Borrows the graph so the values and edges along it can be read without searching again.
+///
+/// Created by [AdjListGraph::path], [AdjListGraph::dfs_path] and [AdjListGraph::dijkstra_path].
+#[derive(Debug, Clone)]
+pub struct Path<'g, T> {
+    graph: &'g AdjListGraph<T>,
+    nodes: Vec<NodeID>,
+    edges: Vec<EdgeID>,
+}
+impl<'g, T> Path<'g, T> {
+    pub fn nodes(&self) -> &[NodeID] {
+        &self.nodes
+    }
+    /// The edges between the consecutive nodes. One shorter than [Path::nodes].
+    pub fn edges(&self) -> &[EdgeID] {
+        &self.edges
+    }
+    pub fn values(&self) -> impl Iterator<Item = &'g T> + '_ {
+        self.nodes.iter().map(|node| self.graph[node].value())
+    }
+    pub fn total_weight(&self) -> u64 {
+        self.edges
+            .iter()
+            .map(|edge| self.graph[edge].weight() as u64)
+            .sum()
+    }
+    /// The number of nodes in the path.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+    pub fn contains(&self, node: NodeID) -> bool {
+        self.nodes.contains(&node)
+    }
+    pub fn into_nodes(self) -> Vec<NodeID> {
+        self.nodes
+    }
+}
+/// Serialized as the node IDs, the edge IDs and the total weight. The values are not included.
+impl<T> Serialize for Path<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Path", 3)?;
+        state.serialize_field("nodes", &self.nodes)?;
+        state.serialize_field("edges", &self.edges)?;
+        state.serialize_field("total_weight", &self.total_weight())?;
+        state.end()
+    }
+}
+impl<T: Display> Display for Path<'_, T> {
+    /// Formats the path as `A -> B -> C`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, value) in self.values().enumerate() {
+            if index != 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{value}")?;
+        }
+        Ok(())
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Wraps the nodes of a path. Consecutive nodes must be connected.
+    ///
+    /// If there is more than one edge between two nodes, the lightest is used.
+    pub fn path(&self, nodes: Vec<NodeID>) -> Path<'_, T> {
+        let edges = nodes
+            .windows(2)
+            .filter_map(|pair| {
+                let connection = self.connection(pair[0], pair[1]);
+                debug_assert!(
+                    connection.is_some(),
+                    "{:?} and {:?} are next to each other in the path but are not connected",
+                    pair[0],
+                    pair[1]
+                );
+                connection.map(|(edge, _)| edge)
+            })
+            .collect();
+        Path {
+            graph: self,
+            nodes,
+            edges,
+        }
+    }
+    /// [AdjListGraph::dfs] returning a [Path].
+    pub fn dfs_path<F>(&self, f: F) -> Option<Path<'_, T>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.dfs(f).map(|nodes| self.path(nodes))
+    }
+    /// The cheapest path between two nodes. Returns None if either node does not exist or there is no path.
+    pub fn dijkstra_path(&self, from: NodeID, to: NodeID) -> Option<Path<'_, T>> {
+        self.dijkstra(from, to).map(|(nodes, _)| self.path(nodes))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::*;
+
+    #[test]
+    pub fn weighted_path() {
+        let graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+
+            a -- b [weight = 2];
+            b -- c [weight = 3];
+            a -- c [weight = 10];
+            c -- d [weight = 4];
+        };
+        let path = graph.dijkstra_path(NodeID(0), NodeID(3)).unwrap();
+        assert_eq!(path.nodes(), &[NodeID(0), NodeID(1), NodeID(2), NodeID(3)]);
+        assert_eq!(path.edges(), &[EdgeID(0), EdgeID(1), EdgeID(3)]);
+        assert_eq!(path.values().collect::<String>(), "ABCD");
+        assert_eq!(path.total_weight(), 9);
+        assert_eq!(path.len(), 4);
+        assert!(path.contains(NodeID(1)));
+        assert!(!path.contains(NodeID(4)));
+        assert_eq!(path.to_string(), "A -> B -> C -> D");
+
+        let path = graph.dfs_path(|value| *value == 'A').unwrap();
+        assert_eq!(path.to_string(), "A");
+        assert_eq!(path.total_weight(), 0);
+        assert!(path.edges().is_empty());
+    }
+    #[test]
+    #[should_panic(expected = "are not connected")]
+    #[cfg(debug_assertions)]
+    pub fn disconnected_path() {
+        let mut graph = AdjListGraph::default();
+        let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+        graph.path(vec![a, b]);
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens when a connection breaks a rule of [GraphPolicy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PolicyAction {
+    Allow,
+    /// The connection returns an error.
+    Reject,
+}
+/// The kinds of edges a graph accepts. Checked by [super::AdjListGraph::connect_nodes_with_weight].
+///
+/// The default allows self loops and rejects parallel edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GraphPolicy {
+    /// Edges that connect a node to itself.
+    pub self_loops: PolicyAction,
+    /// More than one edge between the same pair of nodes.
+    pub parallel_edges: PolicyAction,
+}
+impl Default for GraphPolicy {
+    fn default() -> Self {
+        Self {
+            self_loops: PolicyAction::Allow,
+            parallel_edges: PolicyAction::Reject,
+        }
+    }
+}
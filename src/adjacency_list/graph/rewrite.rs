@@ -0,0 +1,279 @@
+use crate::collections::HashMap;
+use crate::prelude::*;
+
+use crate::{adjacency_list::*, GraphError};
+
+use super::AdjListGraph;
+
+/// Which node of the template receives the edges of the replaced node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RewireTo {
+    #[default]
+    Entry,
+    Exit,
+}
+
+impl<T> AdjListGraph<T> {
+    /// Replaces every node matching `predicate` with a copy of `template`.
+    ///
+    /// The edges of the replaced node are reconnected to the copy of `entry` or `exit` depending on `rewire`. A self-loop on the replaced node becomes a self-loop on that node.
+    ///
+    /// Nodes that are not replaced keep their IDs. Nodes inside the copied templates are never matched.
+    ///
+    /// Returns an error if `entry` or `exit` do not exist in the template.
+    pub fn expand_nodes<P>(
+        &mut self,
+        predicate: P,
+        template: &AdjListGraph<T>,
+        entry: NodeID,
+        exit: NodeID,
+        rewire: RewireTo,
+    ) -> Result<(), GraphError>
+    where
+        P: Fn(&T) -> bool,
+        T: Clone,
+    {
+        template.check_node_exists(entry)?;
+        template.check_node_exists(exit)?;
+        let matching: Vec<NodeID> = self
+            .iter_nodes()
+            .filter(|(_, value)| predicate(value))
+            .map(|(id, _)| id)
+            .collect();
+        for node in matching {
+            let copied = self.copy_template(template);
+            let target = match rewire {
+                RewireTo::Entry => copied[&entry],
+                RewireTo::Exit => copied[&exit],
+            };
+            let connections: Vec<(NodeID, u32)> = self
+                .adjacent(node)
+                .map(|(_, other, weight)| (other, weight))
+                .collect();
+            self.remove_node(node);
+            for (other, weight) in connections {
+                let other = if other == node { target } else { other };
+                // Two replaced edges can end up between the same nodes. The first one wins.
+                let _ = self.connect_nodes_with_weight(target, other, weight);
+            }
+        }
+        Ok(())
+    }
+    /// Replaces the edge `a -- b` with `a -- m -- b`, where `m` is a new node holding `value`.
+    ///
+    /// `weight_split` gets the weight of the edge and returns the weights of `a -- m` and `m -- b`. Pass [halve_weight] to split it evenly.
+    /// The ID of the edge is freed and is reused like any removed edge.
+    ///
+    /// Subdividing a self loop connects `m` to the node twice, so it returns [GraphError::NodesAlreadyConnected]
+    /// unless the [GraphPolicy] allows parallel edges. Returns [GraphError::EdgeDoesNotExist] if the edge is not live.
+    ///
+    /// Returns `m` and the edges `a -- m` and `m -- b`.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::{halve_weight, AdjListGraph};
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b] = graph.add_nodes_from_sized_array(["A", "B"]);
+    /// let edge = graph.connect_nodes_with_weight(a, b, 5).unwrap();
+    ///
+    /// let (middle, first, second) = graph.subdivide_edge(edge, "M", halve_weight).unwrap();
+    /// assert_eq!(graph[first].weight(), 3);
+    /// assert_eq!(graph[second].weight(), 2);
+    /// assert!(graph.is_node_connected_to_node(a, middle));
+    /// assert!(!graph.is_node_connected_to_node(a, b));
+    /// ```
+    pub fn subdivide_edge(
+        &mut self,
+        edge: EdgeID,
+        value: T,
+        weight_split: impl FnOnce(u32) -> (u32, u32),
+    ) -> Result<(NodeID, EdgeID, EdgeID), GraphError> {
+        let (a, b, weight) = self
+            .get_edge(edge)
+            .map(|edge| (edge.node_a, edge.node_b, edge.weight()))
+            .ok_or(GraphError::EdgeDoesNotExist { edge })?;
+        if a == b && self.policy.parallel_edges == PolicyAction::Reject {
+            return Err(GraphError::NodesAlreadyConnected {
+                node_a: a,
+                node_b: b,
+                edge,
+            });
+        }
+        let (weight_a, weight_b) = weight_split(weight);
+        let middle = self.add_node(value);
+        // The new edges are connected first, so they do not take the slot of the edge they replace.
+        let first = self
+            .connect_nodes_with_weight(a, middle, weight_a)
+            .expect("The new node has no edges yet");
+        let second = self
+            .connect_nodes_with_weight(middle, b, weight_b)
+            .expect("Parallel edges are allowed or the edge was not a self loop");
+        self.remove_edge(edge);
+        Ok((middle, first, second))
+    }
+    /// Copies every live node and edge of the template into this graph.
+    fn copy_template(&mut self, template: &AdjListGraph<T>) -> HashMap<NodeID, NodeID>
+    where
+        T: Clone,
+    {
+        let mut copied = HashMap::with_capacity(template.number_of_nodes());
+        for (id, value) in template {
+            copied.insert(id, self.add_node(value.clone()));
+        }
+        for (index, edge) in template.edges.iter().enumerate() {
+            if template.is_edge_empty(index) {
+                continue;
+            }
+            let _ = self.connect_nodes_with_weight(
+                copied[&edge.node_a],
+                copied[&edge.node_b],
+                edge.weight(),
+            );
+        }
+        copied
+    }
+}
+/// Splits the weight in two halves for [AdjListGraph::subdivide_edge]. An odd remainder goes to the first half.
+pub fn halve_weight(weight: u32) -> (u32, u32) {
+    (weight - weight / 2, weight / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::*;
+    use crate::GraphError;
+
+    fn template() -> AdjListGraph<&'static str> {
+        graph_no_import! {
+            entry [value = "entry"];
+            middle [value = "middle"];
+            exit [value = "exit"];
+
+            entry -- middle [weight = 1];
+            middle -- exit [weight = 1];
+        }
+    }
+    #[test]
+    pub fn expand_path_node() {
+        let mut graph = graph_no_import! {
+            a [value = "a"];
+            b [value = "macro"];
+            c [value = "c"];
+
+            a -- b [weight = 5];
+            b -- c [weight = 6];
+        };
+        let (a, c) = (NodeID(0), NodeID(2));
+        graph
+            .expand_nodes(
+                |value| *value == "macro",
+                &template(),
+                NodeID(0),
+                NodeID(2),
+                RewireTo::Entry,
+            )
+            .unwrap();
+        assert_eq!(graph.number_of_nodes(), 5);
+        assert_eq!(graph.number_of_edges(), 4);
+        assert_eq!(graph.validate(), Ok(()));
+        assert_eq!(*graph[a].value(), "a");
+        assert_eq!(*graph[c].value(), "c");
+
+        let entry = graph.find_node_with_that_equals(&"entry").unwrap();
+        assert_eq!(
+            graph.connection(a, entry).map(|(_, weight)| weight),
+            Some(5)
+        );
+        assert_eq!(
+            graph.connection(c, entry).map(|(_, weight)| weight),
+            Some(6)
+        );
+    }
+    #[test]
+    pub fn expand_to_exit() {
+        let mut graph = graph_no_import! {
+            a [value = "a"];
+            b [value = "macro"];
+            c [value = "macro"];
+
+            a -- b;
+            b -- c;
+        };
+        graph
+            .expand_nodes(
+                |value| *value == "macro",
+                &template(),
+                NodeID(0),
+                NodeID(2),
+                RewireTo::Exit,
+            )
+            .unwrap();
+        assert_eq!(graph.number_of_nodes(), 7);
+        // Two copies of the template edges, plus a to the first exit and the two exits to each other.
+        assert_eq!(graph.number_of_edges(), 6);
+        assert_eq!(graph.validate(), Ok(()));
+        assert!(graph.find_node_with_that_equals(&"macro").is_none());
+        assert!(graph
+            .expand_nodes(|_| true, &template(), NodeID(5), NodeID(2), RewireTo::Exit)
+            .is_err());
+    }
+    #[test]
+    pub fn subdivide_weighted_edge() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["a", "b", "c"]);
+        let edge = graph.connect_nodes_with_weight(a, b, 7).unwrap();
+        graph.connect_nodes_with_weight(b, c, 1).unwrap();
+
+        let (middle, first, second) = graph.subdivide_edge(edge, "m", halve_weight).unwrap();
+        assert_eq!(graph[middle].value(), &"m");
+        assert_eq!(graph.connection(a, middle), Some((first, 4)));
+        assert_eq!(graph.connection(middle, b), Some((second, 3)));
+        assert_eq!(graph.connection(a, b), None);
+        assert_eq!(graph.connected_nodes(middle).len(), 2);
+        assert!(!graph.is_edge_alive(edge));
+        assert_eq!(graph.number_of_edges(), 3);
+        assert_eq!(graph.validate(), Ok(()));
+
+        // The next connect reuses the freed slot.
+        assert_eq!(graph.connect_nodes(a, c).unwrap(), edge);
+
+        let (_, first, second) = graph
+            .subdivide_edge(second, "n", |weight| (weight, 10))
+            .unwrap();
+        assert_eq!((graph[first].weight(), graph[second].weight()), (3, 10));
+        assert_eq!(
+            graph.subdivide_edge(EdgeID(40), "x", halve_weight),
+            Err(GraphError::EdgeDoesNotExist { edge: EdgeID(40) })
+        );
+    }
+    #[test]
+    pub fn subdivide_self_loop() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node("a");
+        let edge = graph.connect_nodes_with_weight(a, a, 2).unwrap();
+        assert_eq!(
+            graph.subdivide_edge(edge, "m", halve_weight),
+            Err(GraphError::NodesAlreadyConnected {
+                node_a: a,
+                node_b: a,
+                edge
+            })
+        );
+        assert_eq!(graph.number_of_nodes(), 1);
+        assert!(graph.is_edge_alive(edge));
+
+        let mut graph = AdjListGraph::with_policy(GraphPolicy {
+            parallel_edges: PolicyAction::Allow,
+            ..Default::default()
+        });
+        let a = graph.add_node("a");
+        let edge = graph.connect_nodes_with_weight(a, a, 2).unwrap();
+        let (middle, first, second) = graph.subdivide_edge(edge, "m", halve_weight).unwrap();
+        assert_eq!(graph[first].nodes(), (a, middle));
+        assert_eq!(graph[second].nodes(), (middle, a));
+        assert!(!graph.is_node_connected_to_itself(a));
+        assert!(graph.has_parallel_edges());
+    }
+}
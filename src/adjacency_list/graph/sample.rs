@@ -0,0 +1,208 @@
+use crate::collections::HashMap;
+use crate::prelude::*;
+use core::mem;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::adjacency_list::*;
+use crate::utils::logging::trace;
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// Picks `n` distinct live nodes uniformly at random. The same seed always picks the same nodes in the same order.
+    ///
+    /// If `n` is more than the number of live nodes, every live node is returned in a random order.
+    pub fn sample_nodes(&self, n: usize, seed: u64) -> Vec<NodeID> {
+        partial_shuffle(self.node_ids().collect(), n, seed)
+    }
+    /// Picks `n` distinct live edges uniformly at random. The same seed always picks the same edges in the same order.
+    ///
+    /// If `n` is more than the number of live edges, every live edge is returned in a random order.
+    pub fn sample_edges(&self, n: usize, seed: u64) -> Vec<EdgeID> {
+        partial_shuffle(self.edge_ids().collect(), n, seed)
+    }
+    /// Picks a live edge with a probability proportional to its weight, as used by Karger's minimum cut.
+    ///
+    /// Edges with a weight of 0 are never picked. `None` if there are no edges with a weight.
+    pub fn sample_edge_weighted(&self, seed: u64) -> Option<EdgeID> {
+        let total: u64 = self.weights().map(|(_, weight)| weight as u64).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut target = StdRng::seed_from_u64(seed).gen_range(0..total);
+        for (edge, weight) in self.weights() {
+            let weight = weight as u64;
+            if target < weight {
+                return Some(edge);
+            }
+            target -= weight;
+        }
+        unreachable!("target is less than the total weight")
+    }
+    /// A copy of the graph after `swaps` random double edge swaps. Every node keeps its degree.
+    ///
+    /// A swap picks two edges `(a, b)` and `(c, d)` and rewires them to `(a, d)` and `(c, b)`.
+    /// Swaps that would create a self loop or connect nodes that are already connected are skipped, and self loops are never moved.
+    /// Each edge keeps its ID and weight. Gives up after `100 * swaps` attempts, so a graph with few possible swaps gets fewer.
+    ///
+    /// The same seed always gives the same graph. Useful as a null model that only keeps the degree sequence.
+    pub fn rewire_preserving_degrees(&self, swaps: usize, seed: u64) -> AdjListGraph<T>
+    where
+        T: Clone,
+    {
+        let pair = |a: NodeID, b: NodeID| (a.min(b), a.max(b));
+        let mut edges: Vec<(EdgeID, NodeID, NodeID)> = self
+            .edges()
+            .filter(|edge| !edge.is_self_loop())
+            .map(|edge| {
+                let (a, b) = edge.endpoints();
+                (edge.id(), a, b)
+            })
+            .collect();
+        // Counted, so a graph that allows parallel edges does not lose track of the second one.
+        let mut connected: HashMap<(NodeID, NodeID), usize> = HashMap::new();
+        for (_, a, b) in &edges {
+            *connected.entry(pair(*a, *b)).or_default() += 1;
+        }
+        let mut random = StdRng::seed_from_u64(seed);
+        let (mut done, mut attempts) = (0, 0);
+        while done < swaps && attempts < swaps.saturating_mul(100) && edges.len() >= 2 {
+            attempts += 1;
+            let first = random.gen_range(0..edges.len());
+            let second = random.gen_range(0..edges.len());
+            if first == second {
+                continue;
+            }
+            let (_, a, b) = edges[first];
+            let (_, mut c, mut d) = edges[second];
+            // Either end of the second edge can be swapped.
+            if random.gen_bool(0.5) {
+                mem::swap(&mut c, &mut d);
+            }
+            let is_connected = |a, b| connected.get(&pair(a, b)).is_some_and(|count| *count > 0);
+            if a == d || c == b || is_connected(a, d) || is_connected(c, b) {
+                continue;
+            }
+            for removed in [pair(a, b), pair(c, d)] {
+                *connected.get_mut(&removed).expect("Edges are counted") -= 1;
+            }
+            for added in [pair(a, d), pair(c, b)] {
+                *connected.entry(added).or_default() += 1;
+            }
+            edges[first] = (edges[first].0, a, d);
+            edges[second] = (edges[second].0, c, b);
+            done += 1;
+        }
+        trace!(done, attempts, "Rewired edges");
+        let mut rewired = self.clone();
+        for (id, a, b) in edges {
+            let edge = &mut rewired.edges[id.0];
+            let (old_a, old_b) = (edge.node_a, edge.node_b);
+            if (old_a, old_b) == (a, b) {
+                continue;
+            }
+            edge.node_a = a;
+            edge.node_b = b;
+            rewired.nodes[old_a.0].edges.remove(&id);
+            rewired.nodes[old_b.0].edges.remove(&id);
+            rewired.nodes[a.0].edges.insert(id);
+            rewired.nodes[b.0].edges.insert(id);
+        }
+        // The copy shares the cached analytics of this graph until it is marked as changed.
+        rewired.touch();
+        rewired
+    }
+}
+/// Fisher-Yates shuffle of the first `n` values. The rest are dropped.
+fn partial_shuffle<I>(mut values: Vec<I>, n: usize, seed: u64) -> Vec<I> {
+    let n = n.min(values.len());
+    let mut random = StdRng::seed_from_u64(seed);
+    for index in 0..n {
+        let swap = random.gen_range(index..values.len());
+        values.swap(index, swap);
+    }
+    values.truncate(n);
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::{test_graphs::random_graph, *};
+    use crate::collections::HashSet;
+
+    #[test]
+    pub fn samples_are_reproducible() {
+        let graph = random_graph(200, 400, 3);
+        let nodes = graph.sample_nodes(20, 42);
+        assert_eq!(nodes, graph.sample_nodes(20, 42));
+        assert_ne!(nodes, graph.sample_nodes(20, 43));
+        assert_eq!(nodes.iter().collect::<HashSet<_>>().len(), 20);
+        assert!(nodes.iter().all(|node| graph.does_node_id_exist(*node)));
+
+        let edges = graph.sample_edges(20, 42);
+        assert_eq!(edges, graph.sample_edges(20, 42));
+        assert_eq!(edges.iter().collect::<HashSet<_>>().len(), 20);
+        assert!(edges.iter().all(|edge| graph.does_edge_id_exist(*edge)));
+    }
+    #[test]
+    pub fn oversized_samples_are_truncated() {
+        let graph = random_graph(50, 30, 1);
+        let mut nodes = graph.sample_nodes(1_000, 7);
+        nodes.sort_unstable();
+        assert_eq!(nodes, graph.live_node_ids());
+        assert_eq!(graph.sample_edges(1_000, 7).len(), graph.number_of_edges());
+        assert!(AdjListGraph::<u32>::default().sample_nodes(3, 7).is_empty());
+    }
+    #[test]
+    pub fn weighted_edge_sampling() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+        graph.connect_nodes_with_weight(a, b, 1).unwrap();
+        let heavy = graph.connect_nodes_with_weight(b, c, 1000).unwrap();
+        let zero = graph.connect_nodes(a, c).unwrap();
+
+        let picks: Vec<EdgeID> = (0..10_000)
+            .map(|seed| graph.sample_edge_weighted(seed).unwrap())
+            .collect();
+        let heavy_picks = picks.iter().filter(|edge| **edge == heavy).count();
+        assert!(heavy_picks > 9_950, "{heavy_picks}");
+        assert!(!picks.contains(&zero));
+        assert_eq!(graph.sample_edge_weighted(5), graph.sample_edge_weighted(5));
+
+        graph.remove_edge(heavy);
+        graph.remove_edge(EdgeID(0));
+        assert_eq!(graph.sample_edge_weighted(5), None);
+    }
+    #[test]
+    pub fn rewiring_keeps_degrees() {
+        let mut graph = random_graph(100, 300, 8);
+        let first = graph.node_ids().next().unwrap();
+        graph.connect_nodes_with_weight(first, first, 5).unwrap();
+        let degrees = |graph: &AdjListGraph<usize>| -> Vec<(NodeID, usize)> {
+            graph
+                .node_ids()
+                .map(|node| (node, graph[node].edges.len()))
+                .collect()
+        };
+        let weights = |graph: &AdjListGraph<usize>| -> Vec<u32> {
+            let mut weights: Vec<u32> = graph.weights().map(|(_, weight)| weight).collect();
+            weights.sort_unstable();
+            weights
+        };
+
+        let rewired = graph.rewire_preserving_degrees(500, 3);
+        assert_eq!(degrees(&rewired), degrees(&graph));
+        assert_eq!(rewired.number_of_edges(), graph.number_of_edges());
+        assert_eq!(weights(&rewired), weights(&graph));
+        assert_eq!(rewired.validate(), Ok(()));
+        assert!(!rewired.has_parallel_edges());
+        assert_ne!(rewired, graph);
+        let self_loops =
+            |graph: &AdjListGraph<usize>| graph.edges().filter(|edge| edge.is_self_loop()).count();
+        assert_eq!(self_loops(&rewired), self_loops(&graph));
+        assert_eq!(rewired, graph.rewire_preserving_degrees(500, 3));
+        assert_ne!(rewired, graph.rewire_preserving_degrees(500, 4));
+
+        assert_eq!(graph.rewire_preserving_degrees(0, 3), graph);
+    }
+}
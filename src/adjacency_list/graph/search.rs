@@ -1,72 +1,285 @@
-use tracing::trace;
+use crate::prelude::*;
+use alloc::collections::{BinaryHeap, VecDeque};
+use core::{cmp::Reverse, mem};
 
-use crate::adjacency_list::*;
+use crate::{adjacency_list::*, algo, GraphError};
 
 use super::AdjListGraph;
 impl<T> AdjListGraph<T> {
-    /// Depth First Search
+    /// Depth First Search from the first live node. See [AdjListGraph::dfs_from]
     pub fn dfs<F>(&self, f: F) -> Option<Vec<NodeID>>
     where
         F: Fn(&T) -> bool,
     {
-        let mut visited = vec![false; self.nodes.len()];
-        let mut path = vec![];
-        if self.dfs_inner(0, &mut visited, &mut path, &f) {
-            Some(path)
-        } else {
-            None
-        }
+        self.dfs_from(self.first_live_node()?, f)
+    }
+    /// Depth First Search from `start`. Only the connected component of `start` is searched.
+    ///
+    /// Returns the path to the first node whose value matches `f`. Returns None if there is no such node or `start` does not exist. See [algo::dfs]
+    pub fn dfs_from<F>(&self, start: NodeID, f: F) -> Option<Vec<NodeID>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        algo::dfs(self, start, |node| f(self[node].value()))
+    }
+    /// Breadth First Search from the first live node. See [AdjListGraph::bfs_from]
+    pub fn bfs_find<F>(&self, f: F) -> Option<Vec<NodeID>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.bfs_from(self.first_live_node()?, f)
     }
-    fn dfs_inner<F>(
+    /// Breadth First Search from `start`. Only the connected component of `start` is searched.
+    ///
+    /// Returns the path to the closest node whose value matches `f`, counted in edges.
+    /// Returns None if there is no such node or `start` does not exist. See [algo::bfs_find]
+    pub fn bfs_from<F>(&self, start: NodeID, f: F) -> Option<Vec<NodeID>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        algo::bfs_find(self, start, |node| f(self[node].value()))
+    }
+    /// Depth First Search from `start` that explores the edges of every node in the order of `key`.
+    ///
+    /// Ties are explored in the order of their edge IDs, so the search is deterministic.
+    /// Use the weight as the key to explore the lightest edges first.
+    ///
+    /// Returns the path to the first node whose value matches `f`. Returns None if there is no such node or `start` does not exist.
+    pub fn dfs_ordered<F, K>(
         &self,
-        node: usize,
-        visited: &mut Vec<bool>,
-        path: &mut Vec<NodeID>,
-        f: &F,
-    ) -> bool
+        start: NodeID,
+        key: impl Fn(EdgeID, &Edge) -> K,
+        f: F,
+    ) -> Option<Vec<NodeID>>
     where
         F: Fn(&T) -> bool,
+        K: Ord,
     {
-        if visited[node] {
-            return false;
-        }
-        let node_id = NodeID(node);
-        if self.empty_node_slots.contains(&node_id) {
-            // Doesn't exist
-            return false;
+        if !self.does_node_id_exist(start) {
+            return None;
         }
-        visited[node] = true;
-        path.push(node_id);
-        if f(self.nodes[node].value()) {
-            return true;
+        let mut visited = vec![false; self.nodes.len()];
+        visited[start.0] = true;
+        if f(self[start].value()) {
+            return Some(vec![start]);
         }
-        for &edge in &self.nodes[node].edges {
-            let next = if self.edges[edge.0].node_a == node {
-                self.edges[edge.0].node_b.0
-            } else {
-                self.edges[edge.0].node_a.0
+        // The nodes on the stack are the current path. Each keeps the neighbors it has not explored yet.
+        let mut stack = vec![(start, self.ordered_neighbors(start, &key))];
+        while let Some((_, unexplored)) = stack.last_mut() {
+            let Some(next) = unexplored.next() else {
+                stack.pop();
+                continue;
             };
-            trace!(?next, ?visited, ?path, "DFS inner");
-            if self.dfs_inner(next, visited, path, f) {
-                return true;
+            if mem::replace(&mut visited[next.0], true) {
+                continue;
             }
+            if f(self[next].value()) {
+                let mut path: Vec<NodeID> = stack.into_iter().map(|(node, _)| node).collect();
+                path.push(next);
+                return Some(path);
+            }
+            stack.push((next, self.ordered_neighbors(next, &key)));
+        }
+        None
+    }
+    /// The neighbors of `node` sorted by `key` and then by edge ID.
+    fn ordered_neighbors<K: Ord>(
+        &self,
+        node: NodeID,
+        key: &impl Fn(EdgeID, &Edge) -> K,
+    ) -> impl Iterator<Item = NodeID> {
+        let mut edges: Vec<(K, EdgeID, NodeID)> = self
+            .adjacent(node)
+            .map(|(edge, next, _)| (key(edge, &self[edge]), edge, next))
+            .collect();
+        edges.sort_by(|(key_a, edge_a, _), (key_b, edge_b, _)| {
+            key_a.cmp(key_b).then(edge_a.cmp(edge_b))
+        });
+        edges.into_iter().map(|(_, _, next)| next)
+    }
+    /// Breadth First Search. Returns the nodes in the order they are visited.
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn bfs(&self, start: NodeID) -> Result<Vec<NodeID>, GraphError> {
+        algo::bfs(self, start).ok_or(GraphError::NodeDoesNotExist { node: start })
+    }
+    /// Depth first traversal from `start` that reports every event to the visitor. See [algo::Visitor]
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn depth_first_visit(
+        &self,
+        start: NodeID,
+        visitor: &mut impl algo::Visitor<NodeID, EdgeID>,
+    ) -> Result<(), GraphError> {
+        if algo::depth_first_visit(self, start, visitor) {
+            Ok(())
+        } else {
+            Err(GraphError::NodeDoesNotExist { node: start })
         }
-        path.pop();
-        false
+    }
+    /// Finds every node whose cheapest path from `start` costs at most `max_total_weight`.
+    ///
+    /// The result is sorted by cost and includes `start` with a cost of 0.
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn reachable_within(
+        &self,
+        start: NodeID,
+        max_total_weight: u64,
+    ) -> Result<Vec<(NodeID, u64)>, GraphError> {
+        algo::reachable_within(self, start, max_total_weight)
+            .ok_or(GraphError::NodeDoesNotExist { node: start })
+    }
+    /// Finds every node that is at most `hops` edges away from `start`.
+    ///
+    /// The result is sorted by the number of hops and includes `start`.
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn neighbors_within_hops(
+        &self,
+        start: NodeID,
+        hops: usize,
+    ) -> Result<Vec<NodeID>, GraphError> {
+        algo::neighbors_within_hops(self, start, hops)
+            .ok_or(GraphError::NodeDoesNotExist { node: start })
+    }
+    /// The number of edges on the shortest path from `start` to every node, ignoring weights.
+    ///
+    /// The result is indexed by node ID. Unreachable and removed nodes are `None`.
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn bfs_distances(&self, start: NodeID) -> Result<Vec<Option<usize>>, GraphError> {
+        let found =
+            algo::bfs_distances(self, start).ok_or(GraphError::NodeDoesNotExist { node: start })?;
+        let mut distances = vec![None; self.nodes.len()];
+        for (node, distance) in found {
+            distances[node.0] = Some(distance);
+        }
+        Ok(distances)
     }
 
+    /// Groups the nodes reachable from `start` by their number of hops from it.
+    ///
+    /// The first layer is `[start]`. Every layer is sorted.
+    ///
+    /// Returns an error if `start` does not exist.
+    pub fn bfs_layers(&self, start: NodeID) -> Result<Vec<Vec<NodeID>>, GraphError> {
+        let mut layers: Vec<Vec<NodeID>> = Vec::new();
+        for (node, distance) in self.bfs_distances(start)?.into_iter().enumerate() {
+            let Some(distance) = distance else {
+                continue;
+            };
+            if layers.len() <= distance {
+                layers.resize(distance + 1, Vec::new());
+            }
+            layers[distance].push(NodeID(node));
+        }
+        Ok(layers)
+    }
+    /// Labels every node with the number of hops to the nearest of `sources` and which source that is.
+    ///
+    /// All sources start at 0 hops. If two sources are equally close, the one with the lowest ID wins.
+    /// The result is indexed by node ID. Unreachable and removed nodes are `None`.
+    ///
+    /// Returns an error if any source does not exist.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+    /// graph.connect_nodes(a, b).unwrap();
+    /// graph.connect_nodes(b, c).unwrap();
+    ///
+    /// let labels = graph.multi_source_bfs(&[c, a]).unwrap();
+    /// // B is one hop from both. A has the lower ID.
+    /// assert_eq!(labels, vec![Some((0, a)), Some((1, a)), Some((0, c))]);
+    /// ```
+    pub fn multi_source_bfs(
+        &self,
+        sources: &[NodeID],
+    ) -> Result<Vec<Option<(usize, NodeID)>>, GraphError> {
+        let mut labels = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        // Seeding the sources in order keeps every layer of the queue ordered by source, so the lowest source reaches a node first.
+        for source in self.sorted_sources(sources)? {
+            if labels[source.0].is_none() {
+                labels[source.0] = Some((0, source));
+                queue.push_back(source);
+            }
+        }
+        while let Some(node) = queue.pop_front() {
+            let (distance, source) = labels[node.0].expect("Queued nodes are labeled");
+            for (_, next, _) in self.adjacent(node) {
+                if labels[next.0].is_none() {
+                    labels[next.0] = Some((distance + 1, source));
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(labels)
+    }
+    /// [AdjListGraph::multi_source_bfs] with the total edge weight instead of the number of hops.
+    pub fn multi_source_dijkstra(
+        &self,
+        sources: &[NodeID],
+    ) -> Result<Vec<Option<(u64, NodeID)>>, GraphError> {
+        let mut labels: Vec<Option<(u64, NodeID)>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        for source in self.sorted_sources(sources)? {
+            queue.push(Reverse((0, source, source)));
+        }
+        while let Some(Reverse((distance, source, node))) = queue.pop() {
+            if labels[node.0].is_some() {
+                continue;
+            }
+            labels[node.0] = Some((distance, source));
+            for (_, next, weight) in self.adjacent(node) {
+                if labels[next.0].is_none() {
+                    queue.push(Reverse((distance + weight as u64, source, next)));
+                }
+            }
+        }
+        Ok(labels)
+    }
+    /// Checks that every source exists and sorts them by ID.
+    fn sorted_sources(&self, sources: &[NodeID]) -> Result<Vec<NodeID>, GraphError> {
+        for source in sources {
+            self.check_node_exists(*source)?;
+        }
+        let mut sources = sources.to_vec();
+        sources.sort_unstable();
+        Ok(sources)
+    }
+    /// Finds the first node whose value matches `f`.
+    ///
+    /// Nodes are scanned in ascending ID order. That is not always the order they were added in.
+    /// [AdjListGraph::add_node] reuses the slots of removed nodes, so a node added after a removal can have a lower ID than older nodes.
+    ///
+    /// Removed nodes are skipped.
     pub fn find_node<F>(&self, f: F) -> Option<NodeID>
     where
         F: Fn(&T) -> bool,
     {
-        for (index, node) in self.nodes.iter().enumerate() {
-            if let Some(value) = node.optional_value() {
-                if f(value) {
-                    return Some(NodeID(index));
-                }
-            }
-        }
-        None
+        self.find_node_entry(f).map(|(id, _)| id)
+    }
+    /// [AdjListGraph::find_node] that also returns the value.
+    pub fn find_node_entry<F>(&self, f: F) -> Option<(NodeID, &T)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter_nodes().find(|(_, value)| f(value))
+    }
+    /// Finds the node with the highest ID whose value matches `f`. Scans in the opposite order of [AdjListGraph::find_node].
+    pub fn find_last_node<F>(&self, f: F) -> Option<NodeID>
+    where
+        F: Fn(&T) -> bool,
+    {
+        (0..self.nodes.len())
+            .rev()
+            .filter(|index| !self.is_node_empty(*index))
+            .find(|index| self.nodes[*index].optional_value().is_some_and(&f))
+            .map(NodeID)
     }
 
     pub fn find_node_with_that_equals(&self, value: &T) -> Option<NodeID>
@@ -83,7 +296,11 @@ impl<T> AdjListGraph<T> {
     where
         T: PartialEq,
     {
-        self.nodes.iter().find(|b| node.node_value_eq(b))
+        self.nodes
+            .iter()
+            .enumerate()
+            .find(|(index, b)| !self.is_node_empty(*index) && node.node_value_eq(b))
+            .map(|(_, b)| b)
     }
     /// Finds all nodes in the graph that are equivalent to the given node.
     pub fn find_all_equivalent_nodes_values<'a>(&'a self, node: &Node<T>) -> Vec<&'a Node<T>>
@@ -92,17 +309,31 @@ impl<T> AdjListGraph<T> {
     {
         self.nodes
             .iter()
-            .filter(|b| node.node_value_eq(b))
+            .enumerate()
+            .filter(|(index, b)| !self.is_node_empty(*index) && node.node_value_eq(b))
+            .map(|(_, b)| b)
             .collect()
     }
 }
-
+/// Searches for graphs whose values are names.
+///
+/// The predicates take `&str`, so `String` values can be matched against literals without `as_str`.
+impl<S: AsRef<str>> AdjListGraph<S> {
+    /// Depth First Search from the first node. See [AdjListGraph::dfs]
+    pub fn dfs_by_name(&self, f: impl Fn(&str) -> bool) -> Option<Vec<NodeID>> {
+        self.dfs(|value| f(value.as_ref()))
+    }
+    /// Finds the first node whose name matches `f`.
+    pub fn find_node_by_name(&self, f: impl Fn(&str) -> bool) -> Option<NodeID> {
+        self.find_node(|value| f(value.as_ref()))
+    }
+}
 #[cfg(test)]
-
 mod tests {
     use tux_graph_macros::graph_no_import;
 
-    use crate::adjacency_list::*;
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+    use crate::GraphError;
 
     #[test]
     pub fn test_searches() {
@@ -129,5 +360,294 @@ mod tests {
 
         let path = graph.dfs(|x| *x == "Data 9").unwrap();
         assert_eq!(path, vec![0, 1, 3, 8]);
+        let path = graph.bfs_find(|x| *x == "Data 9").unwrap();
+        assert_eq!(path, vec![0, 1, 3, 8]);
+    }
+    #[test]
+    pub fn search_from_a_start_node() {
+        let mut graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+            e [value = 'E'];
+
+            a -- b;
+            c -- d;
+            d -- e;
+        };
+        let (a, c, d, e) = (NodeID(0), NodeID(2), NodeID(3), NodeID(4));
+        // E is in the other component, so searching from A never reaches it.
+        assert_eq!(graph.dfs(|value| *value == 'E'), None);
+        assert_eq!(graph.bfs_find(|value| *value == 'E'), None);
+        assert_eq!(graph.dfs_from(a, |value| *value == 'E'), None);
+        assert_eq!(graph.bfs_from(a, |value| *value == 'E'), None);
+        assert_eq!(
+            graph.dfs_from(c, |value| *value == 'E'),
+            Some(vec![c, d, e])
+        );
+        assert_eq!(
+            graph.bfs_from(e, |value| *value == 'C'),
+            Some(vec![e, d, c])
+        );
+
+        graph.remove_node(a);
+        assert_eq!(graph.dfs_from(a, |_| true), None);
+        assert_eq!(graph.bfs_from(a, |_| true), None);
+        assert_eq!(graph.dfs_from(NodeID(10), |_| true), None);
+        // The search starts from B, the lowest live node, instead of the removed slot.
+        assert_eq!(graph.dfs(|_| true), Some(vec![NodeID(1)]));
+        assert_eq!(graph.bfs_find(|value| *value == 'E'), None);
+
+        graph.remove_node(NodeID(1));
+        assert_eq!(graph.dfs(|value| *value == 'E'), Some(vec![c, d, e]));
+    }
+    #[test]
+    pub fn bfs_find_takes_fewest_hops() {
+        // A depth first search can reach the target through the long branch.
+        let mut graph = graph_no_import! {
+            start [value = "Start"];
+            long_1 [value = "Long 1"];
+            long_2 [value = "Long 2"];
+            long_3 [value = "Long 3"];
+            target [value = "Target"];
+            short [value = "Short"];
+
+            start -- long_1;
+            long_1 -- long_2;
+            long_2 -- long_3;
+            long_3 -- target;
+            start -- short;
+            short -- target;
+        };
+        let is_target = |x: &&str| *x == "Target";
+        let path = graph.bfs_find(is_target).unwrap();
+        assert_eq!(path, vec![0, 5, 4]);
+        let hops = graph.bfs_distances(NodeID(0)).unwrap()[4].unwrap();
+        assert_eq!(path.len() - 1, hops);
+        assert!(graph.dfs(is_target).unwrap().len() >= path.len());
+
+        assert_eq!(graph.bfs_find(|x| *x == "Start").unwrap(), vec![0]);
+        assert_eq!(graph.bfs_find(|x| x.is_empty()), None);
+        // Starts from the first live node when node 0 is removed.
+        graph.remove_node(NodeID(0));
+        assert_eq!(graph.bfs_find(is_target).unwrap(), vec![1, 2, 3, 4]);
+    }
+    #[test]
+    pub fn find_after_slot_reuse() {
+        let mut graph = AdjListGraph::default();
+        let [a, _b, _c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        graph.remove_node(a);
+        // Reuses the slot of A.
+        let d = graph.add_node("D");
+        assert_eq!(d, a);
+
+        // Scanned by ID, not insertion order. D is found before B and C.
+        assert_eq!(graph.find_node_entry(|_| true), Some((NodeID(0), &"D")));
+        assert_eq!(graph.find_last_node(|_| true), Some(NodeID(2)));
+        assert_eq!(graph.find_last_node(|value| *value < "C"), Some(NodeID(1)));
+        assert_eq!(graph.find_node(|value| *value == "A"), None);
+
+        graph.remove_node(NodeID(2));
+        assert_eq!(graph.find_last_node(|_| true), Some(NodeID(1)));
+        assert_eq!(graph.find_node_with_that_equals(&"C"), None);
+    }
+    #[test]
+    pub fn test_searches_by_name() {
+        let graph = graph_no_import! {
+            data_1 [value = "Data 1".to_string()];
+            data_2 [value = "Data 2".to_string()];
+            data_3 [value = "Data 3".to_string()];
+            data_4 [value = "Data 4".to_string()];
+            data_5 [value = "Data 5".to_string()];
+
+            data_1 -- data_2;
+            data_1 -- data_3;
+            data_2 -- data_4;
+            data_2 -- data_5;
+        };
+        let path = graph.dfs_by_name(|name| name == "Data 5").unwrap();
+        assert_eq!(path, vec![0, 1, 4]);
+        assert_eq!(
+            graph.find_node_by_name(|name| name.ends_with('3')),
+            Some(NodeID(2))
+        );
+        assert_eq!(graph.find_node_by_name(|name| name.is_empty()), None);
+        assert_eq!(graph.bfs(NodeID(0)).unwrap().len(), 5);
+    }
+    #[test]
+    pub fn test_dfs_ordered() {
+        let mut graph = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+            d [value = 'D'];
+
+            a -- c [weight = 10];
+            c -- d [weight = 10];
+            a -- b [weight = 1];
+            b -- d [weight = 1];
+        };
+        let lightest = graph
+            .dfs_ordered(NodeID(0), |_, edge| edge.weight(), |value| *value == 'D')
+            .unwrap();
+        assert_eq!(lightest, vec![NodeID(0), NodeID(1), NodeID(3)]);
+        let heaviest = graph
+            .dfs_ordered(
+                NodeID(0),
+                |_, edge| core::cmp::Reverse(edge.weight()),
+                |value| *value == 'D',
+            )
+            .unwrap();
+        assert_eq!(heaviest, vec![NodeID(0), NodeID(2), NodeID(3)]);
+
+        graph.remove_node(NodeID(1));
+        let lightest = graph
+            .dfs_ordered(NodeID(0), |_, edge| edge.weight(), |value| *value == 'D')
+            .unwrap();
+        assert_eq!(lightest, vec![NodeID(0), NodeID(2), NodeID(3)]);
+        assert!(graph
+            .dfs_ordered(NodeID(1), |_, edge| edge.weight(), |_| true)
+            .is_none());
+    }
+    #[test]
+    pub fn dfs_ordered_long_path() {
+        let mut graph = AdjListGraph::default();
+        let nodes = graph.add_nodes_from_iterator(0..200_000u32);
+        for pair in nodes.windows(2) {
+            graph.connect_nodes(pair[0], pair[1]).unwrap();
+        }
+        let path = graph
+            .dfs_ordered(nodes[0], |_, edge| edge.weight(), |value| *value == 199_999)
+            .unwrap();
+        assert_eq!(path, nodes);
+    }
+    #[test]
+    pub fn test_reachable_within() {
+        let graph = example_from_video();
+        let reachable = graph.reachable_within(NodeID(0), 5).unwrap();
+        // A, B, C, D and E. F costs 10 and G 19.
+        assert_eq!(
+            reachable,
+            vec![
+                (NodeID(0), 0),
+                (NodeID(1), 2),
+                (NodeID(2), 3),
+                (NodeID(3), 3),
+                (NodeID(4), 4)
+            ]
+        );
+        let everything = graph.reachable_within(NodeID(0), 19).unwrap();
+        assert_eq!(everything.len(), 7);
+        assert_eq!(everything.last(), Some(&(NodeID(6), 19)));
+    }
+    #[test]
+    pub fn test_neighbors_within_hops() {
+        let mut graph = example_from_video();
+        let nodes = graph.neighbors_within_hops(NodeID(6), 1).unwrap();
+        assert_eq!(nodes, vec![NodeID(6), NodeID(5)]);
+        let nodes = graph.neighbors_within_hops(NodeID(6), 2).unwrap();
+        assert_eq!(nodes.len(), 4);
+
+        graph.remove_node(NodeID(6));
+        assert!(graph.neighbors_within_hops(NodeID(6), 2).is_err());
+        assert!(graph.reachable_within(NodeID(6), 2).is_err());
+    }
+    #[test]
+    pub fn test_bfs_distances() {
+        let mut graph = example_from_video();
+        let distances = graph.bfs_distances(NodeID(0)).unwrap();
+        assert_eq!(
+            distances,
+            vec![
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(3)
+            ]
+        );
+        graph.remove_node(NodeID(5));
+        let distances = graph.bfs_distances(NodeID(0)).unwrap();
+        assert_eq!(distances[5], None);
+        assert_eq!(distances[6], None);
+        assert!(graph.bfs_distances(NodeID(5)).is_err());
+    }
+    #[test]
+    pub fn test_bfs_layers() {
+        let mut graph = example_from_video();
+        let layers = graph.bfs_layers(NodeID(0)).unwrap();
+        assert_eq!(
+            layers,
+            vec![
+                vec![NodeID(0)],
+                vec![NodeID(1), NodeID(2), NodeID(3)],
+                vec![NodeID(4), NodeID(5)],
+                vec![NodeID(6)]
+            ]
+        );
+        graph.remove_node(NodeID(5));
+        assert_eq!(graph.bfs_layers(NodeID(0)).unwrap().len(), 3);
+        assert!(graph.bfs_layers(NodeID(5)).is_err());
+    }
+    #[test]
+    pub fn multi_source_split_at_the_midpoint() {
+        let mut graph = AdjListGraph::default();
+        let nodes = graph.add_nodes_from_iterator(0..7);
+        for pair in nodes.windows(2) {
+            graph
+                .connect_nodes_with_weight(pair[0], pair[1], 1)
+                .unwrap();
+        }
+        let (left, right) = (nodes[0], nodes[6]);
+        let labels = graph.multi_source_bfs(&[right, left]).unwrap();
+        assert_eq!(
+            labels,
+            vec![
+                Some((0, left)),
+                Some((1, left)),
+                Some((2, left)),
+                // The midpoint is three hops from both ends and goes to the lower ID.
+                Some((3, left)),
+                Some((2, right)),
+                Some((1, right)),
+                Some((0, right))
+            ]
+        );
+        let weighted = graph.multi_source_dijkstra(&[right, left]).unwrap();
+        for (hops, weighted) in labels.iter().zip(&weighted) {
+            assert_eq!(hops.map(|(hops, source)| (hops as u64, source)), *weighted);
+        }
+
+        // A heavy edge moves the boundary for the weighted labels only.
+        let (edge, _) = graph.connection(nodes[1], nodes[2]).unwrap();
+        graph.remove_edge(edge);
+        graph
+            .connect_nodes_with_weight(nodes[1], nodes[2], 10)
+            .unwrap();
+        let weighted = graph.multi_source_dijkstra(&[left, right]).unwrap();
+        assert_eq!(weighted[2], Some((4, right)));
+        assert_eq!(
+            graph.multi_source_bfs(&[left, right]).unwrap()[2],
+            Some((2, left))
+        );
+    }
+    #[test]
+    pub fn multi_source_dead_nodes() {
+        let mut graph = example_from_video();
+        graph.remove_node(NodeID(5));
+        let labels = graph.multi_source_bfs(&[NodeID(0)]).unwrap();
+        // F is removed and G is only reachable through it.
+        assert_eq!(labels[5], None);
+        assert_eq!(labels[6], None);
+        assert_eq!(labels[4], Some((2, NodeID(0))));
+        assert_eq!(
+            graph.multi_source_bfs(&[NodeID(0), NodeID(5)]),
+            Err(GraphError::NodeDoesNotExist { node: NodeID(5) })
+        );
+        assert!(graph.multi_source_dijkstra(&[NodeID(9)]).is_err());
+        assert_eq!(graph.multi_source_bfs(&[]).unwrap(), vec![None; 7]);
     }
 }
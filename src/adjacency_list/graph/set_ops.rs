@@ -0,0 +1,141 @@
+//! Comparing two graphs whose nodes are matched by value.
+use crate::collections::{HashMap, HashSet};
+use core::hash::Hash;
+
+use crate::adjacency_list::*;
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// The nodes whose values are in both graphs and the edges whose pair of values is connected in both graphs.
+    ///
+    /// The weights come from `self`. If a value appears more than once in a graph, the node with the lowest ID is used.
+    ///
+    /// Returns the new graph and a map of the values to their new IDs. The new graph has no dead slots.
+    pub fn intersection(&self, other: &Self) -> (AdjListGraph<T>, HashMap<T, NodeID>)
+    where
+        T: Clone + Eq + Hash,
+    {
+        let other_to_self = self.matched_nodes(other);
+        let other_edges = self.matched_edges(other, &other_to_self);
+        let shared: HashSet<NodeID> = other_to_self.into_values().collect();
+        self.copy_matching(
+            |node| shared.contains(&node),
+            |key| other_edges.contains(&key),
+        )
+    }
+    /// Every node of `self` and the edges of `self` whose pair of values is not connected in `other`.
+    ///
+    /// Returns the new graph and a map of the values to their new IDs. The new graph has no dead slots.
+    pub fn difference(&self, other: &Self) -> (AdjListGraph<T>, HashMap<T, NodeID>)
+    where
+        T: Clone + Eq + Hash,
+    {
+        let other_to_self = self.matched_nodes(other);
+        let other_edges = self.matched_edges(other, &other_to_self);
+        self.copy_matching(|_| true, |key| !other_edges.contains(&key))
+    }
+    /// Maps the IDs of `other` to the IDs of `self` with an equal value.
+    fn matched_nodes(&self, other: &Self) -> HashMap<NodeID, NodeID>
+    where
+        T: Eq + Hash,
+    {
+        let mut by_value: HashMap<&T, NodeID> = HashMap::with_capacity(self.number_of_nodes());
+        for (id, value) in self.iter_nodes() {
+            by_value.entry(value).or_insert(id);
+        }
+        other
+            .iter_nodes()
+            .filter_map(|(id, value)| Some((id, *by_value.get(value)?)))
+            .collect()
+    }
+    /// The edges of `other` as sorted pairs of the IDs in `self`. Edges with a node that is not in `self` are dropped.
+    fn matched_edges(
+        &self,
+        other: &Self,
+        other_to_self: &HashMap<NodeID, NodeID>,
+    ) -> HashSet<(NodeID, NodeID)> {
+        other
+            .unique_edges()
+            .filter_map(|(a, b, _)| {
+                let (a, b) = (other_to_self.get(&a)?, other_to_self.get(&b)?);
+                Some((*a.min(b), *a.max(b)))
+            })
+            .collect()
+    }
+    fn copy_matching(
+        &self,
+        keep_node: impl Fn(NodeID) -> bool,
+        keep_edge: impl Fn((NodeID, NodeID)) -> bool,
+    ) -> (AdjListGraph<T>, HashMap<T, NodeID>)
+    where
+        T: Clone + Eq + Hash,
+    {
+        let mut graph = AdjListGraph::with_policy(self.policy);
+        let mut new_ids = HashMap::new();
+        let mut values = HashMap::new();
+        for (id, value) in self.iter_nodes() {
+            if !keep_node(id) || values.contains_key(value) {
+                continue;
+            }
+            let new_id = self.copy_node_into(&mut graph, id);
+            values.insert(value.clone(), new_id);
+            new_ids.insert(id, new_id);
+        }
+        for (a, b, weight) in self.unique_edges() {
+            let (Some(new_a), Some(new_b)) = (new_ids.get(&a), new_ids.get(&b)) else {
+                continue;
+            };
+            if keep_edge((a, b)) {
+                // Only fails for parallel edges the policy rejects. The first one wins.
+                let _ = graph.connect_nodes_with_weight(*new_a, *new_b, weight);
+            }
+        }
+        (graph, values)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::*;
+
+    /// Two triangles sharing the edge `B -- C`.
+    #[test]
+    pub fn overlapping_triangles() {
+        let first = graph_no_import! {
+            a [value = 'A'];
+            b [value = 'B'];
+            c [value = 'C'];
+
+            a -- b [weight = 1];
+            b -- c [weight = 2];
+            c -- a [weight = 3];
+        };
+        let second = graph_no_import! {
+            d [value = 'D'];
+            c [value = 'C'];
+            b [value = 'B'];
+
+            c -- b [weight = 20];
+            b -- d [weight = 40];
+            d -- c [weight = 60];
+        };
+        let (shared, ids) = first.intersection(&second);
+        assert_eq!(shared.number_of_nodes(), 2);
+        assert_eq!(shared.number_of_edges(), 1);
+        assert!(!shared.has_dead_nodes());
+        assert_eq!(shared.connection(ids[&'B'], ids[&'C']).unwrap().1, 2);
+        assert!(!ids.contains_key(&'A'));
+
+        let (only_first, ids) = first.difference(&second);
+        assert_eq!(only_first.number_of_nodes(), 3);
+        assert_eq!(only_first.number_of_edges(), 2);
+        assert!(only_first.connection(ids[&'B'], ids[&'C']).is_none());
+        assert_eq!(only_first.connection(ids[&'A'], ids[&'C']).unwrap().1, 3);
+
+        let (only_second, ids) = second.difference(&first);
+        assert_eq!(only_second.connection(ids[&'D'], ids[&'B']).unwrap().1, 40);
+        assert_eq!(only_second.number_of_edges(), 2);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::prelude::*;
+use crate::{adjacency_list::*, algo};
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// Finds the path with the lowest total edge weight between two nodes.
+    ///
+    /// Returns the path and its total weight. A path from a node to itself is just the node with a weight of 0.
+    /// `None` if either node does not exist or there is no path.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+    /// graph.connect_nodes_with_weight(a, b, 2).unwrap();
+    /// graph.connect_nodes_with_weight(b, c, 2).unwrap();
+    /// graph.connect_nodes_with_weight(a, c, 5).unwrap();
+    ///
+    /// assert_eq!(graph.dijkstra(a, c), Some((vec![a, b, c], 4)));
+    /// assert_eq!(graph.dijkstra(a, a), Some((vec![a], 0)));
+    /// ```
+    pub fn dijkstra(&self, start: NodeID, goal: NodeID) -> Option<(Vec<NodeID>, u32)> {
+        self.dijkstra_with_node_costs(start, goal, |_| 0)
+    }
+    /// Finds the cheapest path between two nodes where passing through a node also has a cost.
+    ///
+    /// `node_cost(n)` is added when the node `n` is settled. This includes `from` and `to`.
+    ///
+    /// Returns the path and its total cost. `None` if either node does not exist or there is no path.
+    pub fn dijkstra_with_node_costs<C>(
+        &self,
+        from: NodeID,
+        to: NodeID,
+        node_cost: C,
+    ) -> Option<(Vec<NodeID>, u32)>
+    where
+        C: Fn(NodeID) -> u32,
+    {
+        algo::dijkstra_with_node_costs(self, from, to, node_cost)
+    }
+    /// Finds the path whose lightest edge is as heavy as possible. Useful when the weights are capacities or similarities.
+    ///
+    /// Returns the weight of the lightest edge and the path. See [algo::widest_path].
+    pub fn widest_path(&self, a: NodeID, b: NodeID) -> Option<(u32, Vec<NodeID>)> {
+        algo::widest_path(self, a, b)
+    }
+    /// Finds the path whose heaviest edge is as light as possible.
+    ///
+    /// Returns the weight of the heaviest edge and the path. See [algo::minimax_path].
+    pub fn minimax_path(&self, a: NodeID, b: NodeID) -> Option<(u32, Vec<NodeID>)> {
+        algo::minimax_path(self, a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+
+    #[test]
+    pub fn expensive_node_is_avoided() {
+        let graph = graph_no_import! {
+            start [value = "Start"];
+            toll [value = "Toll"];
+            detour [value = "Detour"];
+            end [value = "End"];
+
+            start -- toll [weight = 1];
+            toll -- end [weight = 1];
+            start -- detour [weight = 3];
+            detour -- end [weight = 3];
+        };
+        let start = graph.find_node_with_that_equals(&"Start").unwrap();
+        let toll = graph.find_node_with_that_equals(&"Toll").unwrap();
+        let detour = graph.find_node_with_that_equals(&"Detour").unwrap();
+        let end = graph.find_node_with_that_equals(&"End").unwrap();
+
+        let (path, cost) = graph.dijkstra_with_node_costs(start, end, |_| 0).unwrap();
+        assert_eq!(path, vec![start, toll, end]);
+        assert_eq!(cost, 2);
+
+        let (path, cost) = graph
+            .dijkstra_with_node_costs(start, end, |node| if node == toll { 10 } else { 1 })
+            .unwrap();
+        assert_eq!(path, vec![start, detour, end]);
+        // Three nodes cost 1 each plus the two edges.
+        assert_eq!(cost, 9);
+    }
+    #[test]
+    pub fn dijkstra_on_video_example() {
+        let mut graph = example_from_video();
+        let (a, d, f, g) = (NodeID(0), NodeID(3), NodeID(5), NodeID(6));
+        assert_eq!(graph.dijkstra(a, g), Some((vec![a, d, f, g], 19)));
+        assert_eq!(graph.dijkstra(g, a).map(|(_, cost)| cost), Some(19));
+        assert_eq!(graph.dijkstra(g, g), Some((vec![g], 0)));
+
+        // Without D -- F the cheapest way to F is A, C, E for 4 and then 8.
+        let (edge, _) = graph.connection(d, f).unwrap();
+        graph.remove_edge(edge);
+        assert_eq!(graph.dijkstra(a, g).map(|(_, cost)| cost), Some(21));
+
+        graph.remove_node(f);
+        assert_eq!(graph.dijkstra(a, g), None);
+        assert_eq!(graph.dijkstra(a, f), None);
+        assert_eq!(graph.dijkstra(f, f), None);
+    }
+    #[test]
+    pub fn no_path() {
+        let mut graph = AdjListGraph::default();
+        let [a, b] = graph.add_nodes_from_sized_array(["A", "B"]);
+        assert_eq!(graph.dijkstra_with_node_costs(a, b, |_| 1), None);
+        assert_eq!(
+            graph.dijkstra_with_node_costs(a, a, |_| 1),
+            Some((vec![a], 1))
+        );
+    }
+    /// The direct route has one weak link. The detour is longer but every edge on it is wide.
+    fn weak_link() -> AdjListGraph<&'static str> {
+        graph_no_import! {
+            start [value = "Start"];
+            end [value = "End"];
+            a [value = "A"];
+            b [value = "B"];
+            c [value = "C"];
+
+            start -- end [weight = 2];
+            start -- a [weight = 8];
+            a -- b [weight = 6];
+            b -- c [weight = 9];
+            c -- end [weight = 7];
+        }
+    }
+    #[test]
+    pub fn widest_path_takes_the_detour() {
+        let graph = weak_link();
+        let node = |value| graph.find_node_with_that_equals(&value).unwrap();
+        let (start, end) = (node("Start"), node("End"));
+        assert_eq!(
+            graph.dijkstra_with_node_costs(start, end, |_| 0).unwrap().0,
+            vec![start, end]
+        );
+        assert_eq!(
+            graph.widest_path(start, end),
+            Some((6, vec![start, node("A"), node("B"), node("C"), end]))
+        );
+        assert_eq!(
+            graph.widest_path(start, start),
+            Some((u32::MAX, vec![start]))
+        );
+    }
+    #[test]
+    pub fn minimax_path_avoids_the_heavy_edge() {
+        let mut graph = weak_link();
+        let node = |value| graph.find_node_with_that_equals(&value).unwrap();
+        let route = [node("Start"), node("A"), node("B"), node("C"), node("End")];
+        let (start, end) = (route[0], route[4]);
+        assert_eq!(graph.minimax_path(start, end), Some((2, vec![start, end])));
+
+        // With a heavy direct edge, the detour is taken even though B -- C weighs 9.
+        let (edge, _) = graph.connection(start, end).unwrap();
+        graph.remove_edge(edge);
+        graph.connect_nodes_with_weight(start, end, 20).unwrap();
+        assert_eq!(graph.minimax_path(start, end), Some((9, route.to_vec())));
+
+        let lonely = graph.add_node("Lonely");
+        assert_eq!(graph.minimax_path(start, lonely), None);
+        assert_eq!(graph.widest_path(start, lonely), None);
+        assert_eq!(graph.widest_path(start, NodeID(40)), None);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+
+use crate::utils::IdType;
+
+/// The removed slots of the nodes or edges of a graph.
+///
+/// Slots are reused in the order they were removed. `contains` is O(1), so checking if an ID is dead does not depend on how many slots are dead.
+#[derive(Debug, Clone)]
+pub(crate) struct EmptySlots<I> {
+    /// Reuse order.
+    queue: VecDeque<I>,
+    /// Indexed by the slot. True if the slot is in the queue.
+    is_empty: Vec<bool>,
+}
+impl<I> Default for EmptySlots<I> {
+    fn default() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            is_empty: Vec::new(),
+        }
+    }
+}
+impl<I: IdType + Copy> EmptySlots<I> {
+    pub fn push_back(&mut self, id: I) {
+        let index = id.as_usize();
+        if self.is_empty.len() <= index {
+            self.is_empty.resize(index + 1, false);
+        }
+        debug_assert!(!self.is_empty[index], "slot {index} was removed twice");
+        self.is_empty[index] = true;
+        self.queue.push_back(id);
+    }
+    /// Takes the oldest empty slot.
+    pub fn pop_front(&mut self) -> Option<I> {
+        let id = self.queue.pop_front()?;
+        self.is_empty[id.as_usize()] = false;
+        Some(id)
+    }
+    /// Takes a specific slot out of the queue. Returns false if it was not empty.
+    ///
+    /// O(n) in the number of empty slots.
+    pub fn remove(&mut self, id: I) -> bool {
+        if !self.contains(&id) {
+            return false;
+        }
+        self.is_empty[id.as_usize()] = false;
+        let index = id.as_usize();
+        self.queue.retain(|slot| slot.as_usize() != index);
+        true
+    }
+    /// Keeps only the slots for which `keep` returns true, in the same order. Returns how many were taken out.
+    pub fn retain(&mut self, mut keep: impl FnMut(I) -> bool) -> usize {
+        let before = self.queue.len();
+        let is_empty = &mut self.is_empty;
+        self.queue.retain(|slot| {
+            let kept = keep(*slot);
+            if !kept {
+                is_empty[slot.as_usize()] = false;
+            }
+            kept
+        });
+        before - self.queue.len()
+    }
+    pub fn contains(&self, id: &I) -> bool {
+        self.is_empty.get(id.as_usize()).copied().unwrap_or(false)
+    }
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+impl<I> IntoIterator for EmptySlots<I> {
+    type Item = I;
+    type IntoIter = alloc::collections::vec_deque::IntoIter<I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.queue.into_iter()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::EmptySlots;
+    use crate::adjacency_list::NodeID;
+
+    #[test]
+    pub fn reuse_order_and_membership() {
+        let mut slots = EmptySlots::default();
+        slots.push_back(NodeID(4));
+        slots.push_back(NodeID(1));
+        assert!(slots.contains(&NodeID(4)));
+        assert!(!slots.contains(&NodeID(2)));
+        assert!(!slots.contains(&NodeID(100)));
+
+        assert_eq!(slots.pop_front(), Some(NodeID(4)));
+        assert!(!slots.contains(&NodeID(4)));
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots.into_iter().collect::<Vec<_>>(), vec![NodeID(1)]);
+    }
+    #[test]
+    pub fn remove_from_the_middle() {
+        let mut slots = EmptySlots::default();
+        for id in [3, 1, 2] {
+            slots.push_back(NodeID(id));
+        }
+        assert!(slots.remove(NodeID(1)));
+        assert!(!slots.remove(NodeID(1)));
+        assert!(!slots.contains(&NodeID(1)));
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots.pop_front(), Some(NodeID(3)));
+        assert_eq!(slots.pop_front(), Some(NodeID(2)));
+    }
+}
@@ -0,0 +1,198 @@
+//! Cheap copies of a graph for undo, sharing the storage that did not change.
+use crate::prelude::*;
+use alloc::sync::Arc;
+
+use crate::adjacency_list::{Edge, EdgeID, Node, NodeID};
+use crate::utils::logging::debug;
+
+use super::{insertion_order::InsertionOrder, AdjListGraph, EmptySlots, GraphPolicy};
+
+/// The number of slots in a chunk of a [GraphSnapshot].
+const CHUNK_LEN: usize = 64;
+
+/// The state of a graph at the time it was taken. Created by [AdjListGraph::snapshot].
+///
+/// The node and edge slots are stored in chunks behind [Arc]s. A snapshot taken with [AdjListGraph::snapshot_after]
+/// shares every chunk that did not change with the previous snapshot, so a series of undo steps only stores the chunks each step touched.
+/// The free slots, node weights and insertion order are copied.
+///
+/// Cloning a snapshot shares all of its chunks.
+#[derive(Debug, Clone)]
+pub struct GraphSnapshot<T> {
+    nodes: Vec<Arc<[Node<T>]>>,
+    edges: Vec<Arc<[Edge]>>,
+    empty_edge_slots: EmptySlots<EdgeID>,
+    empty_node_slots: EmptySlots<NodeID>,
+    policy: GraphPolicy,
+    node_weights: Vec<u32>,
+    insertion_order: Option<InsertionOrder>,
+    content_version: u64,
+}
+impl<T> GraphSnapshot<T> {
+    /// Rebuilds the graph as it was when the snapshot was taken, with the same IDs, free slots and [AdjListGraph::content_version].
+    // Without `std` there is no analytics cache to fill in.
+    #[allow(clippy::needless_update)]
+    pub fn restore(&self) -> AdjListGraph<T>
+    where
+        T: Clone,
+    {
+        AdjListGraph {
+            nodes: self
+                .nodes
+                .iter()
+                .flat_map(|chunk| chunk.iter().cloned())
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .flat_map(|chunk| chunk.iter().cloned())
+                .collect(),
+            empty_edge_slots: self.empty_edge_slots.clone(),
+            empty_node_slots: self.empty_node_slots.clone(),
+            policy: self.policy,
+            node_weights: self.node_weights.clone(),
+            insertion_order: self.insertion_order.clone(),
+            content_version: self.content_version,
+            ..Default::default()
+        }
+    }
+}
+impl<T> AdjListGraph<T> {
+    /// Takes a [GraphSnapshot] that does not share any storage. Costs the same as a clone.
+    ///
+    /// Use [AdjListGraph::snapshot_after] for every following snapshot.
+    pub fn snapshot(&self) -> GraphSnapshot<T>
+    where
+        T: Clone,
+    {
+        self.snapshot_with(chunks(&self.nodes), chunks(&self.edges))
+    }
+    /// Takes a [GraphSnapshot] that shares the chunks of `previous` that are equal to the graph now.
+    ///
+    /// `previous` does not have to be a snapshot of this graph, but only one of an earlier state shares much.
+    /// Every chunk is compared, so this takes O(V + E) time, but only the chunks that changed are stored again.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph: AdjListGraph<_> = [("a", "b"), ("b", "c")].into();
+    /// let before = graph.snapshot();
+    /// let d = graph.add_node("d");
+    /// let after = graph.snapshot_after(&before);
+    ///
+    /// graph.remove_node(d);
+    /// graph.remove_node(graph.find_node_with_that_equals(&"b").unwrap());
+    /// assert_eq!(after.restore().number_of_nodes(), 4);
+    /// assert_eq!(before.restore(), [("a", "b"), ("b", "c")].into());
+    /// ```
+    pub fn snapshot_after(&self, previous: &GraphSnapshot<T>) -> GraphSnapshot<T>
+    where
+        T: Clone + PartialEq,
+    {
+        self.snapshot_with(
+            shared_chunks(&self.nodes, &previous.nodes),
+            shared_chunks(&self.edges, &previous.edges),
+        )
+    }
+    fn snapshot_with(&self, nodes: Vec<Arc<[Node<T>]>>, edges: Vec<Arc<[Edge]>>) -> GraphSnapshot<T>
+    where
+        T: Clone,
+    {
+        debug!(
+            node_chunks = nodes.len(),
+            edge_chunks = edges.len(),
+            "Took a snapshot"
+        );
+        GraphSnapshot {
+            nodes,
+            edges,
+            empty_edge_slots: self.empty_edge_slots.clone(),
+            empty_node_slots: self.empty_node_slots.clone(),
+            policy: self.policy,
+            node_weights: self.node_weights.clone(),
+            insertion_order: self.insertion_order.clone(),
+            content_version: self.content_version,
+        }
+    }
+}
+fn chunks<S: Clone>(slots: &[S]) -> Vec<Arc<[S]>> {
+    slots.chunks(CHUNK_LEN).map(Arc::from).collect()
+}
+/// Splits `slots` into chunks, reusing the chunk of `previous` at the same position if it is equal.
+fn shared_chunks<S: Clone + PartialEq>(slots: &[S], previous: &[Arc<[S]>]) -> Vec<Arc<[S]>> {
+    slots
+        .chunks(CHUNK_LEN)
+        .enumerate()
+        .map(|(index, chunk)| match previous.get(index) {
+            Some(shared) if shared[..] == *chunk => shared.clone(),
+            _ => Arc::from(chunk),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use crate::adjacency_list::{test_graphs::random_graph, *};
+
+    #[test]
+    pub fn restore_after_heavy_mutation() {
+        let mut graph = random_graph(150, 400, 7);
+        graph.remove_node(NodeID(3));
+        let expected = graph.clone();
+        let snapshot = graph.snapshot();
+
+        for id in (0..150).step_by(4).map(NodeID) {
+            if graph.is_node_alive(id) {
+                graph.remove_node(id);
+            }
+        }
+        let added = graph.add_nodes_from_iterator(1000..1100);
+        for pair in added.windows(2) {
+            graph.connect_nodes(pair[0], pair[1]).unwrap();
+        }
+        *graph[NodeID(1)].value_mut() = 5000;
+        graph.remove_dead_values();
+        assert_ne!(graph, expected);
+
+        let restored = snapshot.restore();
+        assert_eq!(restored, expected);
+        assert_eq!(restored.content_version(), expected.content_version());
+        // IDs and free slots are kept, so the next node reuses the same slot.
+        assert_eq!(restored.nodes.len(), expected.nodes.len());
+        assert!(!restored.is_node_alive(NodeID(3)));
+        assert_eq!(restored.clone().add_node(0), expected.clone().add_node(0));
+    }
+    #[test]
+    pub fn consecutive_snapshots_share_chunks() {
+        let mut graph = random_graph(300, 600, 11);
+        let first = graph.snapshot();
+        // Only touches the first chunk of the nodes.
+        *graph[NodeID(2)].value_mut() = 1000;
+        let second = graph.snapshot_after(&first);
+
+        assert!(!Arc::ptr_eq(&first.nodes[0], &second.nodes[0]));
+        assert_eq!(Arc::strong_count(&first.nodes[0]), 1);
+        for (a, b) in first.nodes.iter().zip(&second.nodes).skip(1) {
+            assert!(Arc::ptr_eq(a, b));
+            assert_eq!(Arc::strong_count(a), 2);
+        }
+        for (a, b) in first.edges.iter().zip(&second.edges) {
+            assert!(Arc::ptr_eq(a, b));
+        }
+
+        // Connecting changes two nodes in different chunks and adds an edge at the end.
+        graph.connect_nodes(NodeID(1), NodeID(299)).unwrap();
+        let third = graph.snapshot_after(&second);
+        let shared = (third.nodes.iter().zip(&second.nodes))
+            .filter(|(a, b)| Arc::ptr_eq(a, b))
+            .count();
+        assert_eq!(shared, third.nodes.len() - 2);
+        assert_eq!(Arc::strong_count(&second.nodes[1]), 3);
+
+        assert_eq!(first.restore()[NodeID(2)].value(), &2);
+        assert_eq!(second.restore()[NodeID(2)].value(), &1000);
+        assert_eq!(third.restore(), graph);
+    }
+}
@@ -0,0 +1,261 @@
+//! Functions describing the shape of the graph.
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use core::ops::Range;
+use serde::{Deserialize, Serialize};
+
+use super::AdjListGraph;
+use crate::adjacency_list::NodeID;
+
+impl<T> AdjListGraph<T> {
+    /// Splits the range of edge weights into at most `bins` equally sized buckets and counts the live edges in each.
+    ///
+    /// The buckets cover `min..=max` of the weights. If there are less distinct possible weights than `bins`, each bucket covers a single weight.
+    /// The last bucket can be narrower than the others. Fewer buckets are returned if `bins` of that width would start past the max,
+    /// for example 3 buckets for the weights 1 to 5 with `bins` set to 4.
+    ///
+    /// Returns an empty vector if the graph has no edges or `bins` is zero.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+    /// graph.connect_nodes_with_weight(a, b, 1).unwrap();
+    /// graph.connect_nodes_with_weight(b, c, 2).unwrap();
+    /// graph.connect_nodes_with_weight(c, a, 4).unwrap();
+    ///
+    /// assert_eq!(graph.weight_histogram(2), vec![(1..3, 2), (3..5, 1)]);
+    /// ```
+    pub fn weight_histogram(&self, bins: usize) -> Vec<(Range<u32>, usize)> {
+        let weights: Vec<u32> = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.is_edge_empty(*index))
+            .map(|(_, edge)| edge.weight())
+            .collect();
+        let (Some(min), Some(max)) = (weights.iter().min(), weights.iter().max()) else {
+            return Vec::new();
+        };
+        if bins == 0 {
+            return Vec::new();
+        }
+        let (min, max) = (*min as u64, *max as u64);
+        let span = max - min + 1;
+        let width = span.div_ceil((bins as u64).min(span));
+        let bins = span.div_ceil(width);
+        let mut histogram: Vec<(Range<u32>, usize)> = (0..bins)
+            .map(|bin| {
+                let start = min + bin * width;
+                let end = (start + width).min(max + 1);
+                // The end of the last bucket is clamped in case the max weight is u32::MAX.
+                (start as u32..end.min(u32::MAX as u64) as u32, 0)
+            })
+            .collect();
+        for weight in weights {
+            let bin = ((weight as u64 - min) / width) as usize;
+            histogram[bin].1 += 1;
+        }
+        histogram
+    }
+    /// Counts the live nodes by degree. The value at index `d` is the number of nodes with `d` edges.
+    ///
+    /// The last value is never zero. Returns an empty vector if the graph has no nodes.
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if self.is_node_empty(index) {
+                continue;
+            }
+            let degree = node.edges.len();
+            if histogram.len() <= degree {
+                histogram.resize(degree + 1, 0);
+            }
+            histogram[degree] += 1;
+        }
+        histogram
+    }
+    /// The sum of the weights of the live edges of a node, also called its strength. `None` if the node does not exist.
+    ///
+    /// A self loop is counted twice, once for each end. Unlike the degree, edges with a weight of 0 add nothing.
+    pub fn weighted_degree(&self, node: NodeID) -> Option<u64> {
+        self.does_node_id_exist(node).then(|| {
+            self.neighbor_edges(node)
+                .map(|edge| edge.weight() as u64 * if edge.is_self_loop() { 2 } else { 1 })
+                .sum()
+        })
+    }
+    /// Counts the live nodes by [AdjListGraph::weighted_degree]. The weighted version of [AdjListGraph::degree_histogram].
+    pub fn strength_histogram(&self) -> BTreeMap<u64, usize> {
+        let mut histogram = BTreeMap::new();
+        for node in self.node_ids() {
+            let strength = self.weighted_degree(node).unwrap_or_default();
+            *histogram.entry(strength).or_default() += 1;
+        }
+        histogram
+    }
+    /// Counts that summarize the graph. Dead slots are not counted.
+    ///
+    /// This runs [AdjListGraph::connected_components]. Use [AdjListGraph::analytics] to reuse the result between calls.
+    pub fn stats(&self) -> GraphStats {
+        self.stats_with_components(self.connected_components().len())
+    }
+    pub(crate) fn stats_with_components(&self, components: usize) -> GraphStats {
+        let mut stats = GraphStats {
+            nodes: self.number_of_nodes(),
+            edges: self.number_of_edges(),
+            components,
+            ..GraphStats::default()
+        };
+        let mut min_degree = None;
+        for (index, node) in self.nodes.iter().enumerate() {
+            if self.is_node_empty(index) {
+                continue;
+            }
+            let degree = node.edges.len();
+            min_degree = Some(min_degree.map_or(degree, |min: usize| min.min(degree)));
+            stats.max_degree = stats.max_degree.max(degree);
+        }
+        stats.min_degree = min_degree.unwrap_or(0);
+        for (index, edge) in self.edges.iter().enumerate() {
+            if self.is_edge_empty(index) {
+                continue;
+            }
+            if edge.node_a == edge.node_b {
+                stats.self_loops += 1;
+            }
+            stats.total_weight += edge.weight() as u64;
+        }
+        stats
+    }
+}
+/// Returned by [AdjListGraph::stats].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphStats {
+    /// The number of live nodes.
+    pub nodes: usize,
+    /// The number of live edges.
+    pub edges: usize,
+    /// The number of connected components.
+    pub components: usize,
+    /// Edges that connect a node to itself.
+    pub self_loops: usize,
+    /// The smallest number of edges on a node. 0 if the graph has no nodes.
+    pub min_degree: usize,
+    /// The largest number of edges on a node.
+    pub max_degree: usize,
+    /// The sum of the live edge weights.
+    pub total_weight: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+
+    #[test]
+    pub fn histogram_splits_in_the_middle() {
+        let mut graph = AdjListGraph::default();
+        let nodes = graph.add_nodes_from_sized_array([0; 11]);
+        for weight in 1..=10 {
+            graph
+                .connect_nodes_with_weight(nodes[0], nodes[weight], weight as u32)
+                .unwrap();
+        }
+        assert_eq!(graph.weight_histogram(2), vec![(1..6, 5), (6..11, 5)]);
+        assert_eq!(graph.weight_histogram(0), vec![]);
+
+        graph.remove_edge(EdgeID(0));
+        assert_eq!(graph.weight_histogram(2), vec![(2..7, 5), (7..11, 4)]);
+    }
+    #[test]
+    pub fn histogram_with_uneven_span() {
+        let mut graph = AdjListGraph::default();
+        let nodes = graph.add_nodes_from_sized_array([0; 6]);
+        for weight in 1..=5 {
+            graph
+                .connect_nodes_with_weight(nodes[0], nodes[weight], weight as u32)
+                .unwrap();
+        }
+        assert_eq!(
+            graph.weight_histogram(4),
+            vec![(1..3, 2), (3..5, 2), (5..6, 1)]
+        );
+        assert_eq!(
+            graph.weight_histogram(3),
+            vec![(1..3, 2), (3..5, 2), (5..6, 1)]
+        );
+        for bins in 1..=10 {
+            let histogram = graph.weight_histogram(bins);
+            assert!(histogram.len() <= bins);
+            assert!(histogram.iter().all(|(range, _)| range.start < range.end));
+            assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 5);
+        }
+    }
+    #[test]
+    pub fn degree_histogram_of_star() {
+        let mut graph = AdjListGraph::default();
+        let [hub, a, b, c] = graph.add_nodes_from_sized_array(['H', 'A', 'B', 'C']);
+        for leaf in [a, b, c] {
+            graph.connect_nodes(hub, leaf).unwrap();
+        }
+        graph.add_node('D');
+        assert_eq!(graph.degree_histogram(), vec![1, 3, 0, 1]);
+
+        graph.remove_node(hub);
+        assert_eq!(graph.degree_histogram(), vec![4]);
+    }
+    #[test]
+    pub fn histogram_of_edgeless_graph() {
+        let mut graph = AdjListGraph::default();
+        graph.add_node("A");
+        assert!(graph.weight_histogram(4).is_empty());
+    }
+    #[test]
+    pub fn stats_of_small_graph() {
+        let mut graph = AdjListGraph::default();
+        assert_eq!(graph.stats(), GraphStats::default());
+        let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+        graph.connect_nodes_with_weight(a, b, 3).unwrap();
+        graph.connect_nodes_with_weight(b, b, 4).unwrap();
+        graph.add_node('D');
+        graph.remove_node(c);
+        assert_eq!(
+            graph.stats(),
+            GraphStats {
+                nodes: 3,
+                edges: 2,
+                components: 2,
+                self_loops: 1,
+                min_degree: 0,
+                max_degree: 2,
+                total_weight: 7,
+            }
+        );
+    }
+    #[test]
+    pub fn strengths_of_video_example() {
+        let mut graph = example_from_video();
+        let strengths: Vec<u64> = graph
+            .node_ids()
+            .map(|node| graph.weighted_degree(node).unwrap())
+            .collect();
+        assert_eq!(strengths, vec![8, 9, 13, 15, 12, 24, 9]);
+
+        let g = NodeID(6);
+        graph.connect_nodes_with_weight(g, g, 2).unwrap();
+        assert_eq!(graph.weighted_degree(g), Some(13));
+        // A weight of 0 counts for the degree but not for the strength.
+        let h = graph.add_node('H');
+        graph.connect_nodes(h, NodeID(0)).unwrap();
+        assert_eq!(graph.weighted_degree(h), Some(0));
+        assert_eq!(graph.weighted_degree(NodeID(0)), Some(8));
+        assert_eq!(graph[h].edges.len(), 1);
+        assert_eq!(graph.weighted_degree(NodeID(20)), None);
+
+        assert_eq!(
+            graph.strength_histogram().into_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (8, 1), (9, 1), (12, 1), (13, 2), (15, 1), (24, 1)]
+        );
+    }
+}
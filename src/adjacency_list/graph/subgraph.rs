@@ -0,0 +1,497 @@
+use crate::collections::HashMap;
+use crate::prelude::*;
+
+use crate::adjacency_list::*;
+use crate::GraphError;
+
+use super::AdjListGraph;
+
+impl<T> AdjListGraph<T> {
+    /// Creates a new graph containing only the nodes where `keep` returns true and the edges between them.
+    ///
+    /// Edges connected to a filtered out node are dropped.
+    ///
+    /// Returns the new graph and a map of the old node IDs to the new node IDs.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [one, two, four] = graph.add_nodes_from_sized_array([1, 2, 4]);
+    /// graph.connect_nodes(one, two).unwrap();
+    /// graph.connect_nodes(two, four).unwrap();
+    ///
+    /// let (even, ids) = graph.filter_nodes(|value| value % 2 == 0);
+    /// assert_eq!(even.number_of_nodes(), 2);
+    /// assert_eq!(even.number_of_edges(), 1);
+    /// assert!(!ids.contains_key(&one));
+    /// ```
+    pub fn filter_nodes<F>(&self, keep: F) -> (AdjListGraph<T>, HashMap<NodeID, NodeID>)
+    where
+        F: Fn(&T) -> bool,
+        T: Clone,
+    {
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(index, node)| !self.is_node_empty(*index) && keep(node.value()))
+            .map(|(index, _)| NodeID(index));
+        self.copy_nodes_and_edges_between(nodes, |_| true)
+    }
+    /// Creates a new graph containing only the given nodes and the edges between them.
+    ///
+    /// Dead or unknown node IDs are ignored.
+    ///
+    /// Returns the new graph and a map of the old node IDs to the new node IDs.
+    pub fn induced_subgraph(&self, nodes: &[NodeID]) -> (AdjListGraph<T>, HashMap<NodeID, NodeID>)
+    where
+        T: Clone,
+    {
+        let nodes = nodes
+            .iter()
+            .copied()
+            .filter(|node| node.0 < self.nodes.len() && !self.is_node_empty(node.0));
+        self.copy_nodes_and_edges_between(nodes, |_| true)
+    }
+    /// The subgraph induced by the nodes at most `radius` edges away from `center`.
+    ///
+    /// Every edge between two of those nodes is copied, not only the ones on the shortest paths. A radius of 0 is the center and its self loops.
+    ///
+    /// Returns the new graph and a map of the old node IDs to the new node IDs. `None` if the center does not exist.
+    pub fn ego_graph(
+        &self,
+        center: NodeID,
+        radius: usize,
+    ) -> Option<(AdjListGraph<T>, HashMap<NodeID, NodeID>)>
+    where
+        T: Clone,
+    {
+        if !self.does_node_id_exist(center) {
+            return None;
+        }
+        let mut visited = vec![false; self.nodes.len()];
+        visited[center.0] = true;
+        let mut nodes = vec![center];
+        let mut frontier = vec![center];
+        for _ in 0..radius {
+            let mut next_frontier = Vec::new();
+            for node in frontier {
+                for (_, next, _) in self.adjacent(node) {
+                    if !visited[next.0] {
+                        visited[next.0] = true;
+                        next_frontier.push(next);
+                    }
+                }
+            }
+            if next_frontier.is_empty() {
+                break;
+            }
+            nodes.extend_from_slice(&next_frontier);
+            frontier = next_frontier;
+        }
+        Some(self.copy_nodes_and_edges_between(nodes.into_iter(), |_| true))
+    }
+    /// Copies a live edge into `target`, along with the nodes it connects that are not in `id_map` yet.
+    ///
+    /// `id_map` maps the node IDs of this graph to the node IDs in `target`. Copied nodes are added to it, so calling this for each edge of a path copies every node once.
+    ///
+    /// Returns [GraphError::EdgeDoesNotExist] if the edge is not live. Errors from connecting the nodes in `target`, such as a parallel edge its [GraphPolicy] rejects,
+    /// are returned as is. The nodes copied before the error stay in `target` and `id_map`.
+    ///
+    /// ```rust
+    /// use tux_graph::adjacency_list::AdjListGraph;
+    /// use tux_graph::collections::HashMap;
+    ///
+    /// let mut graph = AdjListGraph::default();
+    /// let [a, b, c] = graph.add_nodes_from_sized_array(['A', 'B', 'C']);
+    /// let ab = graph.connect_nodes_with_weight(a, b, 1).unwrap();
+    /// let bc = graph.connect_nodes_with_weight(b, c, 2).unwrap();
+    ///
+    /// let mut target = AdjListGraph::default();
+    /// let mut ids = HashMap::new();
+    /// graph.copy_edge_into(&mut target, ab, &mut ids).unwrap();
+    /// let copied = graph.copy_edge_into(&mut target, bc, &mut ids).unwrap();
+    /// assert_eq!(copied.node_a, None);
+    /// assert_eq!(copied.node_b, Some((c, ids[&c])));
+    /// assert_eq!(target.number_of_nodes(), 3);
+    /// ```
+    pub fn copy_edge_into(
+        &self,
+        target: &mut Self,
+        edge: EdgeID,
+        id_map: &mut HashMap<NodeID, NodeID>,
+    ) -> Result<EdgeCopyResult, GraphError>
+    where
+        T: Clone,
+    {
+        let edge = self
+            .get_edge(edge)
+            .ok_or(GraphError::EdgeDoesNotExist { edge })?;
+        let mut copy_node = |node: NodeID| {
+            if let Some(copied) = id_map.get(&node) {
+                return (*copied, None);
+            }
+            let copied = self.copy_node_into(target, node);
+            id_map.insert(node, copied);
+            (copied, Some((node, copied)))
+        };
+        let (target_a, node_a) = copy_node(edge.node_a);
+        let (target_b, node_b) = copy_node(edge.node_b);
+        let new_edge_id = target.connect_nodes_with_weight(target_a, target_b, edge.weight())?;
+        Ok(EdgeCopyResult {
+            new_edge_id,
+            node_a,
+            node_b,
+        })
+    }
+    /// Copies the graph so that every node ends up at the ID `mapping` gives it.
+    ///
+    /// IDs below the highest target that nothing is mapped to become dead slots, so the result lines up with the graph the IDs came from.
+    /// Edges are renumbered in ascending order of their old IDs.
+    ///
+    /// Returns [GraphError::MissingFromMapping] if a live node is not in the mapping and [GraphError::RelabelCollision] if two nodes are mapped to the same ID.
+    /// Mapping entries for nodes that do not exist are ignored.
+    pub fn relabel(&self, mapping: &HashMap<NodeID, NodeID>) -> Result<AdjListGraph<T>, GraphError>
+    where
+        T: Clone,
+    {
+        let mut sources: HashMap<NodeID, NodeID> = HashMap::with_capacity(self.number_of_nodes());
+        let mut len = 0;
+        for node in self.node_ids() {
+            let target = *mapping
+                .get(&node)
+                .ok_or(GraphError::MissingFromMapping { node })?;
+            if let Some(other) = sources.insert(target, node) {
+                return Err(GraphError::RelabelCollision {
+                    node_a: other,
+                    node_b: node,
+                    target,
+                });
+            }
+            len = len.max(target.0 + 1);
+        }
+        let mut graph = AdjListGraph::with_policy(self.policy);
+        graph.reserve(len, self.number_of_edges());
+        for index in 0..len {
+            let target = NodeID(index);
+            match sources.get(&target) {
+                Some(source) => {
+                    graph.nodes.push(Node::new(self[*source].value().clone()));
+                    self.copy_node_weight(*source, &mut graph, target);
+                }
+                None => {
+                    graph.nodes.push(Node::dead());
+                    graph.empty_node_slots.push_back(target);
+                }
+            }
+        }
+        for edge in self.edge_ids() {
+            let edge = &self[edge];
+            // Parallel edges can only exist if the policy allows them, and the copy has the same policy.
+            let _ = graph.connect_nodes_with_weight(
+                mapping[&edge.node_a],
+                mapping[&edge.node_b],
+                edge.weight(),
+            );
+        }
+        Ok(graph)
+    }
+    /// Keeps every live node but only the `k` lightest edges of each node.
+    ///
+    /// An edge survives if it is one of the `k` lightest edges of either of its nodes, so a node can end up with more than `k` edges.
+    /// Ties are broken by the lower edge ID. Used to sparsify a dense graph before clustering.
+    ///
+    /// Returns the new graph and a map of the old node IDs to the new node IDs.
+    pub fn knn_sparsify(&self, k: usize) -> (AdjListGraph<T>, HashMap<NodeID, NodeID>)
+    where
+        T: Clone,
+    {
+        let mut keep = vec![false; self.edges.len()];
+        let mut incident = Vec::new();
+        for node in self.node_ids() {
+            incident.clear();
+            incident.extend(self.adjacent(node).map(|(edge, _, weight)| (weight, edge)));
+            incident.sort_unstable();
+            for (_, edge) in incident.iter().take(k) {
+                keep[edge.0] = true;
+            }
+        }
+        self.copy_nodes_and_edges_between(self.node_ids(), |edge| keep[edge.0])
+    }
+    /// Copies the given nodes into a new graph. Then copies every edge accepted by `keep_edge` where both nodes were copied.
+    fn copy_nodes_and_edges_between(
+        &self,
+        nodes: impl Iterator<Item = NodeID>,
+        keep_edge: impl Fn(EdgeID) -> bool,
+    ) -> (AdjListGraph<T>, HashMap<NodeID, NodeID>)
+    where
+        T: Clone,
+    {
+        let mut subgraph = AdjListGraph::with_policy(self.policy);
+        let mut updated_node_ids = HashMap::new();
+        for node in nodes {
+            if updated_node_ids.contains_key(&node) {
+                continue;
+            }
+            let new_node = self.copy_node_into(&mut subgraph, node);
+            updated_node_ids.insert(node, new_node);
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            if self.is_edge_empty(index) || !keep_edge(EdgeID(index)) {
+                continue;
+            }
+            let (Some(node_a), Some(node_b)) = (
+                updated_node_ids.get(&edge.node_a),
+                updated_node_ids.get(&edge.node_b),
+            ) else {
+                continue;
+            };
+            // A parallel edge can only exist if the source graph was built without `connect_nodes`. The first one wins.
+            let _ = subgraph.connect_nodes_with_weight(*node_a, *node_b, edge.weight());
+        }
+        (subgraph, updated_node_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use crate::adjacency_list::{test_graphs::example_from_video, *};
+    use crate::collections::HashMap;
+    use crate::GraphError;
+
+    #[test]
+    pub fn filter_even_nodes() {
+        let graph: AdjListGraph<u32> = graph_no_import! {
+            one [value = 1];
+            two [value = 2];
+            three [value = 3];
+            four [value = 4];
+            six [value = 6];
+
+            one -- two;
+            two -- three;
+            two -- four [weight = 5];
+            four -- six;
+            three -- six;
+        };
+        let (even, ids) = graph.filter_nodes(|value| value % 2 == 0);
+        assert_eq!(even.number_of_nodes(), 3);
+        assert_eq!(even.number_of_edges(), 2);
+        assert!(!even.has_invalid_edges());
+        assert!(!even.has_invalid_nodes());
+        assert_eq!(ids.len(), 3);
+
+        let two = graph.find_node_with_that_equals(&2).unwrap();
+        let four = graph.find_node_with_that_equals(&4).unwrap();
+        assert!(even.is_node_connected_to_node(ids[&two], ids[&four]));
+        for node in even.nodes.iter() {
+            assert_eq!(node.value() % 2, 0);
+        }
+    }
+    #[test]
+    pub fn induced_subgraph_skips_dead_nodes() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c] = graph.add_nodes_from_sized_array(["A", "B", "C"]);
+        graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        graph.remove_node(c);
+
+        let (subgraph, ids) = graph.induced_subgraph(&[a, b, c]);
+        assert_eq!(subgraph.number_of_nodes(), 2);
+        assert_eq!(subgraph.number_of_edges(), 1);
+        assert!(!ids.contains_key(&c));
+    }
+    #[test]
+    pub fn knn_sparsify_complete_graph() {
+        let mut graph = AdjListGraph::default();
+        let nodes: Vec<NodeID> = (0..8u32).map(|value| graph.add_node(value)).collect();
+        for (index, a) in nodes.iter().enumerate() {
+            for b in &nodes[index + 1..] {
+                let weight = (a.0 * 7 + b.0 * 3) % 5;
+                graph
+                    .connect_nodes_with_weight(*a, *b, weight as u32)
+                    .unwrap();
+            }
+        }
+        let n = nodes.len();
+
+        let (sparse, ids) = graph.knn_sparsify(1);
+        assert!(sparse.number_of_edges() >= n / 2);
+        assert!(sparse.number_of_edges() <= n);
+        assert_eq!(sparse.number_of_nodes(), n);
+        for node in &nodes {
+            assert!(!sparse[ids[node]].edges.is_empty());
+        }
+        sparse.validate().unwrap();
+        // The original is untouched.
+        assert_eq!(graph.number_of_edges(), n * (n - 1) / 2);
+
+        let (same, _) = graph.knn_sparsify(n - 1);
+        assert_eq!(same, graph);
+        assert_eq!(graph.knn_sparsify(0).0.number_of_edges(), 0);
+    }
+    #[test]
+    pub fn knn_sparsify_breaks_ties_by_edge_id() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d] = graph.add_nodes_from_sized_array(["A", "B", "C", "D"]);
+        let first = graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(a, c).unwrap();
+        graph.connect_nodes(a, d).unwrap();
+        graph.connect_nodes_with_weight(b, c, 5).unwrap();
+        graph.connect_nodes_with_weight(c, d, 5).unwrap();
+        graph.connect_nodes_with_weight(b, d, 5).unwrap();
+        graph.remove_edge(first);
+
+        let (sparse, ids) = graph.knn_sparsify(1);
+        // A keeps A -- C. B has no weight 0 edge left and keeps its lowest ID, B -- C. D keeps A -- D.
+        assert_eq!(sparse.number_of_edges(), 3);
+        assert!(sparse.is_node_connected_to_node(ids[&a], ids[&c]));
+        assert!(sparse.is_node_connected_to_node(ids[&b], ids[&c]));
+        assert!(sparse.is_node_connected_to_node(ids[&a], ids[&d]));
+    }
+    #[test]
+    pub fn relabel_mst_into_original_ids() {
+        let graph = example_from_video();
+        let mst = graph.kruskal_find_mst().unwrap();
+        let mapping: HashMap<NodeID, NodeID> = mst
+            .iter_nodes()
+            .map(|(id, value)| (id, graph.find_node_with_that_equals(value).unwrap()))
+            .collect();
+        let overlay = mst.relabel(&mapping).unwrap();
+        overlay.validate().unwrap();
+        assert_eq!(overlay, mst);
+        for (a, b, weight) in overlay.unique_edges() {
+            assert_eq!(overlay[a].value(), graph[a].value());
+            let (_, original_weight) = graph.connection(a, b).unwrap();
+            assert_eq!(weight, original_weight);
+        }
+    }
+    #[test]
+    pub fn relabel_leaves_gaps_dead() {
+        let graph = example_from_video();
+        let [b, e, g] = [1, 4, 6].map(NodeID);
+        let (subgraph, ids) = graph.induced_subgraph(&[b, e, g]);
+        let back: HashMap<NodeID, NodeID> = ids.iter().map(|(old, new)| (*new, *old)).collect();
+
+        let overlay = subgraph.relabel(&back).unwrap();
+        overlay.validate().unwrap();
+        assert_eq!(overlay.live_node_ids(), vec![b, e, g]);
+        assert_eq!(overlay.number_of_edges(), 1);
+        assert!(overlay.is_node_connected_to_node(b, e));
+        assert!(!overlay.does_node_id_exist(NodeID(0)));
+
+        let mut collision = back.clone();
+        collision.insert(ids[&e], b);
+        assert!(matches!(
+            subgraph.relabel(&collision),
+            Err(GraphError::RelabelCollision { target, .. }) if target == b
+        ));
+        let mut missing = back;
+        missing.remove(&ids[&g]);
+        assert_eq!(
+            subgraph.relabel(&missing),
+            Err(GraphError::MissingFromMapping { node: ids[&g] })
+        );
+    }
+    #[test]
+    pub fn copy_path_edge_by_edge() {
+        let mut graph = AdjListGraph::default();
+        let path = graph.add_nodes_from_sized_array(['A', 'B', 'C', 'D', 'E']);
+        let off_path = graph.add_node('F');
+        let edges: Vec<EdgeID> = path
+            .windows(2)
+            .zip(1..)
+            .map(|(pair, weight)| {
+                graph
+                    .connect_nodes_with_weight(pair[0], pair[1], weight)
+                    .unwrap()
+            })
+            .collect();
+        graph.connect_nodes(path[1], off_path).unwrap();
+        graph.connect_nodes(off_path, path[3]).unwrap();
+
+        let mut copied = AdjListGraph::default();
+        let mut ids = HashMap::new();
+        for edge in &edges {
+            graph.copy_edge_into(&mut copied, *edge, &mut ids).unwrap();
+        }
+        let (induced, induced_ids) = graph.induced_subgraph(&path);
+        assert_eq!(copied, induced);
+        assert_eq!(ids, induced_ids);
+
+        assert_eq!(
+            graph.copy_edge_into(&mut copied, edges[0], &mut ids),
+            Err(GraphError::NodesAlreadyConnected {
+                node_a: ids[&path[0]],
+                node_b: ids[&path[1]],
+                edge: EdgeID(0),
+            })
+        );
+        graph.remove_edge(edges[0]);
+        assert_eq!(
+            graph.copy_edge_into(&mut copied, edges[0], &mut ids),
+            Err(GraphError::EdgeDoesNotExist { edge: edges[0] })
+        );
+    }
+    #[test]
+    pub fn copy_self_loop() {
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node("A");
+        let edge = graph.connect_nodes_with_weight(a, a, 3).unwrap();
+
+        let mut target = AdjListGraph::default();
+        let mut ids = HashMap::new();
+        let result = graph.copy_edge_into(&mut target, edge, &mut ids).unwrap();
+        assert_eq!(result.node_a, Some((a, NodeID(0))));
+        assert_eq!(result.node_b, None);
+        assert_eq!(target.number_of_nodes(), 1);
+        assert!(target.is_node_connected_to_itself(NodeID(0)));
+    }
+    #[test]
+    pub fn ego_graph_of_video_example() {
+        let graph = example_from_video();
+        let [a, b, c, d, e, f, g] = core::array::from_fn(NodeID);
+
+        let (ego, ids) = graph.ego_graph(a, 0).unwrap();
+        assert_eq!(ego.number_of_nodes(), 1);
+        assert_eq!(ego.number_of_edges(), 0);
+        assert_eq!(ids.keys().copied().collect::<Vec<_>>(), vec![a]);
+
+        let (ego, ids) = graph.ego_graph(a, 1).unwrap();
+        let mut nodes: Vec<NodeID> = ids.keys().copied().collect();
+        nodes.sort_unstable();
+        assert_eq!(nodes, vec![a, b, c, d]);
+        // B -- C and C -- D connect two nodes one hop away.
+        assert_eq!(ego.number_of_edges(), 5);
+        assert!(ego.is_node_connected_to_node(ids[&b], ids[&c]));
+        assert!(ego.is_node_connected_to_node(ids[&c], ids[&d]));
+
+        let (ego, ids) = graph.ego_graph(a, 2).unwrap();
+        let mut nodes: Vec<NodeID> = ids.keys().copied().collect();
+        nodes.sort_unstable();
+        assert_eq!(nodes, vec![a, b, c, d, e, f]);
+        assert_eq!(ego.number_of_edges(), 9);
+        assert!(ego.is_node_connected_to_node(ids[&e], ids[&f]));
+
+        let (ego, _) = graph.ego_graph(g, 10).unwrap();
+        assert_eq!(ego, graph);
+    }
+    #[test]
+    pub fn ego_graph_keeps_self_loops() {
+        let mut graph = AdjListGraph::default();
+        let [a, b] = graph.add_nodes_from_sized_array(['A', 'B']);
+        graph.connect_nodes(a, a).unwrap();
+        graph.connect_nodes(a, b).unwrap();
+
+        let (ego, ids) = graph.ego_graph(a, 0).unwrap();
+        assert_eq!(ego.number_of_edges(), 1);
+        assert!(ego.is_node_connected_to_itself(ids[&a]));
+
+        graph.remove_node(b);
+        assert!(graph.ego_graph(b, 1).is_none());
+        assert!(graph.ego_graph(NodeID(9), 1).is_none());
+    }
+}
@@ -0,0 +1,55 @@
+//! Graphs shared between the tests of the different algorithms.
+use tux_graph_macros::graph_no_import;
+
+use crate::adjacency_list::*;
+
+/// Based on the example found on this video https://www.youtube.com/watch?v=71UQH7Pr9kU
+///
+/// Node IDs are assigned in alphabetical order. `A` is `NodeID(0)` and `G` is `NodeID(6)`.
+pub fn example_from_video() -> AdjListGraph<char> {
+    graph_no_import! {
+        a [value='A'];
+        b [value='B'];
+        c [value='C'];
+        d [value='D'];
+        e [value='E'];
+        f [value='F'];
+        g [value='G'];
+        // Edges
+        // A Connections
+        a -- b [weight=2];
+        a -- c [weight=3];
+        a -- d [weight=3];
+        // B Connections
+        b -- c [weight=4];
+        b -- e [weight=3];
+        // C Connections
+        c -- d [weight=5];
+        c -- e [weight=1];
+        // D Connections
+        d -- f [weight=7];
+        // E Connections
+        e -- f [weight=8];
+        // F Connections
+        f -- g [weight=9];
+    }
+}
+/// A graph with `nodes` nodes and up to `edges` random edges.
+///
+/// Every tenth node is removed afterwards so the graph also has dead slots.
+pub fn random_graph(nodes: usize, edges: usize, seed: u64) -> AdjListGraph<usize> {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut graph = AdjListGraph::default();
+    let ids = graph.add_nodes_from_iterator(0..nodes);
+    for _ in 0..edges {
+        let a = ids[rng.gen_range(0..nodes)];
+        let b = ids[rng.gen_range(0..nodes)];
+        // Duplicates are rejected by the graph and simply skipped.
+        let _ = graph.connect_nodes_with_weight(a, b, rng.gen_range(0..100));
+    }
+    for id in ids.iter().step_by(10) {
+        graph.remove_node(*id);
+    }
+    graph
+}
@@ -1,15 +1,52 @@
+use crate::prelude::*;
 /// Internally used utilities for the adjacency list graph.
 use crate::GraphError;
 
+use serde::{Deserialize, Serialize};
+
 use super::{AdjListGraph, Edge, EdgeID, NodeID};
 pub type EdgeRefAndID<'a> = (EdgeID, &'a Edge);
 pub type EdgeAndID = (EdgeID, Edge);
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Returned by [AdjListGraph::copy_edge_into].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EdgeCopyResult {
+    /// The ID of the edge in the target graph.
     pub new_edge_id: EdgeID,
+    /// `(source, target)` if `node_a` of the edge was copied by this call. `None` if it was already mapped.
     pub node_a: Option<(NodeID, NodeID)>,
+    /// `(source, target)` if `node_b` of the edge was copied by this call. `None` if it was already mapped or is the same node as `node_a`.
     pub node_b: Option<(NodeID, NodeID)>,
 }
+/// Whether [AdjListGraph::select_neighbors] prefers light or heavy edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NeighborOrder {
+    /// Ascending weight, then ascending edge ID.
+    #[default]
+    Lightest,
+    /// Descending weight, then ascending edge ID.
+    Heaviest,
+}
+/// How [AdjListGraph::select_neighbors] picks neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NeighborSelection {
+    pub order: NeighborOrder,
+    /// A self loop makes the node its own neighbor.
+    pub include_self_loops: bool,
+}
+/// Returned by [AdjListGraph::connect_many].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchResult {
+    /// The edges that were created, in input order.
+    pub created: Vec<EdgeID>,
+    /// The position in the input of every edge that could not be connected and why.
+    pub failures: Vec<(usize, GraphError)>,
+}
+impl BatchResult {
+    /// True if every edge was connected.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
 #[derive(Debug, Clone)]
 pub(crate) enum SingleEdgeOrManyEdges {
     Single(EdgeID, Edge),
@@ -44,75 +81,15 @@ impl SingleEdgeOrManyEdges {
 }
 
 impl<T> AdjListGraph<T> {
-    /// Copies the referenced edge and the nodes it connects to the target graph.
-    ///
-    /// To check if a node has been copied, use the `node_if_already_copied` closure.
-    ///
-    /// The return contains the new edge ID and the new node IDs if they were copied.
-    pub(crate) fn copy_edge_and_referenced_nodes<F>(
-        &self,
-        target: &mut Self,
-        edge: EdgeID,
-        node_if_already_copied: F,
-    ) -> Result<EdgeCopyResult, GraphError>
-    where
-        F: Fn(NodeID) -> Option<NodeID>,
-        T: Clone,
-    {
-        let edge = &self.edges[edge.0];
-        let (target_node_a_id, did_create_new_a_node) =
-            self.target_node_or_copy(target, edge.node_a, &node_if_already_copied);
-        let (target_node_b_id, did_create_new_b_node) =
-            self.target_node_or_copy(target, edge.node_b, &node_if_already_copied);
-        let node_a_return = if did_create_new_a_node {
-            Some((edge.node_a, target_node_a_id))
-        } else {
-            None
-        };
-        let node_b_return = if did_create_new_b_node {
-            Some((edge.node_b, target_node_b_id))
-        } else {
-            None
-        };
-        let edge =
-            target.connect_nodes_with_weight(target_node_a_id, target_node_b_id, edge.weight())?;
-
-        Ok(EdgeCopyResult {
-            new_edge_id: edge,
-            node_a: node_a_return,
-            node_b: node_b_return,
-        })
-    }
-    /// Copies the referenced node to the target graph.
-    fn target_node_or_copy<F>(
-        &self,
-        target: &mut Self,
-        node: NodeID,
-        node_if_already_copied: &F,
-    ) -> (NodeID, bool)
-    where
-        T: Clone,
-        F: Fn(NodeID) -> Option<NodeID>,
-    {
-        if let Some(updated_node_id) = node_if_already_copied(node) {
-            return (updated_node_id, false);
-        }
-        let from_node_value_cloned = self[node].value().clone();
-        let new_node = target.add_node(from_node_value_cloned);
-
-        (new_node, true)
-    }
-    /// Returns a list of edges sorted by weight.
+    /// Returns a list of the live edges sorted by weight.
     ///
     /// This is a tuple of the edge's ID and a reference to the edge.
     ///
     /// Note calling enumerate on the iterator will not give the correct id as the edges from this function are sorted.
-    pub(crate) fn get_edges_sorted_by_weight(&self) -> Vec<(EdgeID, &Edge)> {
+    pub(crate) fn get_edges_sorted_by_weight(&self) -> Vec<EdgeRefAndID<'_>> {
         let mut edges = self
-            .edges
-            .iter()
-            .enumerate()
-            .map(|(index, edge)| (EdgeID(index), edge))
+            .edge_ids()
+            .map(|id| (id, &self[id]))
             .collect::<Vec<_>>();
         edges.sort_by_key(|(_, edge)| edge.weight());
         edges
@@ -121,7 +98,7 @@ impl<T> AdjListGraph<T> {
         let mut target: Vec<SingleEdgeOrManyEdges> = Vec::with_capacity(self.edges.len());
 
         for (index, edge) in self.edges.iter().enumerate() {
-            if self.empty_edge_slots.contains(&EdgeID(index)) {
+            if !self.is_edge_alive(EdgeID(index)) {
                 continue;
             }
             let find_item = target
@@ -137,7 +114,40 @@ impl<T> AdjListGraph<T> {
         target.sort_by_key(|item| item.weight());
         target
     }
+    /// True if the slot was removed. Unlike [AdjListGraph::is_node_alive], out of range indexes are not empty.
     pub(crate) fn is_node_empty(&self, node_id: usize) -> bool {
         self.empty_node_slots.contains(&NodeID(node_id))
     }
+    /// True if the slot was removed. Unlike [AdjListGraph::is_edge_alive], out of range indexes are not empty.
+    pub(crate) fn is_edge_empty(&self, edge_id: usize) -> bool {
+        self.empty_edge_slots.contains(&EdgeID(edge_id))
+    }
+    /// The lowest live node ID.
+    pub(crate) fn first_live_node(&self) -> Option<NodeID> {
+        self.node_ids().next()
+    }
+    /// All live node IDs in ascending order.
+    pub(crate) fn live_node_ids(&self) -> Vec<NodeID> {
+        self.node_ids().collect()
+    }
+    /// Returns an error if the node is out of range or has been removed.
+    pub(crate) fn check_node_exists(&self, node: NodeID) -> Result<(), GraphError> {
+        if self.does_node_id_exist(node) {
+            Ok(())
+        } else {
+            Err(GraphError::NodeDoesNotExist { node })
+        }
+    }
+    /// Iterates over the edges of the node.
+    ///
+    /// Each item is the edge, the node on the other side of the edge, and the weight.
+    pub(crate) fn adjacent(
+        &self,
+        node: NodeID,
+    ) -> impl Iterator<Item = (EdgeID, NodeID, u32)> + '_ {
+        self[node].edges.iter().map(move |edge_id| {
+            let edge = &self[edge_id];
+            (*edge_id, edge.other_node(node), edge.weight())
+        })
+    }
 }
@@ -0,0 +1,779 @@
+//! Reads undirected graphs written in the DOT language.
+//!
+//! Covers the parts of the language used by [export_graphiz](crate::adjacency_list::export::graphiz::export_graphiz) and by most other tools:
+//! `strict graph` headers, quoted identifiers, attribute lists, edge chains, anonymous and named subgraphs, ports, and `//`, `/* */` and `#` comments.
+//! Statements can be separated by semicolons, newlines or nothing at all.
+//! The output of `dot -Tdot` can be read back. Layout attributes such as `pos` are ignored and `\N` in a node label is replaced by the node ID.
+use crate::collections::HashMap;
+use crate::prelude::*;
+use thiserror::Error;
+
+use crate::adjacency_list::{AdjListGraph, NodeID};
+use crate::GraphError;
+
+/// Why a DOT file could not be read, along with where the problem is.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{line}:{column}: {kind}")]
+pub struct GraphizParseError {
+    /// Starts at 1.
+    pub line: usize,
+    /// Starts at 1. Counted in characters, not bytes.
+    pub column: usize,
+    pub kind: GraphizParseErrorKind,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum GraphizParseErrorKind {
+    /// `digraph` files are rejected instead of dropping the direction of every edge.
+    #[error("Directed graphs are not supported")]
+    Directed,
+    #[error("Expected {expected}, found {found}")]
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("The string is never closed")]
+    UnterminatedString,
+    #[error("The comment is never closed")]
+    UnterminatedComment,
+    #[error("{0} are not supported")]
+    Unsupported(&'static str),
+    /// The edge could not be added. For example a repeated edge in a graph that is not `strict`.
+    #[error(transparent)]
+    Graph(GraphError),
+}
+/// Parses an undirected DOT graph.
+///
+/// Every node gets its `label` attribute as its value. Nodes without a label get their identifier, with the quotes removed and escapes left verbatim.
+/// Edges get their `weight` attribute as their weight, or their `label` if it is a number, as that is how the exporter writes weights. Otherwise the weight is 0.
+/// `node [label=...]` and `edge [weight=...]` defaults apply to the statements after them in the same subgraph. Every other attribute is ignored.
+///
+/// In a `strict graph` repeated edges are merged and the first weight is kept. Otherwise they are an error, as the graph rejects parallel edges.
+/// `digraph` files return [GraphizParseErrorKind::Directed].
+///
+/// ```
+/// use tux_graph::adjacency_list::import::graphiz::parse_graphiz;
+/// let graph = parse_graphiz(r#"
+///     strict graph {
+///         "New York" -- Boston [weight=306]
+///         Boston -- "New York" // Merged into the first edge
+///     }
+/// "#).unwrap();
+/// assert_eq!(graph.number_of_nodes(), 2);
+/// assert_eq!(graph.weights().map(|(_, weight)| weight).collect::<Vec<_>>(), vec![306]);
+/// ```
+pub fn parse_graphiz(input: &str) -> Result<AdjListGraph<String>, GraphizParseError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        graph: AdjListGraph::default(),
+        ids: HashMap::new(),
+        strict: false,
+        scopes: vec![Defaults::default()],
+    };
+    parser.parse()?;
+    Ok(parser.graph)
+}
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    Quoted(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semicolon,
+    Comma,
+    Equals,
+    Colon,
+    Edge,
+    DirectedEdge,
+    End,
+}
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Id(id) => format!("`{id}`"),
+            Token::Quoted(id) => format!("\"{id}\""),
+            Token::LBrace => "`{`".to_string(),
+            Token::RBrace => "`}`".to_string(),
+            Token::LBracket => "`[`".to_string(),
+            Token::RBracket => "`]`".to_string(),
+            Token::Semicolon => "`;`".to_string(),
+            Token::Comma => "`,`".to_string(),
+            Token::Equals => "`=`".to_string(),
+            Token::Colon => "`:`".to_string(),
+            Token::Edge => "`--`".to_string(),
+            Token::DirectedEdge => "`->`".to_string(),
+            Token::End => "the end of the file".to_string(),
+        }
+    }
+    /// Keywords are case insensitive and can not be quoted.
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Id(id) if id.eq_ignore_ascii_case(keyword))
+    }
+}
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+impl Spanned {
+    fn error(&self, kind: GraphizParseErrorKind) -> GraphizParseError {
+        GraphizParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+    /// Only whitespace has been seen on the current line.
+    line_start: bool,
+}
+impl Lexer {
+    fn peek(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.index + ahead).copied()
+    }
+    fn bump(&mut self) -> Option<char> {
+        let char = self.peek(0)?;
+        self.index += 1;
+        if char == '\n' {
+            self.line += 1;
+            self.column = 1;
+            self.line_start = true;
+        } else {
+            self.column += 1;
+            if !char.is_whitespace() {
+                self.line_start = false;
+            }
+        }
+        Some(char)
+    }
+    fn error(&self, line: usize, column: usize, kind: GraphizParseErrorKind) -> GraphizParseError {
+        GraphizParseError { line, column, kind }
+    }
+    fn skip_line(&mut self) {
+        while self.peek(0).is_some_and(|char| char != '\n') {
+            self.bump();
+        }
+    }
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let mut value = String::new();
+        while let Some(char) = self.peek(0).filter(|char| f(*char)) {
+            value.push(char);
+            self.bump();
+        }
+        value
+    }
+}
+fn is_id_char(char: char) -> bool {
+    char.is_alphanumeric() || char == '_' || !char.is_ascii()
+}
+fn tokenize(input: &str) -> Result<Vec<Spanned>, GraphizParseError> {
+    let mut lexer = Lexer {
+        chars: input.chars().collect(),
+        index: 0,
+        line: 1,
+        column: 1,
+        line_start: true,
+    };
+    let mut tokens = Vec::new();
+    loop {
+        let (line, column) = (lexer.line, lexer.column);
+        let Some(char) = lexer.peek(0) else {
+            tokens.push(Spanned {
+                token: Token::End,
+                line,
+                column,
+            });
+            return Ok(tokens);
+        };
+        let token = match (char, lexer.peek(1)) {
+            (char, _) if char.is_whitespace() => {
+                lexer.bump();
+                continue;
+            }
+            // Lines starting with `#` are C preprocessor output.
+            ('#', _) if lexer.line_start => {
+                lexer.skip_line();
+                continue;
+            }
+            ('/', Some('/')) => {
+                lexer.skip_line();
+                continue;
+            }
+            ('/', Some('*')) => {
+                lexer.bump();
+                lexer.bump();
+                loop {
+                    match (lexer.bump(), lexer.peek(0)) {
+                        (Some('*'), Some('/')) => {
+                            lexer.bump();
+                            break;
+                        }
+                        (Some(_), _) => {}
+                        (None, _) => {
+                            return Err(lexer.error(
+                                line,
+                                column,
+                                GraphizParseErrorKind::UnterminatedComment,
+                            ))
+                        }
+                    }
+                }
+                continue;
+            }
+            ('"', _) => {
+                lexer.bump();
+                let mut value = String::new();
+                loop {
+                    match lexer.bump() {
+                        Some('"') => break,
+                        Some('\\') => match lexer.bump() {
+                            Some('"') => value.push('"'),
+                            // A backslash before a newline continues the string on the next line.
+                            Some('\n') => {}
+                            Some(escaped) => {
+                                value.push('\\');
+                                value.push(escaped);
+                            }
+                            None => break,
+                        },
+                        Some(char) => value.push(char),
+                        None => {
+                            return Err(lexer.error(
+                                line,
+                                column,
+                                GraphizParseErrorKind::UnterminatedString,
+                            ))
+                        }
+                    }
+                }
+                Token::Quoted(value)
+            }
+            ('<', _) => {
+                return Err(lexer.error(
+                    line,
+                    column,
+                    GraphizParseErrorKind::Unsupported("HTML strings"),
+                ))
+            }
+            ('-', Some('-')) => {
+                lexer.bump();
+                lexer.bump();
+                Token::Edge
+            }
+            ('-', Some('>')) => {
+                lexer.bump();
+                lexer.bump();
+                Token::DirectedEdge
+            }
+            ('-' | '.', _) | ('0'..='9', _) => {
+                let mut value = String::new();
+                if char == '-' {
+                    value.push('-');
+                    lexer.bump();
+                }
+                value.push_str(&lexer.take_while(|char| char.is_ascii_digit() || char == '.'));
+                if value == "-" || value.matches('.').count() > 1 {
+                    return Err(lexer.error(
+                        line,
+                        column,
+                        GraphizParseErrorKind::Unexpected {
+                            expected: "a number",
+                            found: format!("`{value}`"),
+                        },
+                    ));
+                }
+                Token::Id(value)
+            }
+            (char, _) if is_id_char(char) => Token::Id(lexer.take_while(is_id_char)),
+            (char, _) => {
+                let token = match char {
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    ';' => Token::Semicolon,
+                    ',' => Token::Comma,
+                    '=' => Token::Equals,
+                    ':' => Token::Colon,
+                    other => {
+                        return Err(lexer.error(
+                            line,
+                            column,
+                            GraphizParseErrorKind::Unexpected {
+                                expected: "a statement",
+                                found: format!("`{other}`"),
+                            },
+                        ))
+                    }
+                };
+                lexer.bump();
+                token
+            }
+        };
+        tokens.push(Spanned {
+            token,
+            line,
+            column,
+        });
+    }
+}
+/// The `node` and `edge` attribute statements of a subgraph.
+#[derive(Debug, Clone, Default)]
+struct Defaults {
+    node_label: Option<String>,
+    edge_weight: Option<u32>,
+}
+type Attributes = Vec<(String, Option<String>)>;
+struct Parser {
+    tokens: Vec<Spanned>,
+    position: usize,
+    graph: AdjListGraph<String>,
+    ids: HashMap<String, NodeID>,
+    strict: bool,
+    /// The innermost subgraph is last.
+    scopes: Vec<Defaults>,
+}
+impl Parser {
+    fn peek(&self) -> &Spanned {
+        &self.tokens[self.position]
+    }
+    fn next(&mut self) -> Spanned {
+        let token = self.tokens[self.position].clone();
+        // The last token is always `End`, so it is never skipped.
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+        token
+    }
+    fn unexpected(&self, expected: &'static str) -> GraphizParseError {
+        let found = self.peek();
+        found.error(GraphizParseErrorKind::Unexpected {
+            expected,
+            found: found.token.describe(),
+        })
+    }
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), GraphizParseError> {
+        if self.peek().token != token {
+            return Err(self.unexpected(expected));
+        }
+        self.next();
+        Ok(())
+    }
+    fn identifier(&mut self, expected: &'static str) -> Result<String, GraphizParseError> {
+        match &self.peek().token {
+            Token::Id(id) | Token::Quoted(id) => {
+                let id = id.clone();
+                self.next();
+                Ok(id)
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+    fn defaults(&mut self) -> &mut Defaults {
+        self.scopes.last_mut().expect("The graph itself is a scope")
+    }
+    fn parse(&mut self) -> Result<(), GraphizParseError> {
+        if self.peek().token.is_keyword("strict") {
+            self.next();
+            self.strict = true;
+        }
+        let header = self.peek();
+        if header.token.is_keyword("digraph") {
+            return Err(header.error(GraphizParseErrorKind::Directed));
+        }
+        if !header.token.is_keyword("graph") {
+            return Err(self.unexpected("`graph`"));
+        }
+        self.next();
+        if matches!(self.peek().token, Token::Id(_) | Token::Quoted(_)) {
+            self.next();
+        }
+        self.expect(Token::LBrace, "`{`")?;
+        self.statements()?;
+        self.expect(Token::RBrace, "`}`")?;
+        self.expect(Token::End, "the end of the file")
+    }
+    /// Parses statements until the closing `}`, which is left for the caller.
+    fn statements(&mut self) -> Result<(), GraphizParseError> {
+        loop {
+            match self.peek().token {
+                Token::RBrace => return Ok(()),
+                Token::End => return Err(self.unexpected("`}`")),
+                Token::Semicolon => {
+                    self.next();
+                }
+                _ => self.statement()?,
+            }
+        }
+    }
+    fn statement(&mut self) -> Result<(), GraphizParseError> {
+        let token = &self.peek().token;
+        if *token == Token::LBrace || token.is_keyword("subgraph") {
+            self.subgraph()?;
+            if matches!(self.peek().token, Token::Edge | Token::DirectedEdge) {
+                return Err(self
+                    .peek()
+                    .error(GraphizParseErrorKind::Unsupported("Subgraphs as edge ends")));
+            }
+            return Ok(());
+        }
+        for kind in ["graph", "node", "edge"] {
+            if token.is_keyword(kind) {
+                self.next();
+                let attributes = self.attributes()?;
+                match kind {
+                    "node" => {
+                        if let Some(label) = label(&attributes) {
+                            self.defaults().node_label = Some(label);
+                        }
+                    }
+                    "edge" => {
+                        if let Some(weight) = weight(&attributes) {
+                            self.defaults().edge_weight = Some(weight);
+                        }
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+        }
+        let id = self.identifier("a statement")?;
+        if self.peek().token == Token::Equals {
+            // A graph attribute such as `layout=neato`.
+            self.next();
+            self.identifier("an attribute value")?;
+            return Ok(());
+        }
+        self.port()?;
+        if !matches!(self.peek().token, Token::Edge | Token::DirectedEdge) {
+            let attributes = self.optional_attributes()?;
+            let label = label(&attributes).map(|label| expand_node_id(&label, &id));
+            let node = self.node(id);
+            if let Some(label) = label {
+                *self.graph[node].value_mut() = label;
+            }
+            return Ok(());
+        }
+        let mut chain = vec![id];
+        let mut operators = Vec::new();
+        while matches!(self.peek().token, Token::Edge | Token::DirectedEdge) {
+            let operator = self.next();
+            if operator.token == Token::DirectedEdge {
+                return Err(operator.error(GraphizParseErrorKind::Directed));
+            }
+            let next = &self.peek().token;
+            if *next == Token::LBrace || next.is_keyword("subgraph") {
+                return Err(self
+                    .peek()
+                    .error(GraphizParseErrorKind::Unsupported("Subgraphs as edge ends")));
+            }
+            chain.push(self.identifier("a node ID")?);
+            self.port()?;
+            operators.push(operator);
+        }
+        let attributes = self.optional_attributes()?;
+        let weight = weight(&attributes)
+            .or(self.defaults().edge_weight)
+            .unwrap_or(0);
+        let nodes: Vec<NodeID> = chain.into_iter().map(|id| self.node(id)).collect();
+        for (pair, operator) in nodes.windows(2).zip(operators) {
+            match self
+                .graph
+                .connect_nodes_with_weight(pair[0], pair[1], weight)
+            {
+                Ok(_) => {}
+                Err(GraphError::NodesAlreadyConnected { .. }) if self.strict => {}
+                Err(error) => return Err(operator.error(GraphizParseErrorKind::Graph(error))),
+            }
+        }
+        Ok(())
+    }
+    fn subgraph(&mut self) -> Result<(), GraphizParseError> {
+        if self.peek().token.is_keyword("subgraph") {
+            self.next();
+            if matches!(self.peek().token, Token::Id(_) | Token::Quoted(_)) {
+                self.next();
+            }
+        }
+        self.expect(Token::LBrace, "`{`")?;
+        let defaults = self.defaults().clone();
+        self.scopes.push(defaults);
+        self.statements()?;
+        self.scopes.pop();
+        self.expect(Token::RBrace, "`}`")
+    }
+    /// Skips a `:port` or `:port:compass` after a node ID.
+    fn port(&mut self) -> Result<(), GraphizParseError> {
+        for _ in 0..2 {
+            if self.peek().token != Token::Colon {
+                break;
+            }
+            self.next();
+            self.identifier("a port")?;
+        }
+        Ok(())
+    }
+    fn optional_attributes(&mut self) -> Result<Attributes, GraphizParseError> {
+        if self.peek().token == Token::LBracket {
+            self.attributes()
+        } else {
+            Ok(Vec::new())
+        }
+    }
+    /// One or more `[name=value, ...]` lists.
+    fn attributes(&mut self) -> Result<Attributes, GraphizParseError> {
+        let mut attributes = Vec::new();
+        self.expect(Token::LBracket, "`[`")?;
+        loop {
+            if self.peek().token == Token::RBracket {
+                self.next();
+                if self.peek().token != Token::LBracket {
+                    return Ok(attributes);
+                }
+                self.next();
+                continue;
+            }
+            let name = self.identifier("an attribute name or `]`")?;
+            let value = if self.peek().token == Token::Equals {
+                self.next();
+                Some(self.identifier("an attribute value")?)
+            } else {
+                None
+            };
+            attributes.push((name, value));
+            if matches!(self.peek().token, Token::Comma | Token::Semicolon) {
+                self.next();
+            }
+        }
+    }
+    /// The node with the ID. Created with the default label of the current subgraph if it does not exist yet.
+    fn node(&mut self, id: String) -> NodeID {
+        if let Some(node) = self.ids.get(&id) {
+            return *node;
+        }
+        let value = match &self.defaults().node_label {
+            Some(label) => expand_node_id(label, &id),
+            None => id.clone(),
+        };
+        let node = self.graph.add_node(value);
+        self.ids.insert(id, node);
+        node
+    }
+}
+fn attribute<'a>(attributes: &'a Attributes, name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .rev()
+        .find(|(key, _)| key == name)
+        .and_then(|(_, value)| value.as_deref())
+}
+fn label(attributes: &Attributes) -> Option<String> {
+    attribute(attributes, "label").map(ToString::to_string)
+}
+/// Replaces the `\N` escape, which Graphviz writes as the default label of every node, with the node ID.
+fn expand_node_id(label: &str, id: &str) -> String {
+    label.replace("\\N", id)
+}
+fn weight(attributes: &Attributes) -> Option<u32> {
+    attribute(attributes, "weight")
+        .and_then(|weight| weight.parse().ok())
+        .or_else(|| attribute(attributes, "label").and_then(|label| label.parse().ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adjacency_list::export::graphiz::{export_graphiz, GraphizSettings};
+    use crate::adjacency_list::test_graphs::example_from_video;
+    use crate::testing::assert_same_graph;
+
+    /// The node values and the `(value, value, weight)` of every edge, with each edge's values in order. Both sorted.
+    fn summary(graph: &AdjListGraph<String>) -> (Vec<String>, Vec<(String, String, u32)>) {
+        let mut nodes: Vec<String> = graph.iter_nodes().map(|(_, value)| value.clone()).collect();
+        nodes.sort();
+        let mut edges: Vec<(String, String, u32)> = graph
+            .unique_edges()
+            .map(|(a, b, weight)| {
+                let (a, b) = (graph[a].value().clone(), graph[b].value().clone());
+                (a.clone().min(b.clone()), a.max(b), weight)
+            })
+            .collect();
+        edges.sort();
+        (nodes, edges)
+    }
+    enum Expected {
+        /// Node values, then `value -- value weight` edges.
+        Graph(
+            &'static [&'static str],
+            &'static [(&'static str, &'static str, u32)],
+        ),
+        Error(usize, usize, GraphizParseErrorKind),
+    }
+    #[test]
+    pub fn fixtures() {
+        let fixtures = [
+            (
+                "exporter.dot",
+                include_str!("../../../test/graphiz/exporter.dot"),
+                Expected::Graph(
+                    &["A", "B", "C"],
+                    &[("A", "B", 1), ("A", "C", 3), ("B", "C", 2)],
+                ),
+            ),
+            (
+                "graphviz_output.dot",
+                include_str!("../../../test/graphiz/graphviz_output.dot"),
+                Expected::Graph(
+                    &["City hub", "a", "b", "c"],
+                    &[("City hub", "b", 0), ("a", "b", 3), ("b", "c", 0)],
+                ),
+            ),
+            (
+                "strict.dot",
+                include_str!("../../../test/graphiz/strict.dot"),
+                Expected::Graph(&["a", "b", "c"], &[("a", "b", 4), ("b", "c", 0)]),
+            ),
+            (
+                "quoted.dot",
+                include_str!("../../../test/graphiz/quoted.dot"),
+                Expected::Graph(
+                    &["Los Angeles", "New York", "San \"Fran\""],
+                    &[
+                        ("Los Angeles", "New York", 2_445),
+                        ("Los Angeles", "San \"Fran\"", 347),
+                    ],
+                ),
+            ),
+            (
+                "comments.dot",
+                include_str!("../../../test/graphiz/comments.dot"),
+                Expected::Graph(
+                    &["a", "b", "c", "d", "e", "f"],
+                    &[("a", "b", 7), ("c", "d", 7), ("e", "f", 7)],
+                ),
+            ),
+            (
+                "newlines.dot",
+                include_str!("../../../test/graphiz/newlines.dot"),
+                Expected::Graph(
+                    &["Hub", "x", "y", "z"],
+                    &[
+                        ("Hub", "x", 1),
+                        ("Hub", "y", 1),
+                        ("x", "y", 5),
+                        ("y", "z", 5),
+                    ],
+                ),
+            ),
+            (
+                "digraph.dot",
+                include_str!("../../../test/graphiz/digraph.dot"),
+                Expected::Error(2, 1, GraphizParseErrorKind::Directed),
+            ),
+            (
+                "parallel.dot",
+                include_str!("../../../test/graphiz/parallel.dot"),
+                Expected::Error(
+                    3,
+                    7,
+                    GraphizParseErrorKind::Graph(GraphError::NodesAlreadyConnected {
+                        node_a: NodeID(1),
+                        node_b: NodeID(0),
+                        edge: crate::adjacency_list::EdgeID(0),
+                    }),
+                ),
+            ),
+            (
+                "html.dot",
+                include_str!("../../../test/graphiz/html.dot"),
+                Expected::Error(2, 14, GraphizParseErrorKind::Unsupported("HTML strings")),
+            ),
+            (
+                "unterminated.dot",
+                include_str!("../../../test/graphiz/unterminated.dot"),
+                Expected::Error(3, 5, GraphizParseErrorKind::UnterminatedComment),
+            ),
+        ];
+        for (name, input, expected) in fixtures {
+            let result = parse_graphiz(input);
+            match expected {
+                Expected::Graph(nodes, edges) => {
+                    let graph = result.unwrap_or_else(|error| panic!("{name}: {error}"));
+                    let expected = (
+                        nodes.iter().map(|node| node.to_string()).collect(),
+                        edges
+                            .iter()
+                            .map(|(a, b, weight)| (a.to_string(), b.to_string(), *weight))
+                            .collect(),
+                    );
+                    assert_eq!(summary(&graph), expected, "{name}");
+                }
+                Expected::Error(line, column, kind) => {
+                    assert_eq!(
+                        result.unwrap_err(),
+                        GraphizParseError { line, column, kind },
+                        "{name}"
+                    );
+                }
+            }
+        }
+    }
+    #[test]
+    pub fn export_round_trip() {
+        let video = example_from_video();
+        let graph = AdjListGraph::from_named_edges(video.unique_edges().map(|(a, b, weight)| {
+            (
+                video[a].value().to_string(),
+                video[b].value().to_string(),
+                weight,
+            )
+        }));
+        let settings = GraphizSettings {
+            show_weights: true,
+            ..Default::default()
+        };
+        let exported = export_graphiz(&graph, &settings);
+        let imported = parse_graphiz(&exported).unwrap();
+        assert_same_graph(&graph, &imported);
+    }
+    #[test]
+    pub fn unexpected_tokens() {
+        assert_eq!(
+            parse_graphiz("graph {\n  a -- ;\n}").unwrap_err(),
+            GraphizParseError {
+                line: 2,
+                column: 8,
+                kind: GraphizParseErrorKind::Unexpected {
+                    expected: "a node ID",
+                    found: "`;`".to_string()
+                }
+            }
+        );
+        assert_eq!(
+            parse_graphiz("graph { a").unwrap_err().to_string(),
+            "1:10: Expected `}`, found the end of the file"
+        );
+        assert_eq!(
+            parse_graphiz("graph { {a b} -- c }").unwrap_err().kind,
+            GraphizParseErrorKind::Unsupported("Subgraphs as edge ends")
+        );
+        assert_eq!(
+            parse_graphiz("graph { label=\"x\n").unwrap_err(),
+            GraphizParseError {
+                line: 1,
+                column: 15,
+                kind: GraphizParseErrorKind::UnterminatedString
+            }
+        );
+    }
+}
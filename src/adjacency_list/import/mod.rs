@@ -0,0 +1,2 @@
+//! Builds graphs from the formats written by [crate::adjacency_list::export].
+pub mod graphiz;
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use core::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::adjacency_list::*;
+/// A node value with a name that can be used to find the node.
+///
+/// Created by [AdjListGraph::add_keyed_node] or by the `keyed;` directive of the graph macro, which uses the node bindings as keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Keyed<T> {
+    pub key: String,
+    pub value: T,
+}
+impl<T: Display> Display for Keyed<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
+}
+impl<T> AdjListGraph<Keyed<T>> {
+    /// Adds a node that can be found with [AdjListGraph::find_by_key].
+    ///
+    /// Keys are not required to be unique. [AdjListGraph::find_by_key] returns the lowest ID with the key.
+    pub fn add_keyed_node(&mut self, key: impl Into<String>, value: T) -> NodeID {
+        self.add_node(Keyed {
+            key: key.into(),
+            value,
+        })
+    }
+    /// Finds the node with the given key.
+    pub fn find_by_key(&self, key: &str) -> Option<NodeID> {
+        self.find_node(|node| node.key == key)
+    }
+}
@@ -2,8 +2,11 @@
 mod edge;
 pub mod export;
 mod graph;
+pub mod import;
+mod keyed;
 mod node;
 
 pub use edge::*;
 pub use graph::*;
+pub use keyed::*;
 pub use node::*;
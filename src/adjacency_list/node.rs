@@ -1,6 +1,6 @@
 use crate::adjacency_list::*;
+use crate::collections::HashSet;
 use crate::utils::macros::id_type;
-use ahash::{HashSet, HashSetExt};
 use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Node<T> {
@@ -14,6 +14,20 @@ impl<T> Node<T> {
             edges: HashSet::new(),
         }
     }
+    /// A node with room for `edges` edges.
+    pub(crate) fn with_capacity(value: T, edges: usize) -> Self {
+        Self {
+            value: Some(value),
+            edges: HashSet::with_capacity(edges),
+        }
+    }
+    /// A removed node. Used to fill slots that are dead from the start.
+    pub(crate) fn dead() -> Self {
+        Self {
+            value: None,
+            edges: HashSet::new(),
+        }
+    }
     /// Removes an edge from the node.
     pub(crate) fn remove_edge(&mut self, edge: EdgeID) {
         self.edges.remove(&edge);
@@ -32,6 +46,9 @@ impl<T> Node<T> {
     pub fn value(&self) -> &T {
         self.value.as_ref().unwrap()
     }
+    pub fn value_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
+    }
     pub fn optional_value(&self) -> Option<&T> {
         self.value.as_ref()
     }
@@ -50,43 +67,6 @@ impl<T> Node<T> {
         }
         self.optional_value() == other.optional_value()
     }
-    /// Checks if this node is truly equal to another node.
-    ///
-    /// Meaning that the each edge in this node exists in the other graph.
-    pub(crate) fn are_nodes_truly_equal<'a, 'b>(
-        &'a self,
-        self_graph: &'a AdjListGraph<T>,
-        other_node: &'b Node<T>,
-        other_graph: &'b AdjListGraph<T>,
-    ) -> bool
-    where
-        T: PartialEq,
-    {
-        if self.edges.len() != other_node.edges.len() {
-            // Different number of edges. Can't be equal.
-            return false;
-        }
-        // Loop through all edges in a and check if they are in b.
-        self.edges.iter().all(|edge| {
-            // Node. No need to check if these edges are valid as they exist in the node meaning they are valid.
-            // If this is not the case, the graph is invalid.
-            let edge = &self_graph.edges[edge.0];
-            let (self_graph_node_a, self_graph_node_b) = edge.node_values(self_graph);
-            // Find an equivalent edge in b.
-            other_node.edges.iter().any(|edge_id| {
-                let edge = &other_graph.edges[edge_id.0];
-                if edge.weight() != edge.weight() {
-                    // Different weight. Can't be equal.
-                    return false;
-                }
-                let (other_graph_node_a, other_graph_node_b) = edge.node_values(other_graph);
-                self_graph_node_a.node_value_eq(other_graph_node_a)
-                    && self_graph_node_b.node_value_eq(other_graph_node_b)
-                    || self_graph_node_a.node_value_eq(other_graph_node_a)
-                        && self_graph_node_b.node_value_eq(other_graph_node_b)
-            })
-        })
-    }
 }
 
 #[derive(Debug, Clone, Copy)]
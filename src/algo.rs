@@ -0,0 +1,48 @@
+//! Algorithms that work with any graph implementing [GraphRead].
+//!
+//! The inherent methods on [AdjListGraph](crate::adjacency_list::AdjListGraph) call into these.
+use core::{fmt::Debug, hash::Hash};
+
+mod budget;
+mod search;
+mod shortest_path;
+mod visit;
+pub use budget::Budget;
+pub(crate) use budget::BudgetTracker;
+pub use search::*;
+pub use shortest_path::*;
+pub use visit::*;
+
+/// Read only access to an undirected weighted graph.
+///
+/// Implement this for your own graph storage to use the functions in [crate::algo].
+pub trait GraphRead {
+    type NodeId: Copy + Eq + Ord + Hash + Debug;
+    type EdgeId: Copy + Eq + Debug;
+    /// Iterates over every node in the graph.
+    fn node_ids(&self) -> impl Iterator<Item = Self::NodeId> + '_;
+    /// Iterates over the edges of the node and the node on the other side of each edge.
+    ///
+    /// A self-loop yields the node itself.
+    fn neighbors(
+        &self,
+        node: Self::NodeId,
+    ) -> impl Iterator<Item = (Self::EdgeId, Self::NodeId)> + '_;
+    /// The weight of the edge. `None` if the edge does not exist.
+    fn edge_weight(&self, edge: Self::EdgeId) -> Option<u32>;
+    fn number_of_nodes(&self) -> usize;
+    fn number_of_edges(&self) -> usize;
+    fn contains_node(&self, node: Self::NodeId) -> bool;
+}
+/// The neighbors of the node along with the weight of the connecting edge.
+pub(crate) fn weighted_neighbors<G: GraphRead>(
+    graph: &G,
+    node: G::NodeId,
+) -> impl Iterator<Item = (G::NodeId, u32)> + '_ {
+    graph.neighbors(node).map(move |(edge, next)| {
+        let weight = graph
+            .edge_weight(edge)
+            .expect("neighbors returned an edge that does not exist");
+        (next, weight)
+    })
+}
@@ -0,0 +1,119 @@
+use crate::GraphError;
+
+/// Limits for the algorithms whose running time can grow exponentially with the size of the graph.
+///
+/// A step is one unit of search work, such as entering a branch. The same graph and budget always take the same number of steps,
+/// so [Budget::max_steps] gives reproducible results. The deadline is checked every [Budget::DEADLINE_CHECK_INTERVAL] steps.
+///
+/// The default budget is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// The number of steps the search may take.
+    pub max_steps: Option<u64>,
+    /// The point in time after which the search gives up.
+    #[cfg(feature = "std")]
+    pub deadline: Option<std::time::Instant>,
+}
+impl Budget {
+    /// How often the deadline is compared against the clock.
+    pub const DEADLINE_CHECK_INTERVAL: u64 = 64;
+    /// A budget without any limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+    /// A budget of `max_steps` steps.
+    // Without `std` there is no deadline to fill in.
+    #[allow(clippy::needless_update)]
+    pub fn steps(max_steps: u64) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+            ..Self::default()
+        }
+    }
+    /// A budget that runs out at `deadline`.
+    #[cfg(feature = "std")]
+    pub fn until(deadline: std::time::Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..Self::default()
+        }
+    }
+}
+/// Counts the steps taken against a [Budget].
+#[derive(Debug, Clone)]
+pub(crate) struct BudgetTracker {
+    budget: Budget,
+    steps_taken: u64,
+}
+impl BudgetTracker {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            steps_taken: 0,
+        }
+    }
+    /// Takes a step.
+    ///
+    /// Returns [GraphError::BudgetExhausted] instead if the budget has run out.
+    pub fn step(&mut self) -> Result<(), GraphError> {
+        let exhausted = GraphError::BudgetExhausted {
+            steps_taken: self.steps_taken,
+        };
+        if self
+            .budget
+            .max_steps
+            .is_some_and(|max_steps| self.steps_taken >= max_steps)
+        {
+            return Err(exhausted);
+        }
+        // `is_multiple_of` needs Rust 1.87, and the crate does not require a recent compiler.
+        #[allow(clippy::manual_is_multiple_of)]
+        #[cfg(feature = "std")]
+        if self.steps_taken % Budget::DEADLINE_CHECK_INTERVAL == 0
+            && self
+                .budget
+                .deadline
+                .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+        {
+            return Err(exhausted);
+        }
+        self.steps_taken += 1;
+        Ok(())
+    }
+    pub fn steps_taken(&self) -> u64 {
+        self.steps_taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Budget, BudgetTracker};
+    use crate::GraphError;
+
+    #[test]
+    pub fn max_steps() {
+        let mut tracker = BudgetTracker::new(Budget::steps(2));
+        assert_eq!(tracker.step(), Ok(()));
+        assert_eq!(tracker.step(), Ok(()));
+        assert_eq!(
+            tracker.step(),
+            Err(GraphError::BudgetExhausted { steps_taken: 2 })
+        );
+        assert_eq!(tracker.steps_taken(), 2);
+
+        let mut tracker = BudgetTracker::new(Budget::unlimited());
+        for _ in 0..1000 {
+            tracker.step().unwrap();
+        }
+        assert_eq!(tracker.steps_taken(), 1000);
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn deadline() {
+        let mut tracker = BudgetTracker::new(Budget::until(std::time::Instant::now()));
+        assert_eq!(
+            tracker.step(),
+            Err(GraphError::BudgetExhausted { steps_taken: 0 })
+        );
+    }
+}
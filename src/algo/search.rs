@@ -0,0 +1,321 @@
+use crate::collections::{HashMap, HashSet};
+use crate::prelude::*;
+use alloc::collections::{BinaryHeap, VecDeque};
+use core::cmp::Reverse;
+
+use crate::utils::logging::trace;
+
+use super::shortest_path::build_path;
+use super::{weighted_neighbors, GraphRead};
+
+/// Breadth First Search. Returns the nodes in the order they are visited.
+///
+/// `None` if `start` does not exist.
+pub fn bfs<G: GraphRead>(graph: &G, start: G::NodeId) -> Option<Vec<G::NodeId>> {
+    if !graph.contains_node(start) {
+        return None;
+    }
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut queue = VecDeque::new();
+    visited.insert(start);
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for (_, next) in graph.neighbors(node) {
+            if visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    Some(order)
+}
+/// The number of edges on the shortest path from `start` to every reachable node, ignoring weights.
+///
+/// `None` if `start` does not exist.
+pub fn bfs_distances<G: GraphRead>(
+    graph: &G,
+    start: G::NodeId,
+) -> Option<HashMap<G::NodeId, usize>> {
+    if !graph.contains_node(start) {
+        return None;
+    }
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(start, 0);
+    queue.push_back((start, 0));
+    while let Some((node, distance)) = queue.pop_front() {
+        for (_, next) in graph.neighbors(node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next, distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    Some(distances)
+}
+/// Finds every node that is at most `hops` edges away from `start`.
+///
+/// The result is sorted by the number of hops and includes `start`. `None` if `start` does not exist.
+pub fn neighbors_within_hops<G: GraphRead>(
+    graph: &G,
+    start: G::NodeId,
+    hops: usize,
+) -> Option<Vec<G::NodeId>> {
+    if !graph.contains_node(start) {
+        return None;
+    }
+    let mut visited = HashSet::new();
+    let mut found = Vec::new();
+    let mut queue = VecDeque::new();
+    visited.insert(start);
+    queue.push_back((start, 0));
+    while let Some((node, distance)) = queue.pop_front() {
+        found.push(node);
+        if distance == hops {
+            continue;
+        }
+        for (_, next) in graph.neighbors(node) {
+            if visited.insert(next) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    Some(found)
+}
+/// Finds every node whose cheapest path from `start` costs at most `max_total_weight`.
+///
+/// The result is sorted by cost and includes `start` with a cost of 0. `None` if `start` does not exist.
+pub fn reachable_within<G: GraphRead>(
+    graph: &G,
+    start: G::NodeId,
+    max_total_weight: u64,
+) -> Option<Vec<(G::NodeId, u64)>> {
+    if !graph.contains_node(start) {
+        return None;
+    }
+    let mut costs: HashMap<G::NodeId, u64> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut reachable = Vec::new();
+    let mut queue = BinaryHeap::new();
+    costs.insert(start, 0);
+    queue.push(Reverse((0u64, start)));
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if !settled.insert(node) {
+            continue;
+        }
+        reachable.push((node, cost));
+        for (next, weight) in weighted_neighbors(graph, node) {
+            let next_cost = cost + weight as u64;
+            if settled.contains(&next) || next_cost > max_total_weight {
+                // Past the budget. The frontier stops here.
+                continue;
+            }
+            if costs.get(&next).is_none_or(|current| next_cost < *current) {
+                costs.insert(next, next_cost);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    Some(reachable)
+}
+/// Groups the nodes into connected components.
+///
+/// Every component is sorted and the components are sorted by their lowest node.
+pub fn connected_components<G: GraphRead>(graph: &G) -> Vec<Vec<G::NodeId>> {
+    let mut nodes: Vec<G::NodeId> = graph.node_ids().collect();
+    nodes.sort_unstable();
+    let mut visited = HashSet::new();
+    let mut components = Vec::new();
+    for start in nodes {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            component.push(node);
+            for (_, next) in graph.neighbors(node) {
+                if visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    components
+}
+/// Depth First Search for the first node matching `f`.
+///
+/// Returns the path from `start` to the matching node. `None` if `start` does not exist or nothing matches.
+pub fn dfs<G, F>(graph: &G, start: G::NodeId, f: F) -> Option<Vec<G::NodeId>>
+where
+    G: GraphRead,
+    F: Fn(G::NodeId) -> bool,
+{
+    if !graph.contains_node(start) {
+        return None;
+    }
+    let mut visited = HashSet::new();
+    let mut path = vec![];
+    if dfs_inner(graph, start, &mut visited, &mut path, &f) {
+        Some(path)
+    } else {
+        None
+    }
+}
+/// Breadth First Search for the first node matching `f`.
+///
+/// Returns the path from `start` to the matching node. The path has the fewest edges of any path to a matching node.
+/// `None` if `start` does not exist or nothing matches.
+pub fn bfs_find<G, F>(graph: &G, start: G::NodeId, f: F) -> Option<Vec<G::NodeId>>
+where
+    G: GraphRead,
+    F: Fn(G::NodeId) -> bool,
+{
+    if !graph.contains_node(start) {
+        return None;
+    }
+    let mut visited = HashSet::new();
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    visited.insert(start);
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        if f(node) {
+            return Some(build_path(&parents, node));
+        }
+        for (_, next) in graph.neighbors(node) {
+            if visited.insert(next) {
+                parents.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+fn dfs_inner<G, F>(
+    graph: &G,
+    node: G::NodeId,
+    visited: &mut HashSet<G::NodeId>,
+    path: &mut Vec<G::NodeId>,
+    f: &F,
+) -> bool
+where
+    G: GraphRead,
+    F: Fn(G::NodeId) -> bool,
+{
+    if !visited.insert(node) {
+        return false;
+    }
+    path.push(node);
+    if f(node) {
+        return true;
+    }
+    for (_, next) in graph.neighbors(node) {
+        trace!(?next, ?path, "DFS inner");
+        if dfs_inner(graph, next, visited, path, f) {
+            return true;
+        }
+    }
+    path.pop();
+    false
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adjacency_list::{test_graphs::example_from_video, NodeID};
+
+    /// A minimal graph storage to check the algorithms only depend on [GraphRead].
+    ///
+    /// `edges[i]` is `(a, b, weight)`. Nodes are `0..nodes`.
+    struct EdgeListGraph {
+        nodes: usize,
+        edges: Vec<(usize, usize, u32)>,
+    }
+    impl GraphRead for EdgeListGraph {
+        type NodeId = usize;
+        type EdgeId = usize;
+        fn node_ids(&self) -> impl Iterator<Item = usize> + '_ {
+            0..self.nodes
+        }
+        fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+            self.edges
+                .iter()
+                .enumerate()
+                .filter_map(move |(id, (a, b, _))| match (*a == node, *b == node) {
+                    (true, _) => Some((id, *b)),
+                    (_, true) => Some((id, *a)),
+                    _ => None,
+                })
+        }
+        fn edge_weight(&self, edge: usize) -> Option<u32> {
+            self.edges.get(edge).map(|(_, _, weight)| *weight)
+        }
+        fn number_of_nodes(&self) -> usize {
+            self.nodes
+        }
+        fn number_of_edges(&self) -> usize {
+            self.edges.len()
+        }
+        fn contains_node(&self, node: usize) -> bool {
+            node < self.nodes
+        }
+    }
+    fn as_edge_list(graph: &crate::adjacency_list::AdjListGraph<char>) -> EdgeListGraph {
+        EdgeListGraph {
+            nodes: graph.number_of_nodes(),
+            edges: graph
+                .edge_ids()
+                .map(|id| {
+                    let edge = &graph[id];
+                    (edge.node_a.0, edge.node_b.0, edge.weight())
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    pub fn bfs_matches_between_graph_types() {
+        let graph = example_from_video();
+        let edge_list = as_edge_list(&graph);
+        assert_eq!(
+            edge_list.number_of_edges(),
+            GraphRead::number_of_edges(&graph)
+        );
+        for start in graph.node_ids() {
+            let adj_order: Vec<usize> = bfs(&graph, start).unwrap().iter().map(|id| id.0).collect();
+            let mut edge_list_order = bfs(&edge_list, start.0).unwrap();
+            // Both visit the same nodes. Neighbor order is storage specific.
+            assert_eq!(adj_order[0], edge_list_order[0]);
+            let mut adj_sorted = adj_order.clone();
+            adj_sorted.sort_unstable();
+            edge_list_order.sort_unstable();
+            assert_eq!(adj_sorted, edge_list_order);
+
+            let adj_distances = bfs_distances(&graph, start).unwrap();
+            let edge_list_distances = bfs_distances(&edge_list, start.0).unwrap();
+            for (node, distance) in adj_distances {
+                assert_eq!(edge_list_distances[&node.0], distance);
+            }
+        }
+        assert_eq!(bfs(&graph, NodeID(7)), None);
+        assert_eq!(bfs(&edge_list, 7), None);
+    }
+    #[test]
+    pub fn reachable_within_matches_between_graph_types() {
+        let graph = example_from_video();
+        let edge_list = as_edge_list(&graph);
+        let adj: Vec<(usize, u64)> = reachable_within(&graph, NodeID(0), 10)
+            .unwrap()
+            .into_iter()
+            .map(|(id, cost)| (id.0, cost))
+            .collect();
+        assert_eq!(adj, reachable_within(&edge_list, 0, 10).unwrap());
+        assert_eq!(
+            dfs(&edge_list, 0, |node| node == 6).map(|path| path.len() > 1),
+            Some(true)
+        );
+    }
+}
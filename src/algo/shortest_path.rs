@@ -0,0 +1,129 @@
+use crate::collections::{HashMap, HashSet};
+use crate::prelude::*;
+use alloc::collections::BinaryHeap;
+use core::cmp::Reverse;
+
+use super::{weighted_neighbors, GraphRead};
+
+/// Finds the cheapest path between two nodes where passing through a node also has a cost.
+///
+/// `node_cost(n)` is added when the node `n` is settled. This includes `from` and `to`.
+///
+/// Returns the path and its total cost. `None` if either node does not exist or there is no path.
+pub fn dijkstra_with_node_costs<G, C>(
+    graph: &G,
+    from: G::NodeId,
+    to: G::NodeId,
+    node_cost: C,
+) -> Option<(Vec<G::NodeId>, u32)>
+where
+    G: GraphRead,
+    C: Fn(G::NodeId) -> u32,
+{
+    if !graph.contains_node(from) || !graph.contains_node(to) {
+        return None;
+    }
+    let mut costs: HashMap<G::NodeId, u32> = HashMap::new();
+    let mut parents: HashMap<G::NodeId, G::NodeId> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    let start_cost = node_cost(from);
+    costs.insert(from, start_cost);
+    queue.push(Reverse((start_cost, from)));
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if !settled.insert(node) {
+            continue;
+        }
+        if node == to {
+            return Some((build_path(&parents, to), cost));
+        }
+        for (next, weight) in weighted_neighbors(graph, node) {
+            if settled.contains(&next) {
+                continue;
+            }
+            let next_cost = cost.saturating_add(weight).saturating_add(node_cost(next));
+            if costs.get(&next).is_none_or(|current| next_cost < *current) {
+                costs.insert(next, next_cost);
+                parents.insert(next, node);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    None
+}
+/// Finds the path between two nodes whose lightest edge is as heavy as possible, also called the bottleneck path.
+///
+/// Returns the weight of the lightest edge and the path. A path from a node to itself has no edges and a width of [u32::MAX].
+/// `None` if either node does not exist or there is no path. If several paths are as wide, any of them is returned.
+pub fn widest_path<G>(graph: &G, from: G::NodeId, to: G::NodeId) -> Option<(u32, Vec<G::NodeId>)>
+where
+    G: GraphRead,
+{
+    // Widening the lightest edge is the same as narrowing the heaviest edge of the flipped weights.
+    bottleneck_path(graph, from, to, |weight| u32::MAX - weight)
+        .map(|(cost, path)| (u32::MAX - cost, path))
+}
+/// Finds the path between two nodes whose heaviest edge is as light as possible.
+///
+/// Returns the weight of the heaviest edge and the path. A path from a node to itself has no edges and a weight of 0.
+/// `None` if either node does not exist or there is no path. If several paths are as light, any of them is returned.
+pub fn minimax_path<G>(graph: &G, from: G::NodeId, to: G::NodeId) -> Option<(u32, Vec<G::NodeId>)>
+where
+    G: GraphRead,
+{
+    bottleneck_path(graph, from, to, |weight| weight)
+}
+/// Dijkstra where the cost of a path is its most expensive edge instead of the sum.
+fn bottleneck_path<G>(
+    graph: &G,
+    from: G::NodeId,
+    to: G::NodeId,
+    edge_cost: impl Fn(u32) -> u32,
+) -> Option<(u32, Vec<G::NodeId>)>
+where
+    G: GraphRead,
+{
+    if !graph.contains_node(from) || !graph.contains_node(to) {
+        return None;
+    }
+    let mut costs: HashMap<G::NodeId, u32> = HashMap::new();
+    let mut parents: HashMap<G::NodeId, G::NodeId> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    costs.insert(from, 0);
+    queue.push(Reverse((0, from)));
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if !settled.insert(node) {
+            continue;
+        }
+        if node == to {
+            return Some((cost, build_path(&parents, to)));
+        }
+        for (next, weight) in weighted_neighbors(graph, node) {
+            if settled.contains(&next) {
+                continue;
+            }
+            let next_cost = cost.max(edge_cost(weight));
+            if costs.get(&next).is_none_or(|current| next_cost < *current) {
+                costs.insert(next, next_cost);
+                parents.insert(next, node);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    None
+}
+/// Walks the parents back from the target to build the path.
+pub(crate) fn build_path<N>(parents: &HashMap<N, N>, target: N) -> Vec<N>
+where
+    N: Copy + Eq + core::hash::Hash,
+{
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(parent) = parents.get(&current) {
+        path.push(*parent);
+        current = *parent;
+    }
+    path.reverse();
+    path
+}
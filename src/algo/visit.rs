@@ -0,0 +1,118 @@
+use crate::collections::HashMap;
+use crate::prelude::*;
+
+use super::GraphRead;
+
+/// Hooks called by [depth_first_visit] as the traversal happens.
+///
+/// Every method does nothing by default, so only the events you care about need to be implemented.
+pub trait Visitor<N, E> {
+    /// Called the first time a node is reached.
+    fn on_discover_node(&mut self, _node: N) {}
+    /// Called once every node reachable through the node has been visited.
+    fn on_finish_node(&mut self, _node: N) {}
+    /// Called for an edge that reaches an undiscovered node. These edges form the DFS tree.
+    fn on_tree_edge(&mut self, _edge: E, _from: N, _to: N) {}
+    /// Called for an edge that leads back to a node that is still being visited. Each of these closes a cycle.
+    ///
+    /// Every back edge is reported once. Self-loops are back edges.
+    fn on_back_edge(&mut self, _edge: E, _from: N, _to: N) {}
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    OnStack,
+    Finished,
+}
+/// Iterative depth first traversal from `start` that reports every event to the visitor.
+///
+/// Only the nodes reachable from `start` are visited. Returns `false` if `start` does not exist.
+pub fn depth_first_visit<G, V>(graph: &G, start: G::NodeId, visitor: &mut V) -> bool
+where
+    G: GraphRead,
+    V: Visitor<G::NodeId, G::EdgeId>,
+{
+    if !graph.contains_node(start) {
+        return false;
+    }
+    let mut states = HashMap::new();
+    states.insert(start, VisitState::OnStack);
+    visitor.on_discover_node(start);
+    // The node, the edge used to reach it and its remaining neighbors.
+    let mut stack = vec![(start, None, graph.neighbors(start))];
+    while let Some((node, parent_edge, neighbors)) = stack.last_mut() {
+        let node = *node;
+        let Some((edge, next)) = neighbors.next() else {
+            states.insert(node, VisitState::Finished);
+            visitor.on_finish_node(node);
+            stack.pop();
+            continue;
+        };
+        if *parent_edge == Some(edge) {
+            continue;
+        }
+        match states.get(&next) {
+            None => {
+                visitor.on_tree_edge(edge, node, next);
+                states.insert(next, VisitState::OnStack);
+                visitor.on_discover_node(next);
+                stack.push((next, Some(edge), graph.neighbors(next)));
+            }
+            Some(VisitState::OnStack) => visitor.on_back_edge(edge, node, next),
+            // Already reported as a back edge from the other side.
+            Some(VisitState::Finished) => {}
+        }
+    }
+    true
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adjacency_list::*;
+
+    #[derive(Default)]
+    struct Counter {
+        discovered: Vec<NodeID>,
+        finished: Vec<NodeID>,
+        tree_edges: usize,
+        back_edges: usize,
+    }
+    impl Visitor<NodeID, EdgeID> for Counter {
+        fn on_discover_node(&mut self, node: NodeID) {
+            self.discovered.push(node);
+        }
+        fn on_finish_node(&mut self, node: NodeID) {
+            self.finished.push(node);
+        }
+        fn on_tree_edge(&mut self, _edge: EdgeID, _from: NodeID, _to: NodeID) {
+            self.tree_edges += 1;
+        }
+        fn on_back_edge(&mut self, _edge: EdgeID, _from: NodeID, _to: NodeID) {
+            self.back_edges += 1;
+        }
+    }
+
+    #[test]
+    pub fn events_match_component() {
+        let mut graph = AdjListGraph::default();
+        let [a, b, c, d, e] = graph.add_nodes_from_sized_array(['A', 'B', 'C', 'D', 'E']);
+        // A triangle with a tail and a self-loop. `e` is in another component.
+        graph.connect_nodes(a, b).unwrap();
+        graph.connect_nodes(b, c).unwrap();
+        graph.connect_nodes(c, a).unwrap();
+        graph.connect_nodes(c, d).unwrap();
+        graph.connect_nodes(d, d).unwrap();
+        graph.connect_nodes(e, e).unwrap();
+
+        let mut counter = Counter::default();
+        assert!(depth_first_visit(&graph, a, &mut counter));
+        assert_eq!(counter.discovered.len(), 4);
+        assert_eq!(counter.discovered[0], a);
+        assert_eq!(counter.finished.len(), 4);
+        assert_eq!(counter.finished.last(), Some(&a));
+        assert_eq!(counter.tree_edges, 3);
+        // The triangle and the self-loop.
+        assert_eq!(counter.back_edges, 2);
+
+        assert!(!depth_first_visit(&graph, NodeID(9), &mut counter));
+    }
+}
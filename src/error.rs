@@ -0,0 +1,113 @@
+use thiserror::Error;
+
+use crate::adjacency_list::{EdgeID, NodeID};
+use crate::prelude::*;
+
+/// Errors returned by the fallible graph operations.
+///
+/// New variants may be added without a major version bump. Use [`GraphError::code`] to map errors without matching on every variant.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum GraphError {
+    #[error("Nodes {node_a:?} and {node_b:?} already have a connected edge. Edge ID: {edge:?}")]
+    NodesAlreadyConnected {
+        node_a: NodeID,
+        node_b: NodeID,
+        edge: EdgeID,
+    },
+    #[error("Node {node:?} does not exist or has been removed")]
+    NodeDoesNotExist { node: NodeID },
+    #[error("Node {node:?} is already in use")]
+    SlotOccupied { node: NodeID },
+    #[error("Edge {edge:?} does not exist or has been removed")]
+    EdgeDoesNotExist { edge: EdgeID },
+    #[error("Node {node:?} references an edge that does not exist")]
+    InvalidNode { node: NodeID },
+    #[error("Edge {edge:?} references a node that does not exist")]
+    InvalidEdge { edge: EdgeID },
+    #[error("Edges {edge_a:?} and {edge_b:?} connect the same nodes")]
+    ParallelEdges { edge_a: EdgeID, edge_b: EdgeID },
+    #[error("The graph is disconnected. It has {components} components")]
+    Disconnected { components: usize },
+    #[error("Node {node:?} can not be connected to itself. The graph policy rejects self loops")]
+    SelfLoopNotAllowed { node: NodeID },
+    #[error("{from} lists {to} as a neighbor with weight {weight}, but {to} does not list {from} with the same weight")]
+    AsymmetricNameMap {
+        from: String,
+        to: String,
+        weight: u32,
+    },
+    #[error("Nodes {node_a:?} and {node_b:?} are both mapped to {target:?}")]
+    RelabelCollision {
+        node_a: NodeID,
+        node_b: NodeID,
+        target: NodeID,
+    },
+    #[error("Node {node:?} is missing from the mapping")]
+    MissingFromMapping { node: NodeID },
+    #[error("The search ran out of budget after {steps_taken} steps")]
+    BudgetExhausted { steps_taken: u64 },
+    #[error("Node {node:?} has no position. The positions are too short")]
+    MissingPosition { node: NodeID },
+}
+impl GraphError {
+    /// A stable identifier for the error.
+    ///
+    /// Unlike the display message, this will not change between versions.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GraphError::NodesAlreadyConnected { .. } => "nodes_already_connected",
+            GraphError::NodeDoesNotExist { .. } => "node_does_not_exist",
+            GraphError::SlotOccupied { .. } => "slot_occupied",
+            GraphError::EdgeDoesNotExist { .. } => "edge_does_not_exist",
+            GraphError::InvalidNode { .. } => "invalid_node",
+            GraphError::InvalidEdge { .. } => "invalid_edge",
+            GraphError::ParallelEdges { .. } => "parallel_edges",
+            GraphError::Disconnected { .. } => "disconnected",
+            GraphError::AsymmetricNameMap { .. } => "asymmetric_name_map",
+            GraphError::SelfLoopNotAllowed { .. } => "self_loop_not_allowed",
+            GraphError::RelabelCollision { .. } => "relabel_collision",
+            GraphError::MissingFromMapping { .. } => "missing_from_mapping",
+            GraphError::BudgetExhausted { .. } => "budget_exhausted",
+            GraphError::MissingPosition { .. } => "missing_position",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adjacency_list::*;
+    use crate::GraphError;
+
+    #[test]
+    pub fn already_connected_error() {
+        let mut graph = AdjListGraph::default();
+        let [a, b] = graph.add_nodes_from_sized_array(["A", "B"]);
+        let edge = graph.connect_nodes(a, b).unwrap();
+
+        let error = graph.connect_nodes(a, b).unwrap_err();
+        assert_eq!(
+            error,
+            GraphError::NodesAlreadyConnected {
+                node_a: a,
+                node_b: b,
+                edge
+            }
+        );
+        assert_eq!(error.code(), "nodes_already_connected");
+    }
+    #[test]
+    pub fn wildcard_match() {
+        let error = GraphError::NodesAlreadyConnected {
+            node_a: NodeID(0),
+            node_b: NodeID(1),
+            edge: EdgeID(0),
+        };
+        #[allow(unreachable_patterns)]
+        let edge = match error {
+            GraphError::NodesAlreadyConnected { edge, .. } => Some(edge),
+            _ => None,
+        };
+        assert_eq!(edge, Some(EdgeID(0)));
+    }
+}
@@ -1,8 +1,30 @@
-use adjacency_list::EdgeID;
-use thiserror::Error;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
 
 pub mod adjacency_list;
+pub mod algo;
+mod error;
+#[cfg(test)]
+mod serde_shapes;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub(crate) mod utils;
+/// The hash based collections used by the graphs.
+///
+/// These come from `hashbrown` so they are available without `std`.
+pub mod collections {
+    pub use hashbrown::{HashMap, HashSet};
+}
+/// The `alloc` types that are normally in the `std` prelude.
+pub(crate) mod prelude {
+    pub use alloc::{
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+}
+pub use error::GraphError;
 /// Graph creation macro.
 ///
 /// ```rust
@@ -18,20 +40,44 @@ pub(crate) mod utils;
 ///   a -- c;
 /// };
 /// ```
+///
+/// Nodes can also be declared after the edges that use them. Connecting a node that is never declared is a compile error.
 pub use tux_graph_macros::graph;
 /// Graph creation macro without importing the graph types.
 ///
 /// This is mainly used inside the actual crate for testing purposes.
 #[doc(hidden)]
 pub use tux_graph_macros::graph_no_import;
-#[derive(Debug, Error)]
-pub enum GraphError {
-    #[error("Nodes already have a connected edge. Edge ID: {0:?}")]
-    NodesAlreadyConnected(EdgeID),
-}
+/// Graph creation macro that builds the graph with a single [AdjListGraph::from_raw_parts](adjacency_list::AdjListGraph::from_raw_parts) call.
+///
+/// Takes the same input as [graph!]. Node IDs are assigned in declaration order and parallel edges or undeclared nodes are compile errors,
+/// so nothing is checked at runtime. Nodes are not bound to variables.
+///
+/// ```rust
+/// use tux_graph::static_graph;
+///
+/// let graph = static_graph! {
+///   a [value='a'];
+///   b [value='b'];
+///   c [value='c'];
+///
+///   a -- b [weight=1];
+///   b -- c [weight=2];
+///   a -- c;
+/// };
+/// assert_eq!(graph, tux_graph::graph! {
+///   a [value='a'];
+///   b [value='b'];
+///   c [value='c'];
+///
+///   a -- b [weight=1];
+///   b -- c [weight=2];
+///   a -- c;
+/// });
+/// ```
+pub use tux_graph_macros::static_graph;
 
 #[cfg(test)]
-
 mod macro_tests {
 
     use tux_graph_macros::graph_no_import;
@@ -51,4 +97,31 @@ mod macro_tests {
         assert_eq!(graph.number_of_nodes(), 3);
         assert_eq!(graph.number_of_edges(), 3);
     }
+    #[test]
+    fn test_weight_type_directive() {
+        const BASE: u32 = 3;
+        let graph: AdjListGraph<char> = graph_no_import! {
+            weight_type = u32;
+            a [value='a'];
+            b [value='b'];
+            a -- b [weight=BASE * 2];
+        };
+        assert_eq!(graph.weights().map(|(_, weight)| weight).sum::<u32>(), 6);
+    }
+    #[test]
+    fn test_keyed_directive() {
+        let graph = graph_no_import! {
+            keyed;
+            NYC [value=8_300_000];
+            BOS [value=650_000];
+            NYC -- BOS [weight=306];
+        };
+        let nyc = graph.find_by_key("NYC").unwrap();
+        assert_eq!(graph[nyc].value().value, 8_300_000);
+        assert_eq!(
+            graph.connection(nyc, graph.find_by_key("BOS").unwrap()),
+            Some((crate::adjacency_list::EdgeID(0), 306))
+        );
+        assert_eq!(graph.find_by_key("LAX"), None);
+    }
 }
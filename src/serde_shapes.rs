@@ -0,0 +1,81 @@
+//! The JSON shapes of the public result types.
+//!
+//! Other programs read these. A failure here means the serialized format changed.
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tux_graph_macros::graph_no_import;
+
+use crate::adjacency_list::*;
+use crate::testing::GraphDiff;
+
+fn triangle() -> AdjListGraph<char> {
+    graph_no_import! {
+        a [value = 'A'];
+        b [value = 'B'];
+        c [value = 'C'];
+
+        a -- b [weight = 1];
+        b -- c [weight = 2];
+        c -- a [weight = 4];
+    }
+}
+#[test]
+pub fn ids() {
+    assert_eq!(serde_json::to_value(NodeID(3)).unwrap(), json!(3));
+    assert_eq!(serde_json::to_value(EdgeID(2)).unwrap(), json!(2));
+    let id: NodeID = serde_json::from_value(json!(5)).unwrap();
+    assert_eq!(id, NodeID(5));
+}
+#[test]
+pub fn path() {
+    let graph = triangle();
+    let path = graph.dijkstra_path(NodeID(0), NodeID(2)).unwrap();
+    assert_eq!(
+        serde_json::to_value(&path).unwrap(),
+        json!({ "nodes": [0, 1, 2], "edges": [0, 1], "total_weight": 3 })
+    );
+}
+#[test]
+pub fn components() {
+    let mut graph = triangle();
+    graph.add_node('D');
+    assert_eq!(
+        serde_json::to_value(graph.connected_components()).unwrap(),
+        json!([[0, 1, 2], [3]])
+    );
+}
+#[test]
+pub fn graph_diff() {
+    let expected = triangle();
+    let mut actual = triangle();
+    actual.remove_node(NodeID(2));
+    actual.remove_dead_values();
+    let diff = GraphDiff::new(&expected, &actual);
+    let value = serde_json::to_value(&diff).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "missing_nodes": ['C'],
+            "extra_nodes": [],
+            "missing_edges": [['A', 'C', 4], ['B', 'C', 2]],
+            "extra_edges": []
+        })
+    );
+    let round_trip: GraphDiff<char> = serde_json::from_value(value).unwrap();
+    assert_eq!(round_trip, diff);
+}
+#[test]
+pub fn policy_and_keyed() {
+    assert_eq!(
+        serde_json::to_value(GraphPolicy::default()).unwrap(),
+        json!({ "self_loops": "Allow", "parallel_edges": "Reject" })
+    );
+    let keyed = Keyed {
+        key: "NYC".to_string(),
+        value: 1,
+    };
+    assert_eq!(
+        serde_json::to_value(keyed).unwrap(),
+        json!({ "key": "NYC", "value": 1 })
+    );
+}
@@ -0,0 +1,215 @@
+//! Helpers for checking that a graph survives a round trip through one of its formats,
+//! and that generated text matches a checked-in [golden] file.
+//!
+//! Enable the `testing` feature to use them outside of this crate.
+use crate::prelude::*;
+use core::fmt::{self, Debug, Display};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::adjacency_list::export::graphiz::{export_graphiz, GraphizSettings};
+use crate::adjacency_list::import::graphiz::parse_graphiz;
+use crate::adjacency_list::AdjListGraph;
+
+pub mod golden;
+
+/// The nodes and edges that are only in one of two graphs.
+///
+/// Nodes are compared by value and edges by the values of their nodes and their weight, so IDs and removed slots are ignored.
+/// Duplicate values are counted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphDiff<T> {
+    /// In the expected graph but not the actual one.
+    pub missing_nodes: Vec<T>,
+    /// In the actual graph but not the expected one.
+    pub extra_nodes: Vec<T>,
+    /// In the expected graph but not the actual one.
+    pub missing_edges: Vec<(T, T, u32)>,
+    /// In the actual graph but not the expected one.
+    pub extra_edges: Vec<(T, T, u32)>,
+}
+impl<T: Ord + Clone> GraphDiff<T> {
+    pub fn new(expected: &AdjListGraph<T>, actual: &AdjListGraph<T>) -> Self {
+        let (missing_nodes, extra_nodes) = difference(sorted_nodes(expected), sorted_nodes(actual));
+        let (missing_edges, extra_edges) = difference(sorted_edges(expected), sorted_edges(actual));
+        Self {
+            missing_nodes,
+            extra_nodes,
+            missing_edges,
+            extra_edges,
+        }
+    }
+    /// True if the graphs are equal.
+    pub fn is_empty(&self) -> bool {
+        self.missing_nodes.is_empty()
+            && self.extra_nodes.is_empty()
+            && self.missing_edges.is_empty()
+            && self.extra_edges.is_empty()
+    }
+}
+impl<T: Debug> Display for GraphDiff<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for node in &self.missing_nodes {
+            writeln!(f, "- node {node:?}")?;
+        }
+        for node in &self.extra_nodes {
+            writeln!(f, "+ node {node:?}")?;
+        }
+        for (a, b, weight) in &self.missing_edges {
+            writeln!(f, "- edge {a:?} -- {b:?} [weight={weight}]")?;
+        }
+        for (a, b, weight) in &self.extra_edges {
+            writeln!(f, "+ edge {a:?} -- {b:?} [weight={weight}]")?;
+        }
+        Ok(())
+    }
+}
+fn sorted_nodes<T: Ord + Clone>(graph: &AdjListGraph<T>) -> Vec<T> {
+    let mut nodes: Vec<T> = graph.iter_nodes().map(|(_, value)| value.clone()).collect();
+    nodes.sort();
+    nodes
+}
+fn sorted_edges<T: Ord + Clone>(graph: &AdjListGraph<T>) -> Vec<(T, T, u32)> {
+    let mut edges: Vec<(T, T, u32)> = graph
+        .edge_ids()
+        .map(|id| {
+            let edge = &graph[id];
+            let a = graph[edge.node_a].value();
+            let b = graph[edge.node_b].value();
+            (a.min(b).clone(), a.max(b).clone(), edge.weight())
+        })
+        .collect();
+    edges.sort();
+    edges
+}
+/// Splits two sorted lists into the items only in `expected` and the items only in `actual`.
+fn difference<T: Ord>(expected: Vec<T>, actual: Vec<T>) -> (Vec<T>, Vec<T>) {
+    let mut missing = Vec::new();
+    let mut extra = Vec::new();
+    let mut expected = expected.into_iter().peekable();
+    let mut actual = actual.into_iter().peekable();
+    loop {
+        match (expected.peek(), actual.peek()) {
+            (Some(a), Some(b)) if a == b => {
+                expected.next();
+                actual.next();
+            }
+            (Some(a), Some(b)) if a < b => missing.extend(expected.next()),
+            (Some(_), Some(_)) | (None, Some(_)) => extra.extend(actual.next()),
+            (Some(_), None) => missing.extend(expected.next()),
+            (None, None) => break,
+        }
+    }
+    (missing, extra)
+}
+/// Panics with the [GraphDiff] if the graphs are not equal.
+#[track_caller]
+pub fn assert_same_graph<T>(expected: &AdjListGraph<T>, actual: &AdjListGraph<T>)
+where
+    T: Ord + Clone + Debug,
+{
+    let diff = GraphDiff::new(expected, actual);
+    if !diff.is_empty() {
+        panic!("The graphs are not equal\n{diff}");
+    }
+}
+/// Serializes the graph to JSON, deserializes it again and asserts the result is equal to `graph`.
+#[track_caller]
+pub fn assert_round_trips_json<T>(graph: &AdjListGraph<T>)
+where
+    T: Serialize + DeserializeOwned + Ord + Clone + Debug,
+{
+    let json = serde_json::to_string(graph).expect("Failed to serialize graph");
+    let round_trip: AdjListGraph<T> =
+        serde_json::from_str(&json).expect("Failed to deserialize graph");
+    assert!(
+        !round_trip.has_dead_nodes() && !round_trip.has_dead_edges(),
+        "The deserialized graph has removed slots"
+    );
+    assert_same_graph(graph, &round_trip);
+}
+/// Exports the graph to DOT with the weights, parses it again and asserts the result is equal to `graph`.
+///
+/// The parsed graph has the [Display] output of the values as its values, so every value must display differently.
+/// Values with backslashes or line breaks do not round trip, as the parser keeps their escapes.
+#[track_caller]
+pub fn assert_round_trips_dot<T>(graph: &AdjListGraph<T>)
+where
+    T: Display,
+{
+    let settings = GraphizSettings {
+        show_weights: true,
+        ..Default::default()
+    };
+    let dot = export_graphiz(graph, &settings);
+    let round_trip = parse_graphiz(&dot)
+        .unwrap_or_else(|error| panic!("Failed to parse the DOT: {error}\n{dot}"));
+    let mut expected = AdjListGraph::with_policy(graph.policy());
+    let mut ids = vec![None; graph.nodes.len()];
+    for (id, value) in graph.iter_nodes() {
+        ids[id.0] = Some(expected.add_node(value.to_string()));
+    }
+    for (a, b, weight) in graph.unique_edges() {
+        let (a, b) = (ids[a.0].unwrap(), ids[b.0].unwrap());
+        expected
+            .connect_nodes_with_weight(a, b, weight)
+            .expect("The copy has the policy of the graph, which allowed the edge");
+    }
+    assert_same_graph(&expected, &round_trip);
+}
+
+#[cfg(test)]
+mod tests {
+    use tux_graph_macros::graph_no_import;
+
+    use super::*;
+    use crate::adjacency_list::*;
+
+    fn example() -> AdjListGraph<String> {
+        graph_no_import! {
+            a [value="A".to_string()];
+            b [value="B".to_string()];
+            c [value="C".to_string()];
+
+            a -- b [weight=1];
+            b -- c [weight=2];
+        }
+    }
+    #[test]
+    pub fn diff_output() {
+        let expected = example();
+        let json = serde_json::to_string(&expected)
+            .unwrap()
+            .replace("\"weight\":2", "\"weight\":7");
+        let actual: AdjListGraph<String> = serde_json::from_str(&json).unwrap();
+
+        let diff = GraphDiff::new(&expected, &actual);
+        assert_eq!(
+            diff.to_string(),
+            "- edge \"B\" -- \"C\" [weight=2]\n+ edge \"B\" -- \"C\" [weight=7]\n"
+        );
+        assert!(GraphDiff::new(&expected, &expected).is_empty());
+    }
+    #[test]
+    #[should_panic(expected = "The graphs are not equal")]
+    pub fn dot_round_trip_mismatch() {
+        // The parser keeps the escaped backslash, so the value comes back as `A\\B`.
+        let graph = graph_no_import! {
+            a [value="A\\B".to_string()];
+            b [value="C".to_string()];
+
+            a -- b [weight=1];
+        };
+        assert_round_trips_dot(&graph);
+    }
+    #[test]
+    #[should_panic(expected = "- node \"C\"\n+ node \"D\"")]
+    pub fn corrupted_json() {
+        let expected = example();
+        let json = serde_json::to_string(&expected)
+            .unwrap()
+            .replace("\"C\"", "\"D\"");
+        let actual: AdjListGraph<String> = serde_json::from_str(&json).unwrap();
+        assert_same_graph(&expected, &actual);
+    }
+}
@@ -0,0 +1,182 @@
+//! Compares generated text against files checked in under `test/golden/`.
+//!
+//! Run the tests with `UPDATE_GOLDEN=1` to write the current output instead of comparing against it.
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::prelude::*;
+
+/// Set this environment variable to `1` to write the golden files instead of comparing against them.
+pub const UPDATE_ENV: &str = "UPDATE_GOLDEN";
+/// The unchanged lines shown around each change in [unified_diff].
+const CONTEXT: usize = 3;
+
+/// The path of the golden file `name`, inside `test/golden/` of the crate being tested.
+pub fn golden_path(name: &str) -> PathBuf {
+    let root = env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    root.join("test").join("golden").join(name)
+}
+/// Panics with a [unified_diff] if `content` is not equal to the golden file `name`.
+///
+/// `name` is a path relative to `test/golden/`, such as `mst/kruskal/tree.dot`.
+/// If [UPDATE_ENV] is set to `1`, the file is written with `content` instead.
+#[track_caller]
+pub fn assert_matches_golden(name: &str, content: &str) {
+    let path = golden_path(name);
+    if env::var(UPDATE_ENV).is_ok_and(|value| value == "1") {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create the golden directory");
+        }
+        fs::write(&path, content).expect("Failed to write the golden file");
+        return;
+    }
+    let expected = match fs::read_to_string(&path) {
+        // Git may have checked the file out with Windows line endings.
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(error) => panic!(
+            "Failed to read the golden file {}: {error}. Run with {UPDATE_ENV}=1 to create it",
+            path.display()
+        ),
+    };
+    if expected != content {
+        let mut diff = unified_diff(&expected, content);
+        if diff.is_empty() {
+            diff = "Only the newline at the end differs\n".to_string();
+        }
+        panic!(
+            "{name} does not match the golden file. Run with {UPDATE_ENV}=1 to update it\n--- {name} (golden)\n+++ {name} (actual)\n{diff}"
+        );
+    }
+}
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+/// The hunks of a unified diff from `expected` to `actual`, without the file headers.
+///
+/// Returns an empty string if the lines are equal.
+pub fn unified_diff(expected: &str, actual: &str) -> String {
+    let lines = diff_lines(
+        &expected.lines().collect::<Vec<_>>(),
+        &actual.lines().collect::<Vec<_>>(),
+    );
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let mut output = String::new();
+    let mut change = 0;
+    while change < changes.len() {
+        let start = changes[change].saturating_sub(CONTEXT);
+        let mut end = changes[change] + 1;
+        // Changes whose context would overlap share a hunk.
+        while changes
+            .get(change + 1)
+            .is_some_and(|next| *next <= end + 2 * CONTEXT)
+        {
+            change += 1;
+            end = changes[change] + 1;
+        }
+        change += 1;
+        let end = (end + CONTEXT).min(lines.len());
+        let old_lines = |range: &[DiffLine]| {
+            range
+                .iter()
+                .filter(|line| !matches!(line, DiffLine::Added(_)))
+                .count()
+        };
+        let new_lines = |range: &[DiffLine]| {
+            range
+                .iter()
+                .filter(|line| !matches!(line, DiffLine::Removed(_)))
+                .count()
+        };
+        let hunk = &lines[start..end];
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_lines(&lines[..start]), old_lines(hunk)),
+            hunk_range(new_lines(&lines[..start]), new_lines(hunk)),
+        ));
+        for line in hunk {
+            let (prefix, text) = match line {
+                DiffLine::Same(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            output.push(prefix);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+/// `start,len` of a hunk. An empty hunk starts at the line before it.
+fn hunk_range(before: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{before},0")
+    } else {
+        format!("{},{len}", before + 1)
+    }
+}
+/// A line diff through the longest common subsequence. Removed lines come before added ones.
+fn diff_lines<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // `common[i][j]` is the length of the longest common subsequence of `expected[i..]` and `actual[j..]`.
+    let mut common = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(expected.len().max(actual.len()));
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push(DiffLine::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || common[i + 1][j] >= common[i][j + 1])
+        {
+            lines.push(DiffLine::Removed(expected[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(actual[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    pub fn diff_hunks() {
+        let expected: String = (1..=20).map(|line| format!("{line}\n")).collect();
+        let actual: String = (1..=20)
+            .filter_map(|line| match line {
+                2 => Some("two\n".to_string()),
+                5 => None,
+                18 => Some("18\n18.5\n".to_string()),
+                _ => Some(format!("{line}\n")),
+            })
+            .collect();
+        assert_eq!(
+            unified_diff(&expected, &actual),
+            "@@ -1,8 +1,7 @@\n 1\n-2\n+two\n 3\n 4\n-5\n 6\n 7\n 8\n@@ -16,5 +15,6 @@\n 16\n 17\n 18\n+18.5\n 19\n 20\n"
+        );
+        assert_eq!(unified_diff(&expected, &expected), "");
+        assert_eq!(unified_diff("", "a\n"), "@@ -0,0 +1,1 @@\n+a\n");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::prelude::*;
+pub(crate) mod logging;
 pub mod macros;
 /// Returns true if the slice contains the item.
 ///
@@ -6,6 +8,30 @@ pub mod macros;
 pub fn binary_search_contains<T: Ord>(slice: &[T], item: &T) -> bool {
     slice.binary_search(item).is_ok()
 }
+/// Removes the values at the dead indexes. The remaining values keep their order.
+///
+/// `moved` is called with the old index, the new index and the value for every value whose index changed.
+///
+/// This is the compaction used for both the nodes and the edges of a graph.
+pub fn remove_dead_slots<T, F>(values: &mut Vec<T>, mut dead: Vec<usize>, mut moved: F)
+where
+    F: FnMut(usize, usize, &T),
+{
+    dead.sort_unstable();
+    dead.dedup();
+    // Everything before the first dead slot stays where it is.
+    let first_dead = dead.first().copied().unwrap_or(values.len());
+    let mut new_index = first_dead;
+    for old_index in first_dead..values.len() {
+        if dead.binary_search_contains(&old_index) {
+            continue;
+        }
+        values.swap(old_index, new_index);
+        moved(old_index, new_index, &values[new_index]);
+        new_index += 1;
+    }
+    values.truncate(new_index);
+}
 pub trait ExtendedVec<T> {
     fn binary_search_contains(&self, item: &T) -> bool
     where
@@ -36,4 +62,32 @@ impl<T> ExtendedVec<T> for Vec<T> {
 }
 pub trait IdType {
     fn from_usize(id: usize) -> Self;
+    fn as_usize(&self) -> usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_dead_slots;
+
+    #[test]
+    pub fn remove_dead_slots_keeps_order() {
+        let mut values = vec!['a', 'b', 'c', 'd', 'e', 'f'];
+        let mut moves = Vec::new();
+        remove_dead_slots(&mut values, vec![3, 1, 3], |old, new, value| {
+            moves.push((old, new, *value))
+        });
+        assert_eq!(values, vec!['a', 'c', 'e', 'f']);
+        assert_eq!(moves, vec![(2, 1, 'c'), (4, 2, 'e'), (5, 3, 'f')]);
+    }
+    #[test]
+    pub fn remove_dead_slots_without_dead() {
+        let mut values = vec![1, 2, 3];
+        remove_dead_slots(&mut values, vec![], |_, _, _| panic!("Nothing should move"));
+        assert_eq!(values, vec![1, 2, 3]);
+
+        remove_dead_slots(&mut values, vec![2], |_, _, _| {
+            panic!("Nothing should move")
+        });
+        assert_eq!(values, vec![1, 2]);
+    }
 }
@@ -0,0 +1,70 @@
+//! Re-exports the `tracing` macros used by the crate.
+//!
+//! Without the `tracing` feature they are replaced with no-ops so call sites do not need their own `cfg`.
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::{debug, debug_span, trace, trace_span};
+
+#[cfg(not(feature = "tracing"))]
+mod noop {
+    /// Stand-in for `tracing::Span` so `span!(..).entered()` keeps compiling.
+    pub(crate) struct NoopSpan;
+    impl NoopSpan {
+        #[inline]
+        pub(crate) fn entered(self) -> Self {
+            self
+        }
+    }
+    /// Borrows every field value so they do not become unused variables.
+    macro_rules! fields {
+        () => {};
+        ($message:literal $(, $arg:expr)* $(,)?) => {
+            let _ = ($(&$arg,)*);
+        };
+        ($name:ident = %$value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $($crate::utils::logging::fields!($($rest)*);)?
+        };
+        ($name:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $($crate::utils::logging::fields!($($rest)*);)?
+        };
+        ($name:ident = $value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $($crate::utils::logging::fields!($($rest)*);)?
+        };
+        (%$name:ident $(, $($rest:tt)*)?) => {
+            let _ = &$name;
+            $($crate::utils::logging::fields!($($rest)*);)?
+        };
+        (?$name:ident $(, $($rest:tt)*)?) => {
+            let _ = &$name;
+            $($crate::utils::logging::fields!($($rest)*);)?
+        };
+        ($name:ident $(, $($rest:tt)*)?) => {
+            let _ = &$name;
+            $($crate::utils::logging::fields!($($rest)*);)?
+        };
+    }
+    macro_rules! event {
+        ($($fields:tt)*) => {
+            if false {
+                $crate::utils::logging::fields!($($fields)*);
+            }
+        };
+    }
+    macro_rules! span {
+        ($name:literal $(, $($fields:tt)*)?) => {{
+            if false {
+                $($crate::utils::logging::fields!($($fields)*);)?
+            }
+            $crate::utils::logging::NoopSpan
+        }};
+    }
+    pub(crate) use event as debug;
+    pub(crate) use event as trace;
+    pub(crate) use fields;
+    pub(crate) use span as debug_span;
+    pub(crate) use span as trace_span;
+}
+#[cfg(not(feature = "tracing"))]
+pub(crate) use noop::*;
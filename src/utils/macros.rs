@@ -2,25 +2,25 @@ macro_rules! id_type {
     (
         $ty:ident
     ) => {
-        impl std::cmp::PartialEq for $ty {
+        impl core::cmp::PartialEq for $ty {
             fn eq(&self, other: &$ty) -> bool {
                 self.0 == other.0
             }
         }
-        impl std::cmp::PartialEq<$ty> for usize {
+        impl core::cmp::PartialEq<$ty> for usize {
             fn eq(&self, other: &$ty) -> bool {
                 *self == other.0
             }
         }
-        impl std::cmp::PartialEq<usize> for $ty {
+        impl core::cmp::PartialEq<usize> for $ty {
             fn eq(&self, other: &usize) -> bool {
                 self.0 == *other
             }
         }
-        impl std::cmp::Eq for $ty {}
+        impl core::cmp::Eq for $ty {}
         #[allow(clippy::non_canonical_partial_ord_impl)]
         impl core::cmp::PartialOrd for $ty {
-            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
                 self.0.partial_cmp(&other.0)
             }
         }
@@ -31,17 +31,17 @@ macro_rules! id_type {
         }
 
         impl core::cmp::PartialOrd<usize> for $ty {
-            fn partial_cmp(&self, other: &usize) -> Option<std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &usize) -> Option<core::cmp::Ordering> {
                 self.0.partial_cmp(other)
             }
         }
         impl core::cmp::PartialOrd<$ty> for usize {
-            fn partial_cmp(&self, other: &$ty) -> Option<std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &$ty) -> Option<core::cmp::Ordering> {
                 self.partial_cmp(&other.0)
             }
         }
-        impl std::hash::Hash for $ty {
-            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        impl core::hash::Hash for $ty {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
                 self.0.hash(state);
             }
         }
@@ -49,6 +49,9 @@ macro_rules! id_type {
             fn from_usize(id: usize) -> Self {
                 Self(id)
             }
+            fn as_usize(&self) -> usize {
+                self.0
+            }
         }
         impl serde::Serialize for $ty {
             fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
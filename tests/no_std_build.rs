@@ -0,0 +1,52 @@
+//! Uses the graph from a `no_std` crate, so the API used here only needs `core` and `alloc`.
+//!
+//! Integration tests link the library built with its default features, so this does not check that the crate builds without `std`.
+//! The `no-std` CI job does that by building it for `thumbv7em-none-eabi`, a target that has no `std`.
+#![no_std]
+extern crate alloc;
+
+use alloc::vec;
+use tux_graph::{adjacency_list::AdjListGraph, graph, static_graph};
+
+#[test]
+fn graph_without_std() {
+    let mut graph: AdjListGraph<u32> = graph! {
+        a [value = 1];
+        b [value = 2];
+        c [value = 3];
+
+        a -- b [weight = 1];
+        b -- c [weight = 2];
+        a -- c [weight = 5];
+    };
+    let mst = graph.kruskal_find_mst().unwrap();
+    assert_eq!(mst.number_of_edges(), 2);
+
+    let path = graph.dfs(|value| *value == 3).unwrap();
+    assert_eq!(path.len(), 3);
+
+    let first = graph.find_node_with_that_equals(&1).unwrap();
+    graph.remove_node(first);
+    graph.remove_dead_values();
+    assert_eq!(
+        graph
+            .iter_nodes()
+            .map(|(_, value)| *value)
+            .collect::<alloc::vec::Vec<_>>(),
+        vec![2, 3]
+    );
+}
+#[test]
+fn static_graph_without_std() {
+    let graph = static_graph! {
+        keyed;
+        a [value = 1];
+        b [value = 2];
+        a -- b [weight = 4];
+    };
+    assert_eq!(graph.number_of_edges(), 1);
+    assert_eq!(
+        graph.find_by_key("b").map(|node| graph[node].value().value),
+        Some(2)
+    );
+}
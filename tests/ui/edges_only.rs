@@ -0,0 +1,8 @@
+use tux_graph::graph;
+
+fn main() {
+    let _graph = graph! {
+        a -- b [weight=1];
+        b -- c;
+    };
+}
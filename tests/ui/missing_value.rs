@@ -0,0 +1,9 @@
+use tux_graph::graph;
+
+fn main() {
+    let _graph = graph! {
+        a [];
+        b [value=2];
+        a -- b;
+    };
+}
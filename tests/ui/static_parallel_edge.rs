@@ -0,0 +1,10 @@
+use tux_graph::static_graph;
+
+fn main() {
+    let _graph = static_graph! {
+        a [value=1];
+        b [value=2];
+        a -- b [weight=1];
+        b -- a [weight=2];
+    };
+}
@@ -0,0 +1,8 @@
+use tux_graph::graph;
+
+fn main() {
+    let _graph = graph! {
+        a [value=1] extra;
+        b [value=2];
+    };
+}
@@ -0,0 +1,10 @@
+use tux_graph::graph;
+
+fn main() {
+    let _graph = graph! {
+        a -- b;
+        a [value=1];
+        a -- c [weight=2];
+        b [value=2];
+    };
+}
@@ -0,0 +1,9 @@
+use tux_graph::graph;
+
+fn main() {
+    let _graph = graph! {
+        a [value=1];
+        b [weight=2];
+        a -- b;
+    };
+}
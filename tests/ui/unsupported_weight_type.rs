@@ -0,0 +1,8 @@
+use tux_graph::graph;
+
+fn main() {
+    let _graph = graph! {
+        weight_type = f64;
+        a [value=1];
+    };
+}
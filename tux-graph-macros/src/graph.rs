@@ -1,9 +1,11 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse::Parse, Error, Expr, Ident, LitInt, Result};
+use syn::{parse::Parse, Error, Expr, ExprLit, Ident, Lit, Result, Type};
 mod kw {
     syn::custom_keyword!(weight);
     syn::custom_keyword!(value);
+    syn::custom_keyword!(weight_type);
+    syn::custom_keyword!(keyed);
 }
 /// The input for the graph macro
 ///
@@ -19,7 +21,14 @@ mod kw {
 ///   node_2 -- node_4 [weight=3];
 ///}
 /// ```
+///
+/// Nodes and edges can be written in any order.
+///
+/// Optional directives can come first:
+/// - `weight_type = u32;` `u32` is currently the only weight type.
+/// - `keyed;` stores every node as a `Keyed` value with the name of its binding as the key.
 pub struct GraphInput {
+    keyed: bool,
     nodes: Vec<Node>,
     edges: Vec<Edge>,
 }
@@ -27,29 +36,96 @@ impl Parse for GraphInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut nodes = Vec::new();
         let mut edges = Vec::new();
+        let mut keyed = false;
+        loop {
+            if input.peek(kw::weight_type) && input.peek2(syn::Token![=]) {
+                parse_weight_type(input)?;
+            } else if input.peek(kw::keyed) && input.peek2(syn::Token![;]) {
+                input.parse::<kw::keyed>()?;
+                input.parse::<syn::Token![;]>()?;
+                keyed = true;
+            } else {
+                break;
+            }
+        }
         while !input.is_empty() {
             let key: Ident = input.parse()?;
             // If the next is is a `-` then we have an edge
-            if input.peek(syn::Token![-]) {
-                edges.push(parse_edge(input, key)?);
+            let declared = if input.peek(syn::Token![-]) {
+                let edge = parse_edge(input, key)?;
+                let declared = format!("the edge `{} -- {}`", edge.node_a, edge.node_b);
+                edges.push(edge);
+                declared
             } else {
+                if !input.peek(syn::token::Bracket) {
+                    return Err(Error::new(
+                        input.span(),
+                        format!("expected `[value=...]` or `-- node` after `{key}`"),
+                    ));
+                }
                 // Parse `,` separated key value pairs
                 let content;
                 syn::bracketed!(content in input);
                 let NodeAttributes { value } = content.parse()?;
+                let Some(value) = value else {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("node `{key}` has no value. Example: `{key} [value=1];`"),
+                    ));
+                };
+                let declared = format!("the node `{key}`");
                 nodes.push(Node { key, value });
+                declared
+            };
+            if !input.peek(syn::Token![;]) {
+                return Err(Error::new(
+                    input.span(),
+                    format!("expected `;` after {declared}"),
+                ));
             }
             input.parse::<syn::Token![;]>()?;
         }
-        Ok(Self { nodes, edges })
+        if let (true, Some(first_edge)) = (nodes.is_empty(), edges.first()) {
+            // Without this, every edge would produce its own unresolved binding error.
+            return Err(Error::new(
+                first_edge.node_a.span(),
+                "no nodes declared. Declare every connected node. Example: `a [value=1];`",
+            ));
+        }
+        Ok(Self {
+            keyed,
+            nodes,
+            edges,
+        })
+    }
+}
+/// Parses `weight_type = u32;`
+///
+/// The graph weights are always `u32`. So any other type is rejected here instead of failing inside the expansion.
+fn parse_weight_type(input: syn::parse::ParseStream) -> Result<()> {
+    input.parse::<kw::weight_type>()?;
+    input.parse::<syn::Token![=]>()?;
+    let weight_type: Type = input.parse()?;
+    input.parse::<syn::Token![;]>()?;
+    match &weight_type {
+        Type::Path(path) if path.qself.is_none() && path.path.is_ident("u32") => Ok(()),
+        _ => Err(Error::new_spanned(
+            weight_type,
+            "unsupported weight type. Edge weights are `u32`",
+        )),
     }
 }
-
 fn parse_edge(input: &syn::parse::ParseBuffer<'_>, node_a: Ident) -> Result<Edge> {
     input.parse::<syn::Token![-]>()?;
+    if !input.peek(syn::Token![-]) {
+        return Err(Error::new(
+            input.span(),
+            format!("expected `--`. Edges are written like `{node_a} -- b;`"),
+        ));
+    }
     input.parse::<syn::Token![-]>()?;
     let node_b: Ident = input.parse()?;
-    if input.peek(syn::Token![;]) {
+    if !input.peek(syn::token::Bracket) {
         Ok(Edge {
             weight: None,
             node_a,
@@ -75,24 +151,22 @@ pub struct Node {
     key: Ident,
     value: Expr,
 }
+/// The value is checked by the caller, which knows the name of the node.
 struct NodeAttributes {
-    value: Expr,
+    value: Option<Expr>,
 }
 impl Parse for NodeAttributes {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut value = None;
         while !input.is_empty() {
+            if !input.peek(kw::value) {
+                return Err(input.error("unknown node attribute. Nodes only take `value`"));
+            }
             let _ = input.parse::<kw::value>()?;
             input.parse::<syn::Token![=]>()?;
             let value_parse: Expr = input.parse()?;
             value = Some(value_parse);
         }
-        let Some(value) = value else {
-            return Err(Error::new(
-                input.span(),
-                "Expected value attribute for node",
-            ));
-        };
         Ok(Self { value })
     }
 }
@@ -102,34 +176,55 @@ impl Parse for NodeAttributes {
 /// {{node_a}} - {{node_b}} [weight={{weight}}]
 /// ```
 pub struct Edge {
-    weight: Option<LitInt>,
+    weight: Option<Expr>,
     node_a: Ident,
     node_b: Ident,
 }
 
 struct EdgeAttributes {
-    weight: Option<LitInt>,
+    weight: Option<Expr>,
 }
 impl Parse for EdgeAttributes {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut weight = None;
         while !input.is_empty() {
+            if !input.peek(kw::weight) {
+                return Err(input.error("unknown edge attribute. Edges only take `weight`"));
+            }
             let _ = input.parse::<kw::weight>()?;
             input.parse::<syn::Token![=]>()?;
-            let value: LitInt = input.parse()?;
+            let value: Expr = input.parse()?;
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Float(float),
+                ..
+            }) = &value
+            {
+                return Err(Error::new(
+                    float.span(),
+                    "floating point weights are not supported. Edge weights are `u32`",
+                ));
+            }
             weight = Some(value);
         }
         Ok(Self { weight })
     }
 }
-fn expand_nodes(nodes: &[Node]) -> Vec<TokenStream> {
+fn expand_nodes(nodes: &[Node], keyed: bool) -> Vec<TokenStream> {
     nodes
         .iter()
         .map(|node| {
             let key = &node.key;
             let value = &node.value;
-            quote! {
-               let #key = graph.add_node(#value);
+            if keyed {
+                // Keys are often written like the data they name, `NYC` for example.
+                quote! {
+                    #[allow(non_snake_case)]
+                    let #key = graph.add_keyed_node(stringify!(#key), #value);
+                }
+            } else {
+                quote! {
+                   let #key = graph.add_node(#value);
+                }
             }
         })
         .collect()
@@ -152,44 +247,186 @@ fn expand_edges(edges: &[Edge]) -> Vec<TokenStream> {
         })
         .collect()
 }
-pub fn expand_no_inputs(input: GraphInput) -> Result<TokenStream> {
-    let GraphInput { nodes, edges } = input;
-    let expanded_nodes: Vec<_> = expand_nodes(&nodes);
+/// Every node binding comes before the edges, so nodes can be declared after the edges that use them.
+fn expand_body(input: GraphInput) -> Result<TokenStream> {
+    let GraphInput {
+        keyed,
+        nodes,
+        edges,
+    } = input;
+    for edge in &edges {
+        // Checked here so an unknown node is not reported as an unresolved binding inside the expansion.
+        node_index(&nodes, &edge.node_a)?;
+        node_index(&nodes, &edge.node_b)?;
+    }
+    let expanded_nodes: Vec<_> = expand_nodes(&nodes, keyed);
     let expanded_edges: Vec<_> = expand_edges(&edges);
     // TODO: Ensure no duplicate edges
-    let result = quote! {
+    Ok(quote! {
+        let mut graph = AdjListGraph::default();
+        #(#expanded_nodes)*
+        #(#expanded_edges)*
+        graph
+    })
+}
+pub fn expand_no_inputs(input: GraphInput) -> Result<TokenStream> {
+    let body = expand_body(input)?;
+    Ok(quote! {
         {
-            let mut graph = AdjListGraph::default();
-            #(#expanded_nodes)*
-            #(#expanded_edges)*
-            graph
+            #body
         }
-    };
-
-    Ok(result)
+    })
 }
 
 pub fn expand(input: GraphInput) -> Result<TokenStream> {
-    let GraphInput { nodes, edges } = input;
-    let expanded_nodes: Vec<_> = expand_nodes(&nodes);
-    let expanded_edges: Vec<_> = expand_edges(&edges);
-    // TODO: Ensure no duplicate edges
-    let result = quote! {
+    let body = expand_body(input)?;
+    Ok(quote! {
         {
             use tux_graph::adjacency_list::AdjListGraph;
-            let mut graph = AdjListGraph::default();
-            #(#expanded_nodes)*
-            #(#expanded_edges)*
-            graph
+            #body
         }
-    };
+    })
+}
+/// The position of the node in the declarations, which is also its ID in the graph.
+fn node_index(nodes: &[Node], key: &Ident) -> Result<usize> {
+    nodes
+        .iter()
+        .position(|node| node.key == *key)
+        .ok_or_else(|| Error::new(key.span(), format!("node `{key}` is not declared")))
+}
 
-    Ok(result)
+/// Expands to a single [AdjListGraph::from_raw_parts] call.
+///
+/// Node IDs are the declaration order, so unknown nodes and repeated edges are caught here instead of at runtime.
+pub fn expand_static(input: GraphInput) -> Result<TokenStream> {
+    let GraphInput {
+        keyed,
+        nodes,
+        edges,
+    } = input;
+    let index_of = |key: &Ident| node_index(&nodes, key);
+    let values: Vec<TokenStream> = nodes
+        .iter()
+        .map(|Node { key, value }| {
+            if keyed {
+                quote! {
+                    tux_graph::adjacency_list::Keyed {
+                        key: ::core::convert::Into::into(stringify!(#key)),
+                        value: #value,
+                    }
+                }
+            } else {
+                quote!(#value)
+            }
+        })
+        .collect();
+    let mut connected: Vec<(usize, usize)> = Vec::with_capacity(edges.len());
+    let mut expanded_edges = Vec::with_capacity(edges.len());
+    for edge in &edges {
+        let node_a = index_of(&edge.node_a)?;
+        let node_b = index_of(&edge.node_b)?;
+        let pair = (node_a.min(node_b), node_a.max(node_b));
+        if connected.contains(&pair) {
+            return Err(Error::new(
+                edge.node_a.span(),
+                format!(
+                    "`{}` and `{}` are already connected. Parallel edges are not supported",
+                    edge.node_a, edge.node_b
+                ),
+            ));
+        }
+        connected.push(pair);
+        let weight = edge
+            .weight
+            .as_ref()
+            .map_or_else(|| quote!(0), |weight| quote!(#weight));
+        expanded_edges.push(quote! {
+            (
+                tux_graph::adjacency_list::NodeID(#node_a),
+                tux_graph::adjacency_list::NodeID(#node_b),
+                #weight,
+            )
+        });
+    }
+    Ok(quote! {
+        tux_graph::adjacency_list::AdjListGraph::from_raw_parts(
+            ::core::convert::From::from([#(#values),*]),
+            ::core::convert::From::from([#(#expanded_edges),*]),
+        )
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    use proc_macro2::TokenStream;
     use quote::quote;
+
+    /// Compares the formatted expansion against `test/golden/expand/{name}.rs`.
+    ///
+    /// Run the tests with `UPDATE_GOLDEN=1` to write the current expansion instead.
+    #[track_caller]
+    fn assert_expansion(name: &str, expanded: TokenStream) {
+        let file = syn::parse2(quote! {
+            fn main() {
+                let graph = #expanded;
+            }
+        })
+        .expect("The expansion is a valid expression");
+        let expanded = prettyplease::unparse(&file);
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test/golden/expand")
+            .join(format!("{name}.rs"));
+        if env::var("UPDATE_GOLDEN").is_ok_and(|value| value == "1") {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, expanded).unwrap();
+            return;
+        }
+        let golden = fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("Failed to read {}: {error}", path.display()));
+        assert_eq!(
+            golden.replace("\r\n", "\n"),
+            expanded,
+            "The expansion of {name} changed. Run with UPDATE_GOLDEN=1 to update it"
+        );
+    }
+    #[test]
+    pub fn expansion_snapshots() {
+        let weighted = quote! {
+            a [value=1];
+            b [value=2];
+            c [value=3];
+            a -- b [weight=1];
+            b -- c [weight=WEIGHT * 2];
+        };
+        let unweighted = quote! {
+            a [value="a"];
+            b [value="b"];
+            a -- b;
+        };
+        let single_node = quote! {
+            only [value=1];
+        };
+        let keyed = quote! {
+            keyed;
+            NYC [value=1];
+            LA [value=2];
+            NYC -- LA [weight=5];
+        };
+        for (name, input) in [
+            ("weighted", weighted.clone()),
+            ("unweighted", unweighted),
+            ("single_node", single_node),
+            ("keyed", keyed),
+        ] {
+            let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+            assert_expansion(name, super::expand(parsed).unwrap());
+        }
+        let parsed = syn::parse2::<super::GraphInput>(weighted).unwrap();
+        assert_expansion("static_weighted", super::expand_static(parsed).unwrap());
+    }
     #[test]
     pub fn test_graph_input_parse() {
         let input = quote! {
@@ -205,6 +442,49 @@ mod tests {
         assert_eq!(parsed.edges.len(), 3);
     }
 
+    #[test]
+    pub fn test_edges_only_graph_input_parse() {
+        let input = quote! {
+            a -- b [weight=1];
+            b -- c;
+        };
+        let error = syn::parse2::<super::GraphInput>(input).err().unwrap();
+        assert!(error.to_string().starts_with("no nodes declared"));
+
+        let empty = syn::parse2::<super::GraphInput>(quote! {}).unwrap();
+        assert!(empty.nodes.is_empty());
+    }
+    #[test]
+    pub fn test_weight_type_and_expression_weights() {
+        let input = quote! {
+            weight_type = u32;
+            a [value=1];
+            b [value=2];
+            a -- b [weight=WEIGHT * 2];
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        let expanded = super::expand(parsed).unwrap().to_string();
+        assert!(expanded.contains(&quote!(connect_nodes_with_weight(a, b, WEIGHT * 2)).to_string()));
+
+        let input = quote! {
+            weight_type = f64;
+            a [value=1];
+        };
+        let error = syn::parse2::<super::GraphInput>(input).err().unwrap();
+        assert!(error.to_string().starts_with("unsupported weight type"));
+    }
+    #[test]
+    pub fn test_keyed_graph_input_parse() {
+        let input = quote! {
+            weight_type = u32;
+            keyed;
+            NYC [value=1];
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        assert!(parsed.keyed);
+        let expanded = super::expand(parsed).unwrap().to_string();
+        assert!(expanded.contains(&quote!(add_keyed_node(stringify!(NYC), 1)).to_string()));
+    }
     #[test]
     pub fn test_invalid_graph_input_parse() {
         let input = quote! {
@@ -215,7 +495,70 @@ mod tests {
             b -- c [weight=2];
             a -- c
         };
-        let parsed = syn::parse2::<super::GraphInput>(input);
-        assert!(parsed.is_err());
+        let error = syn::parse2::<super::GraphInput>(input).err().unwrap();
+        assert_eq!(error.to_string(), "expected `;` after the edge `a -- c`");
+
+        let error = syn::parse2::<super::GraphInput>(quote!(a [];))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "node `a` has no value. Example: `a [value=1];`"
+        );
+    }
+    #[test]
+    pub fn test_static_expansion() {
+        let input = quote! {
+            a [value=1];
+            b [value=2];
+            c [value=3];
+            b -- c [weight=2];
+            a -- c;
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        let expanded = super::expand_static(parsed).unwrap().to_string();
+        assert!(expanded.contains(&quote!(from_raw_parts).to_string()));
+        assert!(!expanded.contains("connect_nodes"));
+
+        let input = quote! {
+            a [value=1];
+            b [value=2];
+            a -- b;
+            b -- a [weight=3];
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        let error = super::expand_static(parsed).err().unwrap();
+        assert!(error.to_string().contains("already connected"));
+
+        let input = quote! {
+            a [value=1];
+            a -- z;
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        let error = super::expand_static(parsed).err().unwrap();
+        assert_eq!(error.to_string(), "node `z` is not declared");
+    }
+    #[test]
+    pub fn test_interleaved_declarations() {
+        let input = quote! {
+            a [value=1];
+            a -- b [weight=1];
+            b -- c;
+            b [value=2];
+            c [value=3];
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        let expanded = super::expand(parsed).unwrap().to_string();
+        let last_node = expanded.find(&quote!(let c).to_string()).unwrap();
+        let first_edge = expanded.find("connect_nodes").unwrap();
+        assert!(last_node < first_edge);
+
+        let input = quote! {
+            a [value=1];
+            a -- z;
+        };
+        let parsed = syn::parse2::<super::GraphInput>(input).unwrap();
+        let error = super::expand_no_inputs(parsed).err().unwrap();
+        assert_eq!(error.to_string(), "node `z` is not declared");
     }
 }
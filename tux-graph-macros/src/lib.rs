@@ -19,3 +19,13 @@ pub fn graph(item: TokenStream) -> TokenStream {
         Err(e) => e.to_compile_error().into(),
     }
 }
+
+#[proc_macro]
+pub fn static_graph(item: TokenStream) -> TokenStream {
+    let parse_content = syn::parse_macro_input!(item as graph::GraphInput);
+    let output = graph::expand_static(parse_content);
+    match output {
+        Ok(output) => output.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
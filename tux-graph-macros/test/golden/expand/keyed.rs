@@ -0,0 +1,12 @@
+fn main() {
+    let graph = {
+        use tux_graph::adjacency_list::AdjListGraph;
+        let mut graph = AdjListGraph::default();
+        #[allow(non_snake_case)]
+        let NYC = graph.add_keyed_node(stringify!(NYC), 1);
+        #[allow(non_snake_case)]
+        let LA = graph.add_keyed_node(stringify!(LA), 2);
+        graph.connect_nodes_with_weight(NYC, LA, 5).unwrap();
+        graph
+    };
+}
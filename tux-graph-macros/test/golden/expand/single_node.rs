@@ -0,0 +1,8 @@
+fn main() {
+    let graph = {
+        use tux_graph::adjacency_list::AdjListGraph;
+        let mut graph = AdjListGraph::default();
+        let only = graph.add_node(1);
+        graph
+    };
+}
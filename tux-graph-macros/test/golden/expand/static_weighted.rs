@@ -0,0 +1,17 @@
+fn main() {
+    let graph = tux_graph::adjacency_list::AdjListGraph::from_raw_parts(
+        ::core::convert::From::from([1, 2, 3]),
+        ::core::convert::From::from([
+            (
+                tux_graph::adjacency_list::NodeID(0usize),
+                tux_graph::adjacency_list::NodeID(1usize),
+                1,
+            ),
+            (
+                tux_graph::adjacency_list::NodeID(1usize),
+                tux_graph::adjacency_list::NodeID(2usize),
+                WEIGHT * 2,
+            ),
+        ]),
+    );
+}
@@ -0,0 +1,10 @@
+fn main() {
+    let graph = {
+        use tux_graph::adjacency_list::AdjListGraph;
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.connect_nodes(a, b).unwrap();
+        graph
+    };
+}
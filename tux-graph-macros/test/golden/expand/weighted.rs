@@ -0,0 +1,12 @@
+fn main() {
+    let graph = {
+        use tux_graph::adjacency_list::AdjListGraph;
+        let mut graph = AdjListGraph::default();
+        let a = graph.add_node(1);
+        let b = graph.add_node(2);
+        let c = graph.add_node(3);
+        graph.connect_nodes_with_weight(a, b, 1).unwrap();
+        graph.connect_nodes_with_weight(b, c, WEIGHT * 2).unwrap();
+        graph
+    };
+}